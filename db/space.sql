@@ -25,23 +25,81 @@ DROP TABLE IF EXISTS `t_space_article`;
 CREATE TABLE `t_space_article` (
   `id` int(11) NOT NULL AUTO_INCREMENT COMMENT '文章id',
   `title` varchar(255) NOT NULL DEFAULT '未命名文档' COMMENT '文章标题',
+  `slug` varchar(128) NOT NULL COMMENT '文章别名，用于永久链接',
   `description` text NOT NULL COMMENT '文章描述',
-  `content` mediumtext NOT NULL COMMENT '文章内容',
+  `content` mediumtext NOT NULL COMMENT '文章内容（Markdown）',
   `is_top` tinyint(1) NOT NULL DEFAULT '0' COMMENT '文章是否置顶',
+  `pin_priority` int(11) NOT NULL DEFAULT '0' COMMENT '置顶优先级，越大越靠前',
   `status_type` int(11) NOT NULL DEFAULT '0' COMMENT '文章状态，0:草稿、1:已发布、2:隐藏、3:删除',
+  `publish_at` datetime DEFAULT NULL COMMENT '定时发布时间',
+  `unpublish_at` datetime DEFAULT NULL COMMENT '定时下线时间',
+  `publish_time` datetime DEFAULT NULL COMMENT '首次发布时间',
+  `view_count` bigint(20) NOT NULL DEFAULT '0' COMMENT '浏览次数',
   `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
   `update_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
   `create_user_id` int(11) NOT NULL COMMENT '创建用户id',
   `update_user_id` int(11) NOT NULL COMMENT '更新用户id',
+  `delete_time` datetime DEFAULT NULL COMMENT '删除时间',
+  `delete_user_id` int(11) DEFAULT NULL COMMENT '删除用户id',
   PRIMARY KEY (`id`),
+  UNIQUE KEY `u_slug` (`slug`),
   KEY `update_user_id` (`update_user_id`),
+  KEY `delete_user_id` (`delete_user_id`),
   KEY `i_status_type` (`status_type`),
   KEY `i_create_user_id` (`create_user_id`),
+  KEY `i_publish_at` (`publish_at`),
+  KEY `i_unpublish_at` (`unpublish_at`),
+  KEY `i_publish_time` (`publish_time`),
+  FULLTEXT KEY `ft_title_content` (`title`,`content`) /*!50100 WITH PARSER `ngram` */,
   CONSTRAINT `t_space_article_ibfk_1` FOREIGN KEY (`create_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE CASCADE,
-  CONSTRAINT `t_space_article_ibfk_2` FOREIGN KEY (`update_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE CASCADE
+  CONSTRAINT `t_space_article_ibfk_2` FOREIGN KEY (`update_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE CASCADE,
+  CONSTRAINT `t_space_article_ibfk_3` FOREIGN KEY (`delete_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE SET NULL
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='文章信息表';
 /*!40101 SET character_set_client = @saved_cs_client */;
 
+--
+-- Table structure for table `t_space_article_reaction`
+--
+
+DROP TABLE IF EXISTS `t_space_article_reaction`;
+/*!40101 SET @saved_cs_client     = @@character_set_client */;
+/*!40101 SET character_set_client = utf8 */;
+CREATE TABLE `t_space_article_reaction` (
+  `article_id` int(11) NOT NULL COMMENT '文章id',
+  `user_id` int(11) NOT NULL COMMENT '用户id',
+  `kind` varchar(32) NOT NULL COMMENT '回应类型，like或表情',
+  `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+  PRIMARY KEY (`article_id`,`user_id`,`kind`),
+  KEY `i_article_id` (`article_id`),
+  KEY `i_user_id` (`user_id`),
+  CONSTRAINT `t_space_article_reaction_ibfk_1` FOREIGN KEY (`article_id`) REFERENCES `t_space_article` (`id`) ON DELETE CASCADE,
+  CONSTRAINT `t_space_article_reaction_ibfk_2` FOREIGN KEY (`user_id`) REFERENCES `t_space_user` (`id`) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='文章回应表';
+/*!40101 SET character_set_client = @saved_cs_client */;
+
+--
+-- Table structure for table `t_space_article_revision`
+--
+
+DROP TABLE IF EXISTS `t_space_article_revision`;
+/*!40101 SET @saved_cs_client     = @@character_set_client */;
+/*!40101 SET character_set_client = utf8 */;
+CREATE TABLE `t_space_article_revision` (
+  `id` int(11) NOT NULL AUTO_INCREMENT COMMENT '修订id',
+  `article_id` int(11) NOT NULL COMMENT '文章id',
+  `title` varchar(255) NOT NULL COMMENT '文章标题快照',
+  `description` text NOT NULL COMMENT '文章描述快照',
+  `content` mediumtext NOT NULL COMMENT '文章内容快照（Markdown）',
+  `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+  `update_user_id` int(11) DEFAULT NULL COMMENT '编辑用户id，用户被清除后为NULL',
+  PRIMARY KEY (`id`),
+  KEY `i_article_id` (`article_id`),
+  KEY `update_user_id` (`update_user_id`),
+  CONSTRAINT `t_space_article_revision_ibfk_1` FOREIGN KEY (`article_id`) REFERENCES `t_space_article` (`id`) ON DELETE CASCADE,
+  CONSTRAINT `t_space_article_revision_ibfk_2` FOREIGN KEY (`update_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='文章修订历史表';
+/*!40101 SET character_set_client = @saved_cs_client */;
+
 --
 -- Table structure for table `t_space_article_tag`
 --
@@ -52,6 +110,7 @@ DROP TABLE IF EXISTS `t_space_article_tag`;
 CREATE TABLE `t_space_article_tag` (
   `article_id` int(11) NOT NULL COMMENT '文章id',
   `tag_id` int(11) NOT NULL COMMENT '标签id',
+  `pin_priority` int(11) DEFAULT NULL COMMENT '标签内置顶优先级，越大越靠前，NULL表示未置顶',
   PRIMARY KEY (`article_id`,`tag_id`),
   KEY `i_article_id` (`article_id`),
   KEY `i_tag_id` (`tag_id`),
@@ -60,6 +119,92 @@ CREATE TABLE `t_space_article_tag` (
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='文章-标签关系表';
 /*!40101 SET character_set_client = @saved_cs_client */;
 
+--
+-- Table structure for table `t_space_comment`
+--
+
+DROP TABLE IF EXISTS `t_space_comment`;
+/*!40101 SET @saved_cs_client     = @@character_set_client */;
+/*!40101 SET character_set_client = utf8 */;
+CREATE TABLE `t_space_comment` (
+  `id` int(11) NOT NULL AUTO_INCREMENT COMMENT '评论id',
+  `article_id` int(11) NOT NULL COMMENT '文章id',
+  `parent_comment_id` int(11) DEFAULT NULL COMMENT '父评论id，NULL表示顶级评论',
+  `depth` int(11) NOT NULL DEFAULT '1' COMMENT '评论层级，顶级评论为1',
+  `content` text NOT NULL COMMENT '评论内容',
+  `status_type` int(11) NOT NULL DEFAULT '0' COMMENT '评论状态，0:待审核、1:通过、2:拒绝、3:删除',
+  `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+  `update_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
+  `create_user_id` int(11) DEFAULT NULL COMMENT '创建用户id，用户被清除后为NULL',
+  PRIMARY KEY (`id`),
+  KEY `i_article_id` (`article_id`),
+  KEY `i_parent_comment_id` (`parent_comment_id`),
+  KEY `i_status_type` (`status_type`),
+  KEY `i_create_user_id` (`create_user_id`),
+  CONSTRAINT `t_space_comment_ibfk_1` FOREIGN KEY (`article_id`) REFERENCES `t_space_article` (`id`) ON DELETE CASCADE,
+  CONSTRAINT `t_space_comment_ibfk_2` FOREIGN KEY (`parent_comment_id`) REFERENCES `t_space_comment` (`id`) ON DELETE CASCADE,
+  CONSTRAINT `t_space_comment_ibfk_3` FOREIGN KEY (`create_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE SET NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='评论信息表';
+/*!40101 SET character_set_client = @saved_cs_client */;
+
+--
+-- Table structure for table `t_space_series`
+--
+
+DROP TABLE IF EXISTS `t_space_series`;
+/*!40101 SET @saved_cs_client     = @@character_set_client */;
+/*!40101 SET character_set_client = utf8 */;
+CREATE TABLE `t_space_series` (
+  `id` int(11) NOT NULL AUTO_INCREMENT COMMENT '系列id',
+  `title` varchar(255) NOT NULL COMMENT '系列标题',
+  `description` text NOT NULL COMMENT '系列描述',
+  `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+  `update_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
+  `create_user_id` int(11) NOT NULL COMMENT '创建用户id',
+  PRIMARY KEY (`id`),
+  KEY `i_create_user_id` (`create_user_id`),
+  CONSTRAINT `t_space_series_ibfk_1` FOREIGN KEY (`create_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='系列信息表';
+/*!40101 SET character_set_client = @saved_cs_client */;
+
+--
+-- Table structure for table `t_space_series_article`
+--
+
+DROP TABLE IF EXISTS `t_space_series_article`;
+/*!40101 SET @saved_cs_client     = @@character_set_client */;
+/*!40101 SET character_set_client = utf8 */;
+CREATE TABLE `t_space_series_article` (
+  `series_id` int(11) NOT NULL COMMENT '系列id',
+  `article_id` int(11) NOT NULL COMMENT '文章id',
+  `position` int(11) NOT NULL COMMENT '文章在系列中的序号，从0开始',
+  PRIMARY KEY (`series_id`,`article_id`),
+  UNIQUE KEY `u_article_id` (`article_id`),
+  KEY `i_series_id_position` (`series_id`,`position`),
+  CONSTRAINT `t_space_series_article_ibfk_1` FOREIGN KEY (`series_id`) REFERENCES `t_space_series` (`id`) ON DELETE CASCADE,
+  CONSTRAINT `t_space_series_article_ibfk_2` FOREIGN KEY (`article_id`) REFERENCES `t_space_article` (`id`) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='系列-文章关系表';
+/*!40101 SET character_set_client = @saved_cs_client */;
+
+--
+-- Table structure for table `t_space_slug_history`
+--
+
+DROP TABLE IF EXISTS `t_space_slug_history`;
+/*!40101 SET @saved_cs_client     = @@character_set_client */;
+/*!40101 SET character_set_client = utf8 */;
+CREATE TABLE `t_space_slug_history` (
+  `id` int(11) NOT NULL AUTO_INCREMENT COMMENT '记录id',
+  `resource_type` int(11) NOT NULL COMMENT '资源类型，0:文章、1:标签',
+  `resource_id` int(11) NOT NULL COMMENT '资源id',
+  `slug` varchar(128) NOT NULL COMMENT '曾用别名',
+  `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `u_resource_type_slug` (`resource_type`,`slug`),
+  KEY `i_resource_id` (`resource_id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='别名历史表';
+/*!40101 SET character_set_client = @saved_cs_client */;
+
 --
 -- Table structure for table `t_space_tag`
 --
@@ -70,6 +215,7 @@ DROP TABLE IF EXISTS `t_space_tag`;
 CREATE TABLE `t_space_tag` (
   `id` int(11) NOT NULL AUTO_INCREMENT COMMENT '标签id',
   `name` varchar(255) NOT NULL COMMENT '标签名称',
+  `slug` varchar(128) NOT NULL COMMENT '标签别名，用于永久链接',
   `parent_tag_id` int(11) DEFAULT NULL COMMENT '父标签id，NULL表示顶级标签',
   `thumbnail_url` varchar(512) DEFAULT NULL COMMENT '标签缩略图url',
   `status_type` int(11) NOT NULL DEFAULT '0' COMMENT '标签状态，0.正常、1.隐藏、2.删除',
@@ -77,17 +223,44 @@ CREATE TABLE `t_space_tag` (
   `update_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
   `create_user_id` int(11) NOT NULL COMMENT '创建用户id',
   `update_user_id` int(11) NOT NULL COMMENT '更新用户id',
+  `delete_time` datetime DEFAULT NULL COMMENT '删除时间',
+  `delete_user_id` int(11) DEFAULT NULL COMMENT '删除用户id',
   PRIMARY KEY (`id`),
+  UNIQUE KEY `u_slug` (`slug`),
   KEY `parent_tag_id` (`parent_tag_id`),
   KEY `update_user_id` (`update_user_id`),
+  KEY `delete_user_id` (`delete_user_id`),
   KEY `i_status_type` (`status_type`),
   KEY `i_create_user_id` (`create_user_id`),
   CONSTRAINT `t_space_tag_ibfk_1` FOREIGN KEY (`parent_tag_id`) REFERENCES `t_space_tag` (`id`) ON DELETE CASCADE,
   CONSTRAINT `t_space_tag_ibfk_2` FOREIGN KEY (`create_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE CASCADE,
-  CONSTRAINT `t_space_tag_ibfk_3` FOREIGN KEY (`update_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE CASCADE
+  CONSTRAINT `t_space_tag_ibfk_3` FOREIGN KEY (`update_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE CASCADE,
+  CONSTRAINT `t_space_tag_ibfk_4` FOREIGN KEY (`delete_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE SET NULL
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='标签信息表';
 /*!40101 SET character_set_client = @saved_cs_client */;
 
+--
+-- Table structure for table `t_space_upload`
+--
+
+DROP TABLE IF EXISTS `t_space_upload`;
+/*!40101 SET @saved_cs_client     = @@character_set_client */;
+/*!40101 SET character_set_client = utf8 */;
+CREATE TABLE `t_space_upload` (
+  `id` int(11) NOT NULL AUTO_INCREMENT COMMENT '上传记录id',
+  `storage_key` varchar(128) NOT NULL COMMENT '存储键，文件内容的sha256加扩展名',
+  `file_name` varchar(255) NOT NULL COMMENT '原始文件名',
+  `mime_type` varchar(64) NOT NULL COMMENT '文件类型',
+  `size` bigint(20) NOT NULL COMMENT '文件大小，单位字节',
+  `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+  `create_user_id` int(11) NOT NULL COMMENT '上传用户id',
+  PRIMARY KEY (`id`),
+  KEY `i_storage_key` (`storage_key`),
+  KEY `i_create_user_id` (`create_user_id`),
+  CONSTRAINT `t_space_upload_ibfk_1` FOREIGN KEY (`create_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='上传文件表';
+/*!40101 SET character_set_client = @saved_cs_client */;
+
 --
 -- Table structure for table `t_space_user`
 --
@@ -107,10 +280,14 @@ CREATE TABLE `t_space_user` (
   `status_type` int(11) NOT NULL DEFAULT '0' COMMENT '用户状态，0:等待、1:激活、2:禁用、3:删除',
   `create_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
   `update_time` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP COMMENT '更新时间',
+  `delete_time` datetime DEFAULT NULL COMMENT '删除时间',
+  `delete_user_id` int(11) DEFAULT NULL COMMENT '删除用户id',
   PRIMARY KEY (`id`),
   UNIQUE KEY `username` (`username`),
   KEY `i_username` (`username`),
-  KEY `i_status_type` (`status_type`)
+  KEY `i_status_type` (`status_type`),
+  KEY `delete_user_id` (`delete_user_id`),
+  CONSTRAINT `t_space_user_ibfk_1` FOREIGN KEY (`delete_user_id`) REFERENCES `t_space_user` (`id`) ON DELETE SET NULL
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='用户信息表';
 /*!40101 SET character_set_client = @saved_cs_client */;
 
@@ -0,0 +1,18 @@
+-- Indexes for article full-text search on Postgres, to be created after the tables.
+-- The indexed expressions must stay the same as the ones in ArticleDAO::select_fulltext,
+-- otherwise the planner can not use them.
+
+--
+-- Words are matched through the "simple" text search configuration
+--
+CREATE INDEX IF NOT EXISTS "ft_title_content" ON "t_space_article"
+  USING GIN (to_tsvector('simple', "title" || ' ' || "content"));
+
+--
+-- The "simple" configuration keeps a CJK run as one word, so queries containing CJK
+-- characters match substrings with LIKE instead, which pg_bigm can index. Without the
+-- extension such queries still work, but scan the whole table.
+--
+CREATE EXTENSION IF NOT EXISTS pg_bigm;
+CREATE INDEX IF NOT EXISTS "bigm_title_content" ON "t_space_article"
+  USING GIN (("title" || ' ' || "content") gin_bigm_ops);
@@ -56,6 +56,21 @@ pub enum AppErrorKind {
         props(http_code = "409", app_code = "-40901")
     )]
     UsernameConflict,
+    #[strum(
+        message = "标签层级关系冲突",
+        props(http_code = "409", app_code = "-40902")
+    )]
+    TagHierarchyConflict,
+    #[strum(
+        message = "上传文件过大",
+        props(http_code = "413", app_code = "-41300")
+    )]
+    PayloadTooLarge,
+    #[strum(
+        message = "不支持的文件类型",
+        props(http_code = "415", app_code = "-41500")
+    )]
+    UnsupportedMediaType,
     #[strum(
         message = "服务端内部错误",
         props(http_code = "500", app_code = "-50000")
@@ -77,6 +92,11 @@ pub enum AppErrorKind {
         props(http_code = "500", app_code = "-50003")
     )]
     ConfigurationError,
+    #[strum(
+        message = "存储操作错误",
+        props(http_code = "500", app_code = "-50004")
+    )]
+    StorageOperationError,
     #[strum(
         message = "未实现的功能",
         props(http_code = "501", app_code = "-50100")
@@ -1,11 +1,20 @@
 // ********************* import ********************* //
+use std::net::IpAddr;
+
 use config::{Config, Environment, File};
 use serde::Deserialize;
 
 use super::common::prelude::*;
 use crate::app::{
     db::DBConfig,
-    utils::{cache::CacheConfig, log::LogConfig},
+    service::types::{
+        article::ArticleConfig, comment::CommentConfig, site::SiteConfig, tag::TagConfig,
+        trash::TrashConfig, upload::UploadConfig,
+    },
+    utils::{
+        cache::CacheConfig, image::ImageConfig, log::LogConfig, markdown::RenderConfig,
+        search::SearchConfig, storage::StorageConfig,
+    },
 };
 
 // ********************* content ********************* //
@@ -14,6 +23,10 @@ use crate::app::{
 pub struct ServiceConfig {
     pub host: String,
     pub port: u16,
+    // public address of the site, used for absolute links in feeds
+    pub base_url: String,
+    // reverse proxies allowed to tell the client address through X-Forwarded-For
+    pub trusted_proxies: Vec<IpAddr>,
 }
 
 impl Default for ServiceConfig {
@@ -21,20 +34,42 @@ impl Default for ServiceConfig {
         Self {
             host: "localhost".into(),
             port: 8069,
+            base_url: "http://localhost:8069".into(),
+            trusted_proxies: Vec::new(),
         }
     }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct AppConfig {
+    #[serde(default)]
+    pub article: ArticleConfig,
     #[serde(default)]
     pub cache: CacheConfig,
     #[serde(default)]
+    pub comment: CommentConfig,
+    #[serde(default)]
     pub db: DBConfig,
     #[serde(default)]
+    pub image: ImageConfig,
+    #[serde(default)]
     pub log: LogConfig,
     #[serde(default)]
+    pub render: RenderConfig,
+    #[serde(default)]
+    pub search: SearchConfig,
+    #[serde(default)]
     pub service: ServiceConfig,
+    #[serde(default)]
+    pub site: SiteConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub tag: TagConfig,
+    #[serde(default)]
+    pub trash: TrashConfig,
+    #[serde(default)]
+    pub upload: UploadConfig,
 }
 
 impl AppConfig {
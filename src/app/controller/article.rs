@@ -0,0 +1,490 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query},
+    response::{IntoResponse, Redirect, Response},
+    routing::{get, patch, post, put},
+    Extension, Json, Router,
+};
+use garde::Validate;
+
+use super::{BearerToken, HandlerAsyncSafe, Visitor};
+use crate::app::{
+    common::prelude::*,
+    service::{prelude::ArticleServiceTrait, types::article::prelude::*},
+};
+
+// ********************* content ********************* //
+// router
+pub fn public_router<A>(_: &A) -> Router
+where
+    A: ArticleServiceTrait + HandlerAsyncSafe,
+{
+    Router::new()
+        .route("/", post(create::<A>))
+        .route("/search", get(search::<A>))
+        .route("/fulltext", get(fulltext_search::<A>))
+        .route("/suggest", get(suggest::<A>))
+        .route("/archive", get(archive::<A>))
+        .route("/archive/:year/:month", get(archive_search::<A>))
+        .route("/mine", get(mine_search::<A>))
+        .route("/slug/:slug", get(find_by_slug::<A>))
+        .route(
+            "/:id",
+            get(find::<A>).patch(edit::<A>).delete(delete_article::<A>),
+        )
+        .route("/:id/render", get(render::<A>))
+        .route("/:id/status", patch(change_status::<A>))
+        .route("/:id/schedule", put(schedule::<A>))
+        .route("/:id/revisions", get(revision_search::<A>))
+        .route("/:id/revisions/diff", get(revision_diff::<A>))
+        .route("/:id/revisions/:revision_id/rollback", post(rollback::<A>))
+        .route("/:id/reactions", get(reactions::<A>))
+        .route("/:id/reactions/:kind", put(react::<A>).delete(unreact::<A>))
+        .route(
+            "/:id/tags",
+            put(replace_tags::<A>)
+                .post(add_tags::<A>)
+                .delete(remove_tags::<A>),
+        )
+}
+
+pub fn admin_router<A>(_: &A) -> Router
+where
+    A: ArticleServiceTrait + HandlerAsyncSafe,
+{
+    Router::new()
+        .route("/search", get(admin_search::<A>))
+        .route("/pins", get(pins::<A>).put(replace_pins::<A>))
+        .route(
+            "/:id",
+            get(admin_find::<A>)
+                .patch(admin_edit::<A>)
+                .delete(admin_delete::<A>),
+        )
+}
+
+// handler
+async fn search<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Query(req_form): Query<ArticleSearchReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service.search(req_form).await.into()
+}
+
+async fn fulltext_search<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Query(req_form): Query<ArticleFulltextSearchReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service.fulltext_search(req_form).await.into()
+}
+
+async fn suggest<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Query(req_form): Query<ArticleSuggestReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service.suggest(req_form).await.into()
+}
+
+async fn archive<A>(Extension(article_service): Extension<Arc<A>>) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    article_service.archive().await.into()
+}
+
+async fn archive_search<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path((year, month)): Path<(i32, u32)>,
+    Query(req_form): Query<ArticleArchiveSearchReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service
+        .archive_search(year, month, req_form)
+        .await
+        .into()
+}
+
+async fn find<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    Visitor(visitor): Visitor,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    article_service.find(id, &visitor).await.into()
+}
+
+async fn find_by_slug<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(slug): Path<String>,
+    Visitor(visitor): Visitor,
+) -> Response
+where
+    A: ArticleServiceTrait,
+{
+    match article_service.find_by_slug(slug, &visitor).await {
+        Ok(ArticleSlugFindResForm::Found(res_form)) => AppResponse::succ(res_form).into_response(),
+        // relative to the requested path, so the mount point does not matter
+        Ok(ArticleSlugFindResForm::Moved(slug)) => Redirect::permanent(&slug).into_response(),
+        Err(e) => AppResponse::from(e).into_response(),
+    }
+}
+
+async fn render<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    article_service.render(id).await.into()
+}
+
+async fn mine_search<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    BearerToken(token): BearerToken,
+    Query(req_form): Query<ArticleMineSearchReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service.mine_search(&token, req_form).await.into()
+}
+
+async fn create<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<ArticleCreateReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service.create(&token, req_form).await.into()
+}
+
+async fn edit<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<ArticleEditReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service.edit(id, &token, req_form).await.into()
+}
+
+async fn change_status<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<ArticleChangeStatusReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service
+        .change_status(id, &token, req_form)
+        .await
+        .into()
+}
+
+async fn delete_article<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    article_service.delete(id, &token).await.into()
+}
+
+async fn schedule<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<ArticleScheduleReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service.schedule(id, &token, req_form).await.into()
+}
+
+async fn replace_tags<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<ArticleTagsReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service
+        .replace_tags(id, &token, req_form)
+        .await
+        .into()
+}
+
+async fn add_tags<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<ArticleTagsReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service.add_tags(id, &token, req_form).await.into()
+}
+
+async fn remove_tags<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<ArticleTagsReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service
+        .remove_tags(id, &token, req_form)
+        .await
+        .into()
+}
+
+async fn react<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path((id, kind)): Path<(i32, String)>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    article_service.react(id, kind, &token).await.into()
+}
+
+async fn unreact<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path((id, kind)): Path<(i32, String)>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    article_service.unreact(id, kind, &token).await.into()
+}
+
+// anonymous visitors get the counts only
+async fn reactions<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    token: Option<BearerToken>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    let token = token.map(|BearerToken(token)| token);
+    article_service.reactions(id, token.as_deref()).await.into()
+}
+
+async fn revision_search<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Query(req_form): Query<ArticleRevisionSearchReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service
+        .revision_search(id, &token, req_form)
+        .await
+        .into()
+}
+
+async fn revision_diff<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Query(req_form): Query<ArticleRevisionDiffReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service
+        .revision_diff(id, &token, req_form)
+        .await
+        .into()
+}
+
+async fn rollback<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path((id, revision_id)): Path<(i32, i32)>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    article_service
+        .rollback(id, revision_id, &token)
+        .await
+        .into()
+}
+
+async fn admin_search<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    BearerToken(token): BearerToken,
+    Query(req_form): Query<ArticleAdminSearchReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service.admin_search(&token, req_form).await.into()
+}
+
+async fn admin_find<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    article_service.admin_find(id, &token).await.into()
+}
+
+async fn admin_edit<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<ArticleAdminEditReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service
+        .admin_edit(id, &token, req_form)
+        .await
+        .into()
+}
+
+async fn admin_delete<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    article_service.admin_delete(id, &token).await.into()
+}
+
+async fn pins<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    BearerToken(token): BearerToken,
+    Query(req_form): Query<ArticlePinsReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service.pins(&token, req_form).await.into()
+}
+
+async fn replace_pins<A>(
+    Extension(article_service): Extension<Arc<A>>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<ArticleReplacePinsReqForm>,
+) -> AppResponse
+where
+    A: ArticleServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    article_service.replace_pins(&token, req_form).await.into()
+}
@@ -0,0 +1,135 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query},
+    routing::{get, patch, post},
+    Extension, Json, Router,
+};
+use garde::Validate;
+
+use super::{BearerToken, HandlerAsyncSafe};
+use crate::app::{
+    common::prelude::*,
+    service::{prelude::CommentServiceTrait, types::comment::prelude::*},
+};
+
+// ********************* content ********************* //
+// router
+pub fn public_router<M>(_: &M) -> Router
+where
+    M: CommentServiceTrait + HandlerAsyncSafe,
+{
+    Router::new()
+        .route("/", post(create::<M>))
+        .route("/article/:article_id", get(thread::<M>))
+        .route("/:id", patch(edit::<M>).delete(delete::<M>))
+}
+
+pub fn admin_router<M>(_: &M) -> Router
+where
+    M: CommentServiceTrait + HandlerAsyncSafe,
+{
+    Router::new()
+        .route("/search", get(admin_search::<M>))
+        .route("/approve", post(admin_approve::<M>))
+        .route("/reject", post(admin_reject::<M>))
+}
+
+// handler
+async fn thread<M>(
+    Extension(comment_service): Extension<Arc<M>>,
+    Path(article_id): Path<i32>,
+) -> AppResponse
+where
+    M: CommentServiceTrait,
+{
+    comment_service.thread(article_id).await.into()
+}
+
+async fn create<M>(
+    Extension(comment_service): Extension<Arc<M>>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<CommentCreateReqForm>,
+) -> AppResponse
+where
+    M: CommentServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    comment_service.create(&token, req_form).await.into()
+}
+
+async fn edit<M>(
+    Extension(comment_service): Extension<Arc<M>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<CommentEditReqForm>,
+) -> AppResponse
+where
+    M: CommentServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    comment_service.edit(id, &token, req_form).await.into()
+}
+
+async fn delete<M>(
+    Extension(comment_service): Extension<Arc<M>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    M: CommentServiceTrait,
+{
+    comment_service.delete(id, &token).await.into()
+}
+
+async fn admin_search<M>(
+    Extension(comment_service): Extension<Arc<M>>,
+    BearerToken(token): BearerToken,
+    Query(req_form): Query<CommentAdminSearchReqForm>,
+) -> AppResponse
+where
+    M: CommentServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    comment_service.admin_search(&token, req_form).await.into()
+}
+
+async fn admin_approve<M>(
+    Extension(comment_service): Extension<Arc<M>>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<CommentAdminModerateReqForm>,
+) -> AppResponse
+where
+    M: CommentServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    comment_service.admin_approve(&token, req_form).await.into()
+}
+
+async fn admin_reject<M>(
+    Extension(comment_service): Extension<Arc<M>>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<CommentAdminModerateReqForm>,
+) -> AppResponse
+where
+    M: CommentServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    comment_service.admin_reject(&token, req_form).await.into()
+}
@@ -1,12 +1,43 @@
 // ********************* mod ********************* //
+pub mod article;
+pub mod comment;
+pub mod series;
+pub mod site;
+pub mod tag;
+pub mod transfer;
+pub mod trash;
+pub mod upload;
 pub mod user;
 
 pub mod prelude {
+    pub use super::article::{
+        admin_router as article_admin_router, public_router as article_public_router,
+    };
+    pub use super::comment::{
+        admin_router as comment_admin_router, public_router as comment_public_router,
+    };
+    pub use super::series::public_router as series_public_router;
+    pub use super::site::router as site_router;
+    pub use super::tag::{admin_router as tag_admin_router, public_router as tag_public_router};
+    pub use super::transfer::admin_router as transfer_admin_router;
+    pub use super::trash::admin_router as trash_admin_router;
+    pub use super::upload::public_router as upload_public_router;
     pub use super::user::{admin_router as user_admin_router, public_router as user_public_router};
+    pub use super::TrustedProxies;
 }
 
 // ********************* import ********************* //
-use axum::{async_trait, extract::FromRequestParts, http::header};
+use std::{
+    convert::Infallible,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::header,
+};
 use http::request::Parts;
 
 use crate::app::common::prelude::*;
@@ -41,3 +72,83 @@ impl<S> FromRequestParts<S> for BearerToken {
         }
     }
 }
+
+// proxies whose X-Forwarded-For is believed, set on the router as an extension
+#[derive(Clone, Debug, Default)]
+pub struct TrustedProxies(pub Arc<Vec<IpAddr>>);
+
+// client address, taken from X-Forwarded-For only when the peer is a trusted proxy
+struct Visitor(pub String);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Visitor {
+    type Rejection = Infallible;
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        let forwarded = parts
+            .headers
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok());
+        let trusted = parts
+            .extensions
+            .get::<TrustedProxies>()
+            .map(|TrustedProxies(trusted)| trusted.as_slice())
+            .unwrap_or_default();
+        let visitor = visitor_ip(peer, forwarded, trusted)
+            .map(|ip| ip.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        Ok(Visitor(visitor))
+    }
+}
+
+// walks X-Forwarded-For from the right, each trusted hop vouches for the one before it, the
+// first hop that is not trusted is the client, anything further left may be forged
+fn visitor_ip(peer: Option<IpAddr>, forwarded: Option<&str>, trusted: &[IpAddr]) -> Option<IpAddr> {
+    let peer = peer?;
+    if !trusted.contains(&peer) {
+        return Some(peer);
+    }
+    let mut visitor = peer;
+    for hop in forwarded.unwrap_or_default().rsplit(',') {
+        match hop.trim().parse::<IpAddr>() {
+            Ok(ip) => {
+                visitor = ip;
+                if !trusted.contains(&ip) {
+                    break;
+                }
+            }
+            // a hop that is not an address can not be told apart from a forged one
+            Err(_) => break,
+        }
+    }
+    Some(visitor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visitor_ip() {
+        let ip = |ip: &str| ip.parse::<IpAddr>().unwrap();
+        let trusted = [ip("10.0.0.1"), ip("10.0.0.2")];
+
+        // untrusted peers can not pick their address
+        let visitor = visitor_ip(Some(ip("1.2.3.4")), Some("5.6.7.8"), &trusted);
+        assert_eq!(visitor, Some(ip("1.2.3.4")));
+        // the client is the last hop not added by a trusted proxy
+        let forwarded = Some("9.9.9.9, 5.6.7.8, 10.0.0.2");
+        let visitor = visitor_ip(Some(ip("10.0.0.1")), forwarded, &trusted);
+        assert_eq!(visitor, Some(ip("5.6.7.8")));
+        // nothing forwarded, the proxy itself
+        let visitor = visitor_ip(Some(ip("10.0.0.1")), None, &trusted);
+        assert_eq!(visitor, Some(ip("10.0.0.1")));
+        // garbage stops the walk at the last known address
+        let visitor = visitor_ip(Some(ip("10.0.0.1")), Some("5.6.7.8, garbage"), &trusted);
+        assert_eq!(visitor, Some(ip("10.0.0.1")));
+        assert_eq!(visitor_ip(None, Some("5.6.7.8"), &trusted), None);
+    }
+}
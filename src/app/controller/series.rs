@@ -0,0 +1,124 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use axum::{
+    extract::Path,
+    routing::{delete, get, post},
+    Extension, Json, Router,
+};
+use garde::Validate;
+
+use super::{BearerToken, HandlerAsyncSafe};
+use crate::app::{
+    common::prelude::*,
+    service::{prelude::SeriesServiceTrait, types::series::prelude::*},
+};
+
+// ********************* content ********************* //
+// router
+pub fn public_router<S>(_: &S) -> Router
+where
+    S: SeriesServiceTrait + HandlerAsyncSafe,
+{
+    Router::new()
+        .route("/", post(create::<S>))
+        .route(
+            "/:id",
+            get(find::<S>).patch(edit::<S>).delete(delete_series::<S>),
+        )
+        .route("/:id/articles", post(insert::<S>).put(reorder::<S>))
+        .route("/:id/articles/:article_id", delete(remove::<S>))
+}
+
+// handler
+async fn find<S>(Extension(series_service): Extension<Arc<S>>, Path(id): Path<i32>) -> AppResponse
+where
+    S: SeriesServiceTrait,
+{
+    series_service.find(id).await.into()
+}
+
+async fn create<S>(
+    Extension(series_service): Extension<Arc<S>>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<SeriesCreateReqForm>,
+) -> AppResponse
+where
+    S: SeriesServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    series_service.create(&token, req_form).await.into()
+}
+
+async fn edit<S>(
+    Extension(series_service): Extension<Arc<S>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<SeriesEditReqForm>,
+) -> AppResponse
+where
+    S: SeriesServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    series_service.edit(id, &token, req_form).await.into()
+}
+
+async fn delete_series<S>(
+    Extension(series_service): Extension<Arc<S>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    S: SeriesServiceTrait,
+{
+    series_service.delete(id, &token).await.into()
+}
+
+async fn reorder<S>(
+    Extension(series_service): Extension<Arc<S>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<SeriesReorderReqForm>,
+) -> AppResponse
+where
+    S: SeriesServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    series_service.reorder(id, &token, req_form).await.into()
+}
+
+async fn insert<S>(
+    Extension(series_service): Extension<Arc<S>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<SeriesInsertReqForm>,
+) -> AppResponse
+where
+    S: SeriesServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    series_service.insert(id, &token, req_form).await.into()
+}
+
+async fn remove<S>(
+    Extension(series_service): Extension<Arc<S>>,
+    Path((id, article_id)): Path<(i32, i32)>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    S: SeriesServiceTrait,
+{
+    series_service.remove(id, article_id, &token).await.into()
+}
@@ -0,0 +1,139 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use axum::{
+    extract::Path,
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Extension, Router,
+};
+use chrono::DateTime;
+
+use super::HandlerAsyncSafe;
+use crate::app::{
+    common::prelude::*,
+    service::{prelude::SiteServiceTrait, types::site::prelude::*},
+    utils::prelude::http_date,
+};
+
+// ********************* content ********************* //
+// router, mounted at the site root rather than under the api prefix
+pub fn router<S>(_: &S) -> Router
+where
+    S: SiteServiceTrait + HandlerAsyncSafe,
+{
+    Router::new()
+        .route("/feed.xml", get(rss::<S>))
+        .route("/atom.xml", get(atom::<S>))
+        .route("/tag/:slug/feed.xml", get(tag_rss::<S>))
+        .route("/tag/:slug/atom.xml", get(tag_atom::<S>))
+        .route("/sitemap.xml", get(sitemap::<S>))
+        .route("/sitemap/:file", get(sitemap_part::<S>))
+        .route("/robots.txt", get(robots::<S>))
+}
+
+// answer 304 when the client copy is still fresh
+fn document_response(res: AppResult<SiteDocument>, headers: &HeaderMap) -> Response {
+    let document = match res {
+        Ok(document) => document,
+        Err(e) => return AppResponse::from(e).into_response(),
+    };
+    let header_str = |name| headers.get(name).and_then(|value| value.to_str().ok());
+    let is_fresh = document.is_fresh(
+        header_str(header::IF_NONE_MATCH),
+        header_str(header::IF_MODIFIED_SINCE),
+    );
+    let mut res_headers = HeaderMap::new();
+    let mut insert = |name: HeaderName, value: &str| {
+        if let Ok(value) = HeaderValue::from_str(value) {
+            res_headers.insert(name, value);
+        }
+    };
+    insert(header::ETAG, &document.etag);
+    insert(header::CACHE_CONTROL, "no-cache");
+    if let Some(last_modified) = document
+        .last_modified
+        .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+    {
+        insert(header::LAST_MODIFIED, &http_date(last_modified));
+    }
+    if is_fresh {
+        return (StatusCode::NOT_MODIFIED, res_headers).into_response();
+    }
+    insert(header::CONTENT_TYPE, &document.content_type);
+    (res_headers, document.body).into_response()
+}
+
+// handler
+async fn rss<S>(Extension(site_service): Extension<Arc<S>>, headers: HeaderMap) -> Response
+where
+    S: SiteServiceTrait,
+{
+    document_response(site_service.rss(None).await, &headers)
+}
+
+async fn atom<S>(Extension(site_service): Extension<Arc<S>>, headers: HeaderMap) -> Response
+where
+    S: SiteServiceTrait,
+{
+    document_response(site_service.atom(None).await, &headers)
+}
+
+async fn tag_rss<S>(
+    Extension(site_service): Extension<Arc<S>>,
+    Path(slug): Path<String>,
+    headers: HeaderMap,
+) -> Response
+where
+    S: SiteServiceTrait,
+{
+    document_response(site_service.rss(Some(slug)).await, &headers)
+}
+
+async fn tag_atom<S>(
+    Extension(site_service): Extension<Arc<S>>,
+    Path(slug): Path<String>,
+    headers: HeaderMap,
+) -> Response
+where
+    S: SiteServiceTrait,
+{
+    document_response(site_service.atom(Some(slug)).await, &headers)
+}
+
+async fn sitemap<S>(Extension(site_service): Extension<Arc<S>>, headers: HeaderMap) -> Response
+where
+    S: SiteServiceTrait,
+{
+    document_response(site_service.sitemap().await, &headers)
+}
+
+// parts of a split sitemap are named like `1.xml`
+async fn sitemap_part<S>(
+    Extension(site_service): Extension<Arc<S>>,
+    Path(file): Path<String>,
+    headers: HeaderMap,
+) -> Response
+where
+    S: SiteServiceTrait,
+{
+    let res = match file
+        .strip_suffix(".xml")
+        .and_then(|num| num.parse::<usize>().ok())
+    {
+        Some(num) => site_service.sitemap_part(num).await,
+        None => Err(AppError::new(
+            format!("Sitemap {} not found", file),
+            AppErrorKind::ResourceNotFound,
+        )),
+    };
+    document_response(res, &headers)
+}
+
+async fn robots<S>(Extension(site_service): Extension<Arc<S>>, headers: HeaderMap) -> Response
+where
+    S: SiteServiceTrait,
+{
+    document_response(site_service.robots().await, &headers)
+}
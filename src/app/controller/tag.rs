@@ -0,0 +1,167 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query},
+    response::{IntoResponse, Redirect, Response},
+    routing::{get, patch, post},
+    Extension, Json, Router,
+};
+use garde::Validate;
+
+use super::{BearerToken, HandlerAsyncSafe};
+use crate::app::{
+    common::prelude::*,
+    service::{prelude::TagServiceTrait, types::tag::prelude::*},
+};
+
+// ********************* content ********************* //
+// router
+pub fn public_router<G>(_: &G) -> Router
+where
+    G: TagServiceTrait + HandlerAsyncSafe,
+{
+    Router::new()
+        .route("/tree", get(tree::<G>))
+        .route("/slug/:slug", get(find_by_slug::<G>))
+        .route("/:id", get(find::<G>))
+        .route("/:id/path", get(path::<G>))
+}
+
+pub fn admin_router<G>(_: &G) -> Router
+where
+    G: TagServiceTrait + HandlerAsyncSafe,
+{
+    Router::new()
+        .route("/", post(admin_create::<G>))
+        .route("/tree", get(admin_tree::<G>))
+        .route("/:id", patch(admin_edit::<G>).delete(admin_delete::<G>))
+        .route("/:id/parent", patch(admin_move::<G>))
+        .route("/:id/merge", post(admin_merge::<G>))
+}
+
+// handler
+async fn tree<G>(Extension(tag_service): Extension<Arc<G>>) -> AppResponse
+where
+    G: TagServiceTrait,
+{
+    tag_service.tree().await.into()
+}
+
+async fn find<G>(Extension(tag_service): Extension<Arc<G>>, Path(id): Path<i32>) -> AppResponse
+where
+    G: TagServiceTrait,
+{
+    tag_service.find(id).await.into()
+}
+
+async fn find_by_slug<G>(
+    Extension(tag_service): Extension<Arc<G>>,
+    Path(slug): Path<String>,
+) -> Response
+where
+    G: TagServiceTrait,
+{
+    match tag_service.find_by_slug(slug).await {
+        Ok(TagSlugFindResForm::Found(res_form)) => AppResponse::succ(res_form).into_response(),
+        // relative to the requested path, so the mount point does not matter
+        Ok(TagSlugFindResForm::Moved(slug)) => Redirect::permanent(&slug).into_response(),
+        Err(e) => AppResponse::from(e).into_response(),
+    }
+}
+
+async fn path<G>(Extension(tag_service): Extension<Arc<G>>, Path(id): Path<i32>) -> AppResponse
+where
+    G: TagServiceTrait,
+{
+    tag_service.path(id).await.into()
+}
+
+async fn admin_tree<G>(
+    Extension(tag_service): Extension<Arc<G>>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    G: TagServiceTrait,
+{
+    tag_service.admin_tree(&token).await.into()
+}
+
+async fn admin_create<G>(
+    Extension(tag_service): Extension<Arc<G>>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<TagAdminCreateReqForm>,
+) -> AppResponse
+where
+    G: TagServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    tag_service.admin_create(&token, req_form).await.into()
+}
+
+async fn admin_edit<G>(
+    Extension(tag_service): Extension<Arc<G>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<TagAdminEditReqForm>,
+) -> AppResponse
+where
+    G: TagServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    tag_service.admin_edit(id, &token, req_form).await.into()
+}
+
+async fn admin_move<G>(
+    Extension(tag_service): Extension<Arc<G>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<TagAdminMoveReqForm>,
+) -> AppResponse
+where
+    G: TagServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    tag_service.admin_move(id, &token, req_form).await.into()
+}
+
+async fn admin_merge<G>(
+    Extension(tag_service): Extension<Arc<G>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<TagAdminMergeReqForm>,
+) -> AppResponse
+where
+    G: TagServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    tag_service.admin_merge(id, &token, req_form).await.into()
+}
+
+async fn admin_delete<G>(
+    Extension(tag_service): Extension<Arc<G>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+    Query(req_form): Query<TagAdminDeleteReqForm>,
+) -> AppResponse
+where
+    G: TagServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    tag_service.admin_delete(id, &token, req_form).await.into()
+}
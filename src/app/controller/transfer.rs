@@ -0,0 +1,79 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    extract::{DefaultBodyLimit, Query},
+    http::header,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Router,
+};
+use chrono::Local;
+use garde::Validate;
+
+use super::{BearerToken, HandlerAsyncSafe};
+use crate::app::{
+    common::prelude::*,
+    service::{prelude::TransferServiceTrait, types::transfer::prelude::*},
+};
+
+// ********************* content ********************* //
+// a whole blog in one request is well above the default body limit
+const IMPORT_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
+// router
+pub fn admin_router<T>(_: &T) -> Router
+where
+    T: TransferServiceTrait + HandlerAsyncSafe,
+{
+    Router::new().route("/export", get(export::<T>)).route(
+        "/import",
+        post(import::<T>).layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT)),
+    )
+}
+
+// handler
+async fn export<T>(
+    Extension(transfer_service): Extension<Arc<T>>,
+    BearerToken(token): BearerToken,
+) -> Response
+where
+    T: TransferServiceTrait,
+{
+    let data = match transfer_service.export(&token).await {
+        Ok(data) => data,
+        Err(e) => return AppResponse::from(e).into_response(),
+    };
+    let disposition = format!(
+        "attachment; filename=\"articles-{}.zip\"",
+        Local::now().format("%Y%m%d%H%M%S")
+    );
+    (
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        data,
+    )
+        .into_response()
+}
+
+async fn import<T>(
+    Extension(transfer_service): Extension<Arc<T>>,
+    BearerToken(token): BearerToken,
+    Query(req_form): Query<TransferImportReqForm>,
+    body: Bytes,
+) -> AppResponse
+where
+    T: TransferServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    transfer_service
+        .import(&token, req_form, body.to_vec())
+        .await
+        .into()
+}
@@ -0,0 +1,65 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query},
+    routing::{get, post},
+    Extension, Router,
+};
+use garde::Validate;
+
+use super::{BearerToken, HandlerAsyncSafe};
+use crate::app::{
+    common::prelude::*,
+    service::{prelude::TrashServiceTrait, types::trash::prelude::*},
+};
+
+// ********************* content ********************* //
+// router
+pub fn admin_router<T>(_: &T) -> Router
+where
+    T: TrashServiceTrait + HandlerAsyncSafe,
+{
+    Router::new()
+        .route("/purge", post(purge::<T>))
+        .route("/:kind", get(search::<T>))
+        .route("/:kind/:id/restore", post(restore::<T>))
+}
+
+// handler
+async fn search<T>(
+    Extension(trash_service): Extension<Arc<T>>,
+    Path(kind): Path<TrashKind>,
+    BearerToken(token): BearerToken,
+    Query(req_form): Query<TrashSearchReqForm>,
+) -> AppResponse
+where
+    T: TrashServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    trash_service.search(kind, &token, req_form).await.into()
+}
+
+async fn restore<T>(
+    Extension(trash_service): Extension<Arc<T>>,
+    Path((kind, id)): Path<(TrashKind, i32)>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    T: TrashServiceTrait,
+{
+    trash_service.restore(kind, id, &token).await.into()
+}
+
+async fn purge<T>(
+    Extension(trash_service): Extension<Arc<T>>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    T: TrashServiceTrait,
+{
+    trash_service.purge(&token).await.into()
+}
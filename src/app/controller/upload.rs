@@ -0,0 +1,187 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use axum::{
+    extract::{DefaultBodyLimit, Json, Multipart, Path, Query},
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Router,
+};
+use garde::Validate;
+
+use super::{BearerToken, HandlerAsyncSafe};
+use crate::app::{
+    common::prelude::*,
+    service::{prelude::UploadServiceTrait, types::upload::prelude::*},
+};
+
+// ********************* content ********************* //
+// leaves room for the multipart framing, the configured size limit is checked later
+const UPLOAD_BODY_LIMIT: usize = 32 * 1024 * 1024;
+
+// router
+pub fn public_router<T>(_: &T) -> Router
+where
+    T: UploadServiceTrait + HandlerAsyncSafe,
+{
+    Router::new()
+        .route(
+            "/",
+            post(create::<T>).layer(DefaultBodyLimit::max(UPLOAD_BODY_LIMIT)),
+        )
+        .route("/presign", post(presign::<T>))
+        .route("/confirm", post(confirm::<T>))
+        .route("/mine", get(mine_search::<T>))
+        .route("/file/:key", get(file::<T>))
+        .route("/file/:key/:size", get(file_variant::<T>))
+}
+
+// the file goes in the `file` field, other fields are ignored
+async fn read_file(mut multipart: Multipart) -> AppResult<UploadFile> {
+    while let Some(field) = multipart.next_field().await.wrap(
+        "Failed to read multipart body",
+        AppErrorKind::RequestParamInvalid,
+    )? {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let file_name = field.file_name().unwrap_or_default().to_string();
+        let data = field.bytes().await.wrap(
+            "Failed to read multipart field 'file'",
+            AppErrorKind::RequestParamInvalid,
+        )?;
+        return Ok(UploadFile {
+            file_name,
+            data: data.to_vec(),
+        });
+    }
+    Err(AppError::new(
+        "Multipart field 'file' not found",
+        AppErrorKind::RequestParamMissing,
+    ))
+}
+
+// handler
+async fn create<T>(
+    Extension(upload_service): Extension<Arc<T>>,
+    BearerToken(token): BearerToken,
+    multipart: Multipart,
+) -> AppResponse
+where
+    T: UploadServiceTrait,
+{
+    let file = read_file(multipart).await?;
+    upload_service.create(&token, file).await.into()
+}
+
+async fn presign<T>(
+    Extension(upload_service): Extension<Arc<T>>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<UploadPresignReqForm>,
+) -> AppResponse
+where
+    T: UploadServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    upload_service.presign(&token, req_form).await.into()
+}
+
+async fn confirm<T>(
+    Extension(upload_service): Extension<Arc<T>>,
+    BearerToken(token): BearerToken,
+    Json(req_form): Json<UploadConfirmReqForm>,
+) -> AppResponse
+where
+    T: UploadServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    upload_service.confirm(&token, req_form).await.into()
+}
+
+async fn mine_search<T>(
+    Extension(upload_service): Extension<Arc<T>>,
+    BearerToken(token): BearerToken,
+    Query(req_form): Query<UploadMineSearchReqForm>,
+) -> AppResponse
+where
+    T: UploadServiceTrait,
+{
+    req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    upload_service.mine_search(&token, req_form).await.into()
+}
+
+async fn file<T>(
+    Extension(upload_service): Extension<Arc<T>>,
+    Path(key): Path<String>,
+    headers: HeaderMap,
+) -> Response
+where
+    T: UploadServiceTrait,
+{
+    file_response(upload_service.file(&key).await, &headers)
+}
+
+async fn file_variant<T>(
+    Extension(upload_service): Extension<Arc<T>>,
+    Path((key, size)): Path<(String, u32)>,
+    headers: HeaderMap,
+) -> Response
+where
+    T: UploadServiceTrait,
+{
+    file_response(upload_service.file_variant(&key, size).await, &headers)
+}
+
+// answer 304 when the client copy is still fresh, or send the client to the storage
+fn file_response(res: AppResult<UploadFileResForm>, headers: &HeaderMap) -> Response {
+    let res_form = match res {
+        Ok(UploadFileResForm::Content(res_form)) => res_form,
+        Ok(UploadFileResForm::Redirect {
+            url,
+            cache_max_age_sec,
+        }) => {
+            let cache_control = format!("private, max-age={}", cache_max_age_sec);
+            return (
+                StatusCode::TEMPORARY_REDIRECT,
+                [
+                    (header::LOCATION, url),
+                    (header::CACHE_CONTROL, cache_control),
+                ],
+            )
+                .into_response();
+        }
+        Err(e) => return AppResponse::from(e).into_response(),
+    };
+    let is_fresh = res_form.is_fresh(
+        headers
+            .get(header::IF_NONE_MATCH)
+            .and_then(|value| value.to_str().ok()),
+    );
+    let mut res_headers = HeaderMap::new();
+    let mut insert = |name: HeaderName, value: &str| {
+        if let Ok(value) = HeaderValue::from_str(value) {
+            res_headers.insert(name, value);
+        }
+    };
+    insert(header::ETAG, &res_form.etag);
+    insert(
+        header::CACHE_CONTROL,
+        &format!("public, max-age={}, immutable", res_form.cache_max_age_sec),
+    );
+    insert(header::X_CONTENT_TYPE_OPTIONS, "nosniff");
+    if is_fresh {
+        return (StatusCode::NOT_MODIFIED, res_headers).into_response();
+    }
+    insert(header::CONTENT_TYPE, &res_form.mime_type);
+    (res_headers, res_form.data).into_response()
+}
@@ -3,6 +3,8 @@ use std::sync::Arc;
 
 use axum::{
     extract::{Path, Query},
+    http::header,
+    response::{IntoResponse, Response},
     routing::{get, patch, post},
     Extension, Json, Router,
 };
@@ -15,6 +17,8 @@ use crate::app::{
 };
 
 // ********************* content ********************* //
+const IDENTICON_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
 // router
 pub fn public_router<U>(_: &U) -> Router
 where
@@ -27,6 +31,7 @@ where
         .route("/search", get(search::<U>))
         .route("/:id", get(find::<U>).patch(edit::<U>))
         .route("/:id/password", patch(change_password::<U>))
+        .route("/:id/identicon", get(identicon::<U>))
 }
 
 pub fn admin_router<U>(_: &U) -> Router
@@ -35,7 +40,12 @@ where
 {
     Router::new()
         .route("/search", get(admin_search::<U>))
-        .route("/:id", get(admin_find::<U>).patch(admin_edit::<U>))
+        .route(
+            "/:id",
+            get(admin_find::<U>)
+                .patch(admin_edit::<U>)
+                .delete(admin_delete::<U>),
+        )
 }
 
 // handler
@@ -107,6 +117,35 @@ where
     user_service.find(id, &token).await.into()
 }
 
+// the same id always gives the same image, so it can be cached for good
+async fn identicon<U>(
+    Extension(user_service): Extension<Arc<U>>,
+    Path(id): Path<i32>,
+    Query(req_form): Query<UserIdenticonReqForm>,
+) -> Response
+where
+    U: UserServiceTrait,
+{
+    if let Err(e) = req_form.validate(&()).wrap_with(
+        || format!("Request form validation failed, form: {:?}", req_form),
+        AppErrorKind::RequestParamInvalid,
+    ) {
+        return AppResponse::from(e).into_response();
+    }
+    match user_service.identicon(id, req_form).await {
+        Ok(res_form) => (
+            [
+                (header::CONTENT_TYPE, res_form.mime_type),
+                (header::CACHE_CONTROL, IDENTICON_CACHE_CONTROL),
+                (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+            ],
+            res_form.data,
+        )
+            .into_response(),
+        Err(e) => AppResponse::from(e).into_response(),
+    }
+}
+
 async fn change_password<U>(
     Extension(user_service): Extension<Arc<U>>,
     Path(id): Path<i32>,
@@ -183,3 +222,14 @@ where
     )?;
     user_service.admin_edit(id, &token, req_form).await.into()
 }
+
+async fn admin_delete<U>(
+    Extension(user_service): Extension<Arc<U>>,
+    Path(id): Path<i32>,
+    BearerToken(token): BearerToken,
+) -> AppResponse
+where
+    U: UserServiceTrait,
+{
+    user_service.admin_delete(id, &token).await.into()
+}
@@ -0,0 +1,825 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::{
+    prelude::DateTime, ColumnTrait, Condition, ConnectionTrait, DbBackend, EntityTrait,
+    IntoActiveModel, IntoSimpleExpr, ModelTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Select, Set, TransactionTrait,
+};
+use sea_query::{Expr, Func, IntoCondition, Query, SimpleExpr, SubQueryStatement};
+
+use super::{
+    super::{
+        traits::article::ArticleDataAccess,
+        types::{article::prelude::*, user::prelude::*, PaginateParam},
+    },
+    DBConnProvider, DataAccessImpl, SoftDeleteImpl,
+};
+use crate::app::{
+    common::prelude::*,
+    db::prelude::{
+        ArticleActiveModel, ArticleColumn, ArticleEntity, ArticleTagColumn, ArticleTagEntity,
+        ArticleToCreateUserLink, DatabaseConnection, UserColumn, UserEntity,
+    },
+    utils::prelude::is_cjk,
+};
+
+// ********************* content ********************* //
+// params
+impl IntoCondition for ArticleFilterParam {
+    fn into_condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(id) = self.id {
+            condition = condition.add(ArticleColumn::Id.eq(id));
+        }
+        if let Some(ids) = self.ids {
+            condition = condition.add(ArticleColumn::Id.is_in(ids));
+        }
+        if let Some(slug) = self.slug {
+            condition = condition.add(ArticleColumn::Slug.eq(slug));
+        }
+        if let Some(slug_prefix) = self.slug_prefix {
+            condition = condition.add(ArticleColumn::Slug.starts_with(&slug_prefix));
+        }
+        if let Some(title_search) = self.title_search {
+            condition = condition.add(ArticleColumn::Title.contains(&title_search));
+        }
+        if let Some(status_type) = self.status_type {
+            condition = condition.add(ArticleColumn::StatusType.eq(status_type));
+        }
+        if let Some(status_types) = self.status_types {
+            condition = condition.add(ArticleColumn::StatusType.is_in(status_types));
+        }
+        if let Some(is_top) = self.is_top {
+            condition = condition.add(ArticleColumn::IsTop.eq(is_top));
+        }
+        if let Some(create_user_id) = self.create_user_id {
+            condition = condition.add(ArticleColumn::CreateUserId.eq(create_user_id));
+        }
+        if let Some(create_time_start) = self.create_time_start {
+            condition = condition.add(ArticleColumn::CreateTime.gte(create_time_start));
+        }
+        if let Some(create_time_end) = self.create_time_end {
+            condition = condition.add(ArticleColumn::CreateTime.lt(create_time_end));
+        }
+        if let Some(publish_time_start) = self.publish_time_start {
+            condition = condition.add(Expr::expr(publish_time()).gte(publish_time_start));
+        }
+        if let Some(publish_time_end) = self.publish_time_end {
+            condition = condition.add(Expr::expr(publish_time()).lt(publish_time_end));
+        }
+        if let Some(publish_at_end) = self.publish_at_end {
+            condition = condition.add(ArticleColumn::PublishAt.lte(publish_at_end));
+        }
+        if let Some(unpublish_at_end) = self.unpublish_at_end {
+            condition = condition.add(ArticleColumn::UnpublishAt.lte(unpublish_at_end));
+        }
+        if let Some(live_at) = self.live_at {
+            let trashed_user_ids = Query::select()
+                .column(UserColumn::Id)
+                .from(UserEntity)
+                .and_where(UserColumn::StatusType.eq(3))
+                .to_owned();
+            condition = condition
+                .add(ArticleColumn::CreateUserId.not_in_subquery(trashed_user_ids))
+                .add(
+                    Condition::any()
+                        .add(ArticleColumn::PublishAt.is_null())
+                        .add(ArticleColumn::PublishAt.lte(live_at)),
+                )
+                .add(
+                    Condition::any()
+                        .add(ArticleColumn::UnpublishAt.is_null())
+                        .add(ArticleColumn::UnpublishAt.gt(live_at)),
+                );
+        }
+        condition
+    }
+}
+
+impl IntoActiveModel<ArticleActiveModel> for ArticleCreateParam {
+    fn into_active_model(self) -> ArticleActiveModel {
+        ArticleActiveModel {
+            title: Set(self.title),
+            slug: Set(self.slug),
+            description: Set(self.description),
+            content: Set(self.content),
+            is_top: Set(self.is_top),
+            status_type: Set(self.status_type),
+            publish_at: Set(self.publish_at),
+            unpublish_at: Set(self.unpublish_at),
+            publish_time: Set(self.publish_time),
+            create_user_id: Set(self.create_user_id),
+            update_user_id: Set(self.create_user_id),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoActiveModel<ArticleActiveModel> for ArticleUpdateParam {
+    fn into_active_model(self) -> ArticleActiveModel {
+        let mut active_model = <ArticleActiveModel as Default>::default();
+        if let Some(title) = self.title {
+            active_model.title = Set(title);
+        }
+        if let Some(slug) = self.slug {
+            active_model.slug = Set(slug);
+        }
+        if let Some(description) = self.description {
+            active_model.description = Set(description);
+        }
+        if let Some(content) = self.content {
+            active_model.content = Set(content);
+        }
+        if let Some(is_top) = self.is_top {
+            active_model.is_top = Set(is_top);
+        }
+        if let Some(pin_priority) = self.pin_priority {
+            active_model.pin_priority = Set(pin_priority);
+        }
+        if let Some(status_type) = self.status_type {
+            active_model.status_type = Set(status_type);
+        }
+        if let Some(publish_at) = self.publish_at {
+            active_model.publish_at = Set(publish_at);
+        }
+        if let Some(unpublish_at) = self.unpublish_at {
+            active_model.unpublish_at = Set(unpublish_at);
+        }
+        if let Some(publish_time) = self.publish_time {
+            active_model.publish_time = Set(Some(publish_time));
+        }
+        if let Some(create_time) = self.create_time {
+            active_model.create_time = Set(create_time);
+        }
+        if let Some(update_time) = self.update_time {
+            active_model.update_time = Set(update_time);
+        }
+        if let Some(update_user_id) = self.update_user_id {
+            active_model.update_user_id = Set(update_user_id);
+        }
+        active_model
+    }
+}
+
+// pinning is not an edit, so keep update_time from being refreshed by ON UPDATE
+fn keep_update_time() -> SimpleExpr {
+    Expr::col(ArticleColumn::UpdateTime).into()
+}
+
+// articles published before the publish time was recorded fall back to their create time
+fn publish_time() -> SimpleExpr {
+    Func::coalesce([
+        Expr::col((ArticleEntity, ArticleColumn::PublishTime)).into(),
+        Expr::col((ArticleEntity, ArticleColumn::CreateTime)).into(),
+    ])
+    .into()
+}
+
+impl IntoSimpleExpr for ArticleAttr {
+    fn into_simple_expr(self) -> SimpleExpr {
+        match self {
+            ArticleAttr::Id => ArticleColumn::Id,
+            ArticleAttr::Title => ArticleColumn::Title,
+            ArticleAttr::IsTop => ArticleColumn::IsTop,
+            ArticleAttr::PinPriority => ArticleColumn::PinPriority,
+            // a scalar subquery rather than a join, so it also works on lists without one
+            ArticleAttr::TagPinPriority(tag_id) => {
+                let pin_priority = Query::select()
+                    .column(ArticleTagColumn::PinPriority)
+                    .from(ArticleTagEntity)
+                    .and_where(
+                        Expr::col((ArticleTagEntity, ArticleTagColumn::ArticleId))
+                            .equals((ArticleEntity, ArticleColumn::Id)),
+                    )
+                    .and_where(ArticleTagColumn::TagId.eq(tag_id))
+                    .to_owned();
+                return Func::coalesce([
+                    SimpleExpr::SubQuery(
+                        None,
+                        Box::new(SubQueryStatement::SelectStatement(pin_priority)),
+                    ),
+                    Expr::val(-1).into(),
+                ])
+                .into();
+            }
+            ArticleAttr::CreateTime => ArticleColumn::CreateTime,
+            ArticleAttr::PublishTime => return publish_time(),
+            ArticleAttr::UpdateTime => ArticleColumn::UpdateTime,
+            ArticleAttr::DeleteTime => ArticleColumn::DeleteTime,
+        }
+        .into_simple_expr()
+    }
+}
+
+// dao
+pub struct ArticleDAO {
+    db_conn: Arc<DatabaseConnection>,
+}
+
+impl ArticleDAO {
+    pub fn new(db_conn: Arc<DatabaseConnection>) -> Self {
+        Self { db_conn }
+    }
+}
+
+impl ArticleDAO {
+    // mysql relies on the ngram FULLTEXT index `ft_title_content`, postgres on the
+    // "simple" text search configuration, which keeps a CJK run as one word, so CJK
+    // queries match substrings instead, see db/space_postgres.sql for the indexes
+    fn select_fulltext(
+        &self,
+        query: String,
+        filter: ArticleFilterParam,
+    ) -> AppResult<(Select<ArticleEntity>, SimpleExpr)> {
+        let (condition, rank) = match self.db_conn().get_database_backend() {
+            DbBackend::MySql => {
+                let matched = "MATCH (`t_space_article`.`title`, `t_space_article`.`content`) \
+                               AGAINST (? IN NATURAL LANGUAGE MODE)";
+                (
+                    Expr::cust_with_values(matched, [query.clone()]),
+                    Expr::cust_with_values(matched, [query]),
+                )
+            }
+            DbBackend::Postgres if query.chars().any(is_cjk) => {
+                let pattern = format!(
+                    "%{}%",
+                    query
+                        .replace('\\', "\\\\")
+                        .replace('%', "\\%")
+                        .replace('_', "\\_")
+                );
+                let document =
+                    r#"("t_space_article"."title" || ' ' || "t_space_article"."content")"#;
+                (
+                    Expr::cust_with_values(format!("{} LIKE $1", document), [pattern.clone()]),
+                    // no relevance to rank by, matches in the title come first
+                    Expr::cust_with_values(
+                        r#"CAST("t_space_article"."title" LIKE $1 AS INTEGER)"#,
+                        [pattern],
+                    ),
+                )
+            }
+            DbBackend::Postgres => {
+                let document = r#"to_tsvector('simple', "t_space_article"."title" || ' ' || "t_space_article"."content")"#;
+                (
+                    Expr::cust_with_values(
+                        format!("{} @@ plainto_tsquery('simple', $1)", document),
+                        [query.clone()],
+                    ),
+                    Expr::cust_with_values(
+                        format!("ts_rank({}, plainto_tsquery('simple', $1))", document),
+                        [query],
+                    ),
+                )
+            }
+            backend => {
+                return Err(AppError::new(
+                    format!("Full-text search is not supported on {:?}", backend),
+                    AppErrorKind::NotImplemented,
+                ))
+            }
+        };
+        let select = ArticleEntity::find().filter(condition).filter(filter);
+        Ok((select, rank))
+    }
+
+    // year and month parts of the publish time, as integers on both backends
+    fn publish_month_exprs(&self) -> AppResult<(SimpleExpr, SimpleExpr)> {
+        let publish_time =
+            r#"COALESCE("t_space_article"."publish_time", "t_space_article"."create_time")"#;
+        match self.db_conn().get_database_backend() {
+            DbBackend::MySql => {
+                let publish_time = publish_time.replace('"', "`");
+                Ok((
+                    Expr::cust(format!("YEAR({})", publish_time)),
+                    Expr::cust(format!("MONTH({})", publish_time)),
+                ))
+            }
+            DbBackend::Postgres => Ok((
+                Expr::cust(format!(
+                    "CAST(EXTRACT(YEAR FROM {}) AS INTEGER)",
+                    publish_time
+                )),
+                Expr::cust(format!(
+                    "CAST(EXTRACT(MONTH FROM {}) AS INTEGER)",
+                    publish_time
+                )),
+            )),
+            backend => Err(AppError::new(
+                format!("Date functions are not supported on {:?}", backend),
+                AppErrorKind::NotImplemented,
+            )),
+        }
+    }
+}
+
+impl DBConnProvider for ArticleDAO {
+    fn db_conn(&self) -> &DatabaseConnection {
+        &self.db_conn
+    }
+}
+
+#[async_trait]
+impl DataAccessImpl for ArticleDAO {
+    type DataAttr = ArticleAttr;
+    type FilterParam = ArticleFilterParam;
+    type CreateParam = ArticleCreateParam;
+    type UpdateParam = ArticleUpdateParam;
+    type Model = ArticleDataModel;
+    type Entity = ArticleEntity;
+    type ActiveModel = ArticleActiveModel;
+}
+
+impl SoftDeleteImpl for ArticleDAO {
+    const DELETED_STATUS_TYPE: i32 = 3;
+    // restored articles come back as drafts
+    const RESTORED_STATUS_TYPE: i32 = 0;
+
+    fn status_type_column() -> ArticleColumn {
+        ArticleColumn::StatusType
+    }
+
+    fn delete_time_column() -> ArticleColumn {
+        ArticleColumn::DeleteTime
+    }
+
+    fn delete_user_id_column() -> ArticleColumn {
+        ArticleColumn::DeleteUserId
+    }
+
+    // a schedule does not survive the trash, or a restored article would be published by it
+    fn cleared_columns() -> Vec<ArticleColumn> {
+        vec![ArticleColumn::PublishAt, ArticleColumn::UnpublishAt]
+    }
+}
+
+#[async_trait]
+impl ArticleDataAccess for ArticleDAO {
+    async fn count_fulltext(&self, query: String, filter: ArticleFilterParam) -> AppResult<u64> {
+        let (select, _) = self.select_fulltext(query, filter)?;
+        select
+            .count(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn list_fulltext(
+        &self,
+        query: String,
+        filter: ArticleFilterParam,
+        paginate: PaginateParam,
+    ) -> AppResult<Vec<ArticleDataModel>> {
+        let (select, rank) = self.select_fulltext(query, filter)?;
+        select
+            .order_by(rank, Order::Desc)
+            .order_by(ArticleColumn::Id, Order::Desc)
+            .paginate(self.db_conn(), paginate.page_size)
+            .fetch_page(paginate.page_num.saturating_sub(1))
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn add_view_count(&self, id: i32, delta: i64) -> AppResult<()> {
+        ArticleEntity::update_many()
+            .col_expr(
+                ArticleColumn::ViewCount,
+                Expr::col(ArticleColumn::ViewCount).add(delta),
+            )
+            .filter(ArticleColumn::Id.eq(id))
+            .exec(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?;
+        Ok(())
+    }
+
+    async fn get_create_user(&self, id: i32) -> AppResult<UserDataModel> {
+        let filter = ArticleFilterParam {
+            id: Some(id),
+            ..Default::default()
+        };
+        <Self as DataAccessImpl>::get(self, self.db_conn(), filter)
+            .await?
+            .find_linked(ArticleToCreateUserLink)
+            .one(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?
+            .ok_or_else(|| {
+                AppError::new(
+                    format!("Creator of article {} not found", id),
+                    AppErrorKind::ResourceNotFound,
+                )
+            })
+    }
+
+    async fn list_slugs(&self, filter: ArticleFilterParam) -> AppResult<Vec<(String, DateTime)>> {
+        ArticleEntity::find()
+            .select_only()
+            .column(ArticleColumn::Slug)
+            .column(ArticleColumn::UpdateTime)
+            .filter(filter)
+            .order_by_asc(ArticleColumn::Id)
+            .into_tuple()
+            .all(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn count_by_month(&self, filter: ArticleFilterParam) -> AppResult<Vec<(i32, i32, i64)>> {
+        let (year, month) = self.publish_month_exprs()?;
+        ArticleEntity::find()
+            .select_only()
+            .column_as(year.clone(), "year")
+            .column_as(month.clone(), "month")
+            .column_as(Expr::col(ArticleColumn::Id).count(), "num")
+            .filter(filter)
+            .group_by(year.clone())
+            .group_by(month.clone())
+            .order_by(year, Order::Desc)
+            .order_by(month, Order::Desc)
+            .into_tuple()
+            .all(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn replace_pins(&self, ids: Vec<i32>) -> AppResult<()> {
+        let txn = self.db_conn().begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        ArticleEntity::update_many()
+            .col_expr(ArticleColumn::IsTop, Expr::value(false))
+            .col_expr(ArticleColumn::PinPriority, Expr::value(0))
+            .col_expr(ArticleColumn::UpdateTime, keep_update_time())
+            .filter(ArticleColumn::IsTop.eq(true))
+            .exec(&txn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?;
+        let pin_num = ids.len() as i32;
+        for (index, id) in ids.into_iter().enumerate() {
+            let update_res = ArticleEntity::update_many()
+                .col_expr(ArticleColumn::IsTop, Expr::value(true))
+                .col_expr(
+                    ArticleColumn::PinPriority,
+                    Expr::value(pin_num - index as i32),
+                )
+                .col_expr(ArticleColumn::UpdateTime, keep_update_time())
+                .filter(ArticleColumn::Id.eq(id))
+                .exec(&txn)
+                .await
+                .with_err_kind(AppErrorKind::DBOperationError)?;
+            if update_res.rows_affected == 0 {
+                return Err(AppError::new(
+                    format!("Data not found, article_id: {}", id),
+                    AppErrorKind::ResourceNotFound,
+                ));
+            }
+        }
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Datelike;
+    use sea_orm::EntityTrait;
+
+    use super::*;
+    use crate::app::{
+        config::AppConfig,
+        dao::{
+            impls::create_test_user,
+            traits::prelude::{DataAccess, SoftDeleteDataAccess},
+            types::{OrderParam, PaginateParam},
+        },
+        db::prelude::{create_db_conn, UserEntity},
+    };
+
+    #[tokio::test]
+    async fn test_article_dao() {
+        // init
+        let cfg = AppConfig::init("config/config_test.toml").unwrap();
+        let db_conn = Arc::new(create_db_conn(&cfg.db).await.unwrap());
+        let article_dao = ArticleDAO::new(db_conn.clone());
+
+        // prepare an admin author, deleting it cascades to all test articles
+        let author = create_test_user(db_conn.as_ref(), "test_article_author", 1).await;
+        let test_filter = ArticleFilterParam {
+            create_user_id: Some(author.id),
+            ..Default::default()
+        };
+
+        // test create success
+        let create_param = ArticleCreateParam {
+            title: "test_article".to_string(),
+            slug: "test-article".to_string(),
+            description: "test".to_string(),
+            content: "test".to_string(),
+            is_top: false,
+            status_type: 0,
+            publish_at: None,
+            unpublish_at: None,
+            publish_time: None,
+            create_user_id: author.id,
+        };
+        let article = <ArticleDAO as DataAccess>::create(&article_dao, create_param.clone())
+            .await
+            .unwrap();
+        assert_eq!(article.title, "test_article");
+        assert_eq!(article.update_user_id, author.id);
+
+        // test count
+        let article_num = <ArticleDAO as DataAccess>::count(&article_dao, test_filter.clone())
+            .await
+            .unwrap();
+        assert_eq!(article_num, 1);
+
+        // test get
+        let article_filter = ArticleFilterParam {
+            id: Some(article.id),
+            ..Default::default()
+        };
+        let article = <ArticleDAO as DataAccess>::get(&article_dao, article_filter.clone())
+            .await
+            .unwrap();
+        assert_eq!(article.content, "test");
+
+        // test get failed(article not found)
+        let article_not_found_filter = ArticleFilterParam {
+            id: Some(-1),
+            ..Default::default()
+        };
+        let get_res =
+            <ArticleDAO as DataAccess>::get(&article_dao, article_not_found_filter.clone()).await;
+        assert!(get_res.is_err());
+
+        // test create_many
+        let bulk_create_param = vec![
+            ArticleCreateParam {
+                title: "test_article1".to_string(),
+                slug: "test-article1".to_string(),
+                status_type: 1,
+                ..create_param.clone()
+            },
+            ArticleCreateParam {
+                title: "test_article2".to_string(),
+                slug: "test-article2".to_string(),
+                is_top: true,
+                status_type: 1,
+                publish_at: Some(article.create_time + chrono::Duration::days(1)),
+                ..create_param.clone()
+            },
+        ];
+        let create_num = <ArticleDAO as DataAccess>::create_many(&article_dao, bulk_create_param)
+            .await
+            .unwrap();
+        assert_eq!(create_num, 2);
+        let article_num = <ArticleDAO as DataAccess>::count(&article_dao, test_filter.clone())
+            .await
+            .unwrap();
+        assert_eq!(article_num, 3);
+
+        // test filter
+        let published_num = <ArticleDAO as DataAccess>::count(
+            &article_dao,
+            ArticleFilterParam {
+                status_type: Some(1),
+                ..test_filter.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(published_num, 2);
+        let top_num = <ArticleDAO as DataAccess>::count(
+            &article_dao,
+            ArticleFilterParam {
+                is_top: Some(true),
+                title_search: Some("article2".to_string()),
+                ..test_filter.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(top_num, 1);
+        let in_range_num = <ArticleDAO as DataAccess>::count(
+            &article_dao,
+            ArticleFilterParam {
+                create_time_start: Some(article.create_time),
+                create_time_end: Some(article.create_time),
+                ..test_filter.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(in_range_num, 0);
+        let live_num = <ArticleDAO as DataAccess>::count(
+            &article_dao,
+            ArticleFilterParam {
+                status_type: Some(1),
+                live_at: Some(article.create_time),
+                ..test_filter.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(live_num, 1);
+
+        // test list
+        let article_list = <ArticleDAO as DataAccess>::list(
+            &article_dao,
+            test_filter.clone(),
+            OrderParam::<ArticleAttr>::default(),
+            PaginateParam::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(article_list.len(), 3);
+        assert_eq!(article_list[2].title, "test_article");
+
+        // test update success
+        let update_param = ArticleUpdateParam {
+            title: Some("test_title".to_string()),
+            ..Default::default()
+        };
+        <ArticleDAO as DataAccess>::update(
+            &article_dao,
+            article_filter.clone(),
+            update_param.clone(),
+        )
+        .await
+        .unwrap();
+        let article = <ArticleDAO as DataAccess>::get(&article_dao, article_filter.clone());
+        assert_eq!(article.await.unwrap().title, "test_title");
+
+        // test add_view_count
+        let article_id = article_filter.id.unwrap();
+        article_dao.add_view_count(article_id, 3).await.unwrap();
+        article_dao.add_view_count(article_id, 2).await.unwrap();
+        let article = <ArticleDAO as DataAccess>::get(&article_dao, article_filter.clone());
+        assert_eq!(article.await.unwrap().view_count, 5);
+
+        // test get_create_user
+        let create_user = article_dao.get_create_user(article_id).await.unwrap();
+        assert_eq!(create_user.nickname, "test_article_author");
+
+        // test list_slugs
+        let slugs = article_dao.list_slugs(test_filter.clone()).await.unwrap();
+        assert_eq!(slugs.len(), 3);
+        assert_eq!(slugs[0].0, "test-article");
+
+        // test count_by_month
+        let months = article_dao
+            .count_by_month(test_filter.clone())
+            .await
+            .unwrap();
+        let article = <ArticleDAO as DataAccess>::get(&article_dao, article_filter.clone());
+        let create_time = article.await.unwrap().create_time;
+        assert_eq!(
+            months,
+            vec![(create_time.year(), create_time.month() as i32, 3)]
+        );
+        // the publish time wins over the create time
+        let publish_time = DateTime::parse_from_str("2001-02-03 04:05:06", "%Y-%m-%d %H:%M:%S");
+        <ArticleDAO as DataAccess>::update(
+            &article_dao,
+            article_filter.clone(),
+            ArticleUpdateParam {
+                publish_time: Some(publish_time.unwrap()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let months = article_dao
+            .count_by_month(test_filter.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            months,
+            vec![
+                (create_time.year(), create_time.month() as i32, 2),
+                (2001, 2, 1)
+            ]
+        );
+        let published_num = <ArticleDAO as DataAccess>::count(
+            &article_dao,
+            ArticleFilterParam {
+                publish_time_start: publish_time.ok(),
+                publish_time_end: Some(create_time),
+                ..test_filter.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(published_num, 1);
+
+        // test replace_pins
+        article_dao
+            .replace_pins(vec![article_list[1].id, article_list[0].id])
+            .await
+            .unwrap();
+        let pin_order = OrderParam {
+            by: ArticleAttr::IsTop,
+            ascending: false,
+            then: vec![(ArticleAttr::PinPriority, false), (ArticleAttr::Id, true)],
+        };
+        let pinned_list = <ArticleDAO as DataAccess>::list(
+            &article_dao,
+            test_filter.clone(),
+            pin_order,
+            PaginateParam::default(),
+        )
+        .await
+        .unwrap();
+        let pinned_ids: Vec<i32> = pinned_list.iter().map(|model| model.id).collect();
+        assert_eq!(
+            pinned_ids,
+            vec![article_list[1].id, article_list[0].id, article_id]
+        );
+        assert!(!pinned_list[2].is_top);
+
+        // test replace_pins failed(article not found), nothing changes
+        assert!(article_dao
+            .replace_pins(vec![article_id, -1])
+            .await
+            .is_err());
+        let top_num = <ArticleDAO as DataAccess>::count(
+            &article_dao,
+            ArticleFilterParam {
+                is_top: Some(true),
+                ..test_filter.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(top_num, 2);
+
+        // test soft_delete and restore, the schedule is dropped on the way
+        let scheduled_filter = ArticleFilterParam {
+            id: Some(article_list[0].id),
+            ..Default::default()
+        };
+        <ArticleDAO as SoftDeleteDataAccess>::soft_delete(
+            &article_dao,
+            scheduled_filter.clone(),
+            author.id,
+        )
+        .await
+        .unwrap();
+        <ArticleDAO as SoftDeleteDataAccess>::restore(&article_dao, scheduled_filter.clone())
+            .await
+            .unwrap();
+        let scheduled = <ArticleDAO as DataAccess>::get(&article_dao, scheduled_filter);
+        let scheduled = scheduled.await.unwrap();
+        assert_eq!(scheduled.status_type, 0);
+        assert_eq!(scheduled.publish_at, None);
+
+        // test update failed(multiple data found)
+        let update_res = <ArticleDAO as DataAccess>::update(
+            &article_dao,
+            test_filter.clone(),
+            update_param.clone(),
+        )
+        .await;
+        assert!(update_res.is_err());
+
+        // test update_all
+        let update_num = <ArticleDAO as DataAccess>::update_all(
+            &article_dao,
+            test_filter.clone(),
+            update_param.clone(),
+        );
+        assert_eq!(update_num.await.unwrap(), 3);
+
+        // test delete success
+        <ArticleDAO as DataAccess>::delete(&article_dao, article_filter)
+            .await
+            .unwrap();
+        let article_num = <ArticleDAO as DataAccess>::count(&article_dao, test_filter.clone())
+            .await
+            .unwrap();
+        assert_eq!(article_num, 2);
+
+        // test delete failed(multiple data found)
+        let delete_res =
+            <ArticleDAO as DataAccess>::delete(&article_dao, test_filter.clone()).await;
+        assert!(delete_res.is_err());
+
+        // test delete_all
+        let delete_num = <ArticleDAO as DataAccess>::delete_all(&article_dao, test_filter.clone())
+            .await
+            .unwrap();
+        assert_eq!(delete_num, 2);
+
+        // clean up
+        UserEntity::delete_by_id(author.id)
+            .exec(db_conn.as_ref())
+            .await
+            .unwrap();
+    }
+}
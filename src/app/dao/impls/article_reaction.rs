@@ -0,0 +1,228 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::{
+    ColumnTrait, Condition, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect,
+    Set, TransactionTrait,
+};
+use sea_query::{Expr, IntoCondition};
+
+use super::{
+    super::{
+        traits::article_reaction::ArticleReactionDataAccess, types::article_reaction::prelude::*,
+    },
+    DBConnProvider, RelationDataAccessImpl,
+};
+use crate::app::{
+    common::prelude::*,
+    db::prelude::{
+        ArticleReactionActiveModel, ArticleReactionColumn, ArticleReactionEntity,
+        DatabaseConnection,
+    },
+};
+
+// ********************* content ********************* //
+// params
+impl IntoCondition for ArticleReactionFilterParam {
+    fn into_condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(article_id) = self.article_id {
+            condition = condition.add(ArticleReactionColumn::ArticleId.eq(article_id));
+        }
+        if let Some(user_id) = self.user_id {
+            condition = condition.add(ArticleReactionColumn::UserId.eq(user_id));
+        }
+        if let Some(kind) = self.kind {
+            condition = condition.add(ArticleReactionColumn::Kind.eq(kind));
+        }
+        condition
+    }
+}
+
+impl IntoActiveModel<ArticleReactionActiveModel> for ArticleReactionCreateParam {
+    fn into_active_model(self) -> ArticleReactionActiveModel {
+        ArticleReactionActiveModel {
+            article_id: Set(self.article_id),
+            user_id: Set(self.user_id),
+            kind: Set(self.kind),
+            ..Default::default()
+        }
+    }
+}
+
+// dao
+pub struct ArticleReactionDAO {
+    db_conn: Arc<DatabaseConnection>,
+}
+
+impl ArticleReactionDAO {
+    pub fn new(db_conn: Arc<DatabaseConnection>) -> Self {
+        Self { db_conn }
+    }
+}
+
+impl DBConnProvider for ArticleReactionDAO {
+    fn db_conn(&self) -> &DatabaseConnection {
+        &self.db_conn
+    }
+}
+
+#[async_trait]
+impl RelationDataAccessImpl for ArticleReactionDAO {
+    type FilterParam = ArticleReactionFilterParam;
+    type CreateParam = ArticleReactionCreateParam;
+    type Model = ArticleReactionDataModel;
+    type Entity = ArticleReactionEntity;
+    type ActiveModel = ArticleReactionActiveModel;
+}
+
+#[async_trait]
+impl ArticleReactionDataAccess for ArticleReactionDAO {
+    async fn add_reaction(&self, article_id: i32, user_id: i32, kind: String) -> AppResult<bool> {
+        let txn = self.db_conn().begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        let filter = ArticleReactionFilterParam {
+            article_id: Some(article_id),
+            user_id: Some(user_id),
+            kind: Some(kind.clone()),
+        };
+        if <Self as RelationDataAccessImpl>::count(self, &txn, filter).await? > 0 {
+            return Ok(false);
+        }
+        let create_param = vec![ArticleReactionCreateParam {
+            article_id,
+            user_id,
+            kind,
+        }];
+        <Self as RelationDataAccessImpl>::create_many(self, &txn, create_param).await?;
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        Ok(true)
+    }
+
+    async fn count_by_kind(&self, article_id: i32) -> AppResult<Vec<(String, i64)>> {
+        ArticleReactionEntity::find()
+            .select_only()
+            .column(ArticleReactionColumn::Kind)
+            .column_as(Expr::col(ArticleReactionColumn::Kind).count(), "num")
+            .filter(ArticleReactionColumn::ArticleId.eq(article_id))
+            .group_by(ArticleReactionColumn::Kind)
+            .order_by_asc(ArticleReactionColumn::Kind)
+            .into_tuple()
+            .all(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::ActiveModelTrait;
+
+    use super::*;
+    use crate::app::{
+        config::AppConfig,
+        dao::{impls::create_test_user, traits::prelude::RelationDataAccess},
+        db::prelude::{create_db_conn, ArticleActiveModel, ArticleEntity, UserColumn, UserEntity},
+    };
+
+    #[tokio::test]
+    async fn test_article_reaction_dao() {
+        // init
+        let cfg = AppConfig::init("config/config_test.toml").unwrap();
+        let db_conn = Arc::new(create_db_conn(&cfg.db).await.unwrap());
+        let article_reaction_dao = ArticleReactionDAO::new(db_conn.clone());
+
+        // prepare an author and a reader, deleting them cascades to all test data
+        let author = create_test_user(db_conn.as_ref(), "test_reaction_author", 0).await;
+        let reader = create_test_user(db_conn.as_ref(), "test_reaction_reader", 0).await;
+        let article = ArticleActiveModel {
+            title: Set("test_reaction_article".to_string()),
+            slug: Set("test-reaction-article".to_string()),
+            description: Set("".to_string()),
+            content: Set("".to_string()),
+            status_type: Set(1),
+            create_user_id: Set(author.id),
+            update_user_id: Set(author.id),
+            ..Default::default()
+        }
+        .insert(db_conn.as_ref())
+        .await
+        .unwrap();
+
+        // test add_reaction
+        for (user_id, kind) in [(author.id, "like"), (reader.id, "like"), (reader.id, "🎉")] {
+            let created = article_reaction_dao
+                .add_reaction(article.id, user_id, kind.to_string())
+                .await
+                .unwrap();
+            assert!(created);
+        }
+        let created = article_reaction_dao
+            .add_reaction(article.id, reader.id, "like".to_string())
+            .await
+            .unwrap();
+        assert!(!created);
+
+        // test count_by_kind
+        let counts = article_reaction_dao
+            .count_by_kind(article.id)
+            .await
+            .unwrap();
+        assert_eq!(counts, [("like".to_string(), 2), ("🎉".to_string(), 1)]);
+
+        // test list_all
+        let mine = <ArticleReactionDAO as RelationDataAccess>::list_all(
+            &article_reaction_dao,
+            ArticleReactionFilterParam {
+                article_id: Some(article.id),
+                user_id: Some(reader.id),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(mine.len(), 2);
+
+        // test delete_all
+        let delete_num = <ArticleReactionDAO as RelationDataAccess>::delete_all(
+            &article_reaction_dao,
+            ArticleReactionFilterParam {
+                article_id: Some(article.id),
+                user_id: Some(reader.id),
+                kind: Some("like".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(delete_num, 1);
+
+        // test cascade on article deletion
+        ArticleEntity::delete_by_id(article.id)
+            .exec(db_conn.as_ref())
+            .await
+            .unwrap();
+        let reaction_num = <ArticleReactionDAO as RelationDataAccess>::count(
+            &article_reaction_dao,
+            ArticleReactionFilterParam {
+                article_id: Some(article.id),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(reaction_num, 0);
+
+        // clean up
+        UserEntity::delete_many()
+            .filter(UserColumn::Id.is_in([author.id, reader.id]))
+            .exec(db_conn.as_ref())
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,299 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Local;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, IntoActiveModel, IntoSimpleExpr,
+    QueryFilter, Set, TransactionTrait,
+};
+use sea_query::{IntoCondition, SimpleExpr};
+
+use super::{
+    super::{
+        traits::article_revision::ArticleRevisionDataAccess,
+        types::{
+            article::prelude::*, article_revision::prelude::*,
+            slug_history::prelude::SLUG_RESOURCE_ARTICLE,
+        },
+    },
+    slug_history::record_slug_change,
+    DBConnProvider, DataAccessImpl,
+};
+use crate::app::{
+    common::prelude::*,
+    db::prelude::{
+        ArticleColumn, ArticleEntity, ArticleRevisionActiveModel, ArticleRevisionColumn,
+        ArticleRevisionEntity, DatabaseConnection,
+    },
+};
+
+// ********************* content ********************* //
+// params
+impl IntoCondition for ArticleRevisionFilterParam {
+    fn into_condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(id) = self.id {
+            condition = condition.add(ArticleRevisionColumn::Id.eq(id));
+        }
+        if let Some(ids) = self.ids {
+            condition = condition.add(ArticleRevisionColumn::Id.is_in(ids));
+        }
+        if let Some(article_id) = self.article_id {
+            condition = condition.add(ArticleRevisionColumn::ArticleId.eq(article_id));
+        }
+        condition
+    }
+}
+
+impl IntoActiveModel<ArticleRevisionActiveModel> for ArticleRevisionCreateParam {
+    fn into_active_model(self) -> ArticleRevisionActiveModel {
+        ArticleRevisionActiveModel {
+            article_id: Set(self.article_id),
+            title: Set(self.title),
+            description: Set(self.description),
+            content: Set(self.content),
+            update_user_id: Set(Some(self.update_user_id)),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoActiveModel<ArticleRevisionActiveModel> for ArticleRevisionUpdateParam {
+    fn into_active_model(self) -> ArticleRevisionActiveModel {
+        <ArticleRevisionActiveModel as Default>::default()
+    }
+}
+
+impl IntoSimpleExpr for ArticleRevisionAttr {
+    fn into_simple_expr(self) -> SimpleExpr {
+        match self {
+            ArticleRevisionAttr::Id => ArticleRevisionColumn::Id,
+            ArticleRevisionAttr::CreateTime => ArticleRevisionColumn::CreateTime,
+        }
+        .into_simple_expr()
+    }
+}
+
+impl From<ArticleDataModel> for ArticleRevisionCreateParam {
+    fn from(model: ArticleDataModel) -> Self {
+        Self {
+            article_id: model.id,
+            title: model.title,
+            description: model.description,
+            content: model.content,
+            update_user_id: model.update_user_id,
+        }
+    }
+}
+
+// articles in the trash only come back through restore, so they are never updated here
+async fn get_article<C: ConnectionTrait>(
+    db_conn: &C,
+    article_id: i32,
+) -> AppResult<ArticleDataModel> {
+    ArticleEntity::find_by_id(article_id)
+        .filter(ArticleColumn::StatusType.is_in([0, 1, 2]))
+        .one(db_conn)
+        .await
+        .with_err_kind(AppErrorKind::DBOperationError)?
+        .ok_or_else(|| {
+            AppError::new(
+                format!("Data not found, article_id: {}", article_id),
+                AppErrorKind::ResourceNotFound,
+            )
+        })
+}
+
+// dao
+pub struct ArticleRevisionDAO {
+    db_conn: Arc<DatabaseConnection>,
+}
+
+impl ArticleRevisionDAO {
+    pub fn new(db_conn: Arc<DatabaseConnection>) -> Self {
+        Self { db_conn }
+    }
+}
+
+impl DBConnProvider for ArticleRevisionDAO {
+    fn db_conn(&self) -> &DatabaseConnection {
+        &self.db_conn
+    }
+}
+
+#[async_trait]
+impl DataAccessImpl for ArticleRevisionDAO {
+    type DataAttr = ArticleRevisionAttr;
+    type FilterParam = ArticleRevisionFilterParam;
+    type CreateParam = ArticleRevisionCreateParam;
+    type UpdateParam = ArticleRevisionUpdateParam;
+    type Model = ArticleRevisionDataModel;
+    type Entity = ArticleRevisionEntity;
+    type ActiveModel = ArticleRevisionActiveModel;
+}
+
+#[async_trait]
+impl ArticleRevisionDataAccess for ArticleRevisionDAO {
+    async fn update_article(
+        &self,
+        article_id: i32,
+        mut update_param: ArticleUpdateParam,
+    ) -> AppResult<ArticleDataModel> {
+        let txn = self.db_conn().begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        let old_model = get_article(&txn, article_id).await?;
+        if update_param.status_type == Some(1) && old_model.publish_time.is_none() {
+            update_param
+                .publish_time
+                .get_or_insert_with(|| Local::now().naive_local());
+        }
+        ArticleEntity::update_many()
+            .set(update_param.into_active_model())
+            .filter(ArticleColumn::Id.eq(article_id))
+            .exec(&txn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?;
+        let article_model = get_article(&txn, article_id).await?;
+        // revisions keep the written content only, the status and schedule are not part of
+        // them and the slug has its own history
+        if (
+            &article_model.title,
+            &article_model.description,
+            &article_model.content,
+        ) != (&old_model.title, &old_model.description, &old_model.content)
+        {
+            let revision_filter = ArticleRevisionFilterParam {
+                article_id: Some(article_id),
+                ..Default::default()
+            };
+            // articles written before revisions existed have no baseline yet
+            if <Self as DataAccessImpl>::count(self, &txn, revision_filter).await? == 0 {
+                <Self as DataAccessImpl>::create(self, &txn, old_model.clone().into()).await?;
+            }
+            <Self as DataAccessImpl>::create(self, &txn, article_model.clone().into()).await?;
+        }
+        if article_model.slug != old_model.slug {
+            record_slug_change(
+                &txn,
+                SLUG_RESOURCE_ARTICLE,
+                article_id,
+                old_model.slug,
+                &article_model.slug,
+            )
+            .await?;
+        }
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        Ok(article_model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::ActiveModelTrait;
+
+    use super::*;
+    use crate::app::{
+        config::AppConfig,
+        dao::{impls::create_test_user, traits::prelude::DataAccess, types::OrderParam},
+        db::prelude::{create_db_conn, ArticleActiveModel, UserEntity},
+    };
+
+    #[tokio::test]
+    async fn test_article_revision_dao() {
+        // init
+        let cfg = AppConfig::init("config/config_test.toml").unwrap();
+        let db_conn = Arc::new(create_db_conn(&cfg.db).await.unwrap());
+        let article_revision_dao = ArticleRevisionDAO::new(db_conn.clone());
+
+        // prepare an author and an article, deleting the author cascades to both
+        let author = create_test_user(db_conn.as_ref(), "test_article_revision_author", 0).await;
+        let article = ArticleActiveModel {
+            title: Set("test_article".to_string()),
+            slug: Set("test-article-revision".to_string()),
+            description: Set("".to_string()),
+            content: Set("v1".to_string()),
+            create_user_id: Set(author.id),
+            update_user_id: Set(author.id),
+            ..Default::default()
+        }
+        .insert(db_conn.as_ref())
+        .await
+        .unwrap();
+
+        // test update_article snapshots the baseline and the new state
+        let article = article_revision_dao
+            .update_article(
+                article.id,
+                ArticleUpdateParam {
+                    content: Some("v2".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(article.content, "v2");
+        article_revision_dao
+            .update_article(
+                article.id,
+                ArticleUpdateParam {
+                    content: Some("v3".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let revision_list = <ArticleRevisionDAO as DataAccess>::list_all(
+            &article_revision_dao,
+            ArticleRevisionFilterParam {
+                article_id: Some(article.id),
+                ..Default::default()
+            },
+            OrderParam::<ArticleRevisionAttr>::default(),
+        )
+        .await
+        .unwrap();
+        let contents: Vec<&str> = revision_list.iter().map(|r| r.content.as_str()).collect();
+        assert_eq!(contents, ["v1", "v2", "v3"]);
+
+        // test update_article without a content change adds no revision
+        article_revision_dao
+            .update_article(
+                article.id,
+                ArticleUpdateParam {
+                    status_type: Some(1),
+                    content: Some("v3".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        let revision_num = <ArticleRevisionDAO as DataAccess>::count(
+            &article_revision_dao,
+            ArticleRevisionFilterParam {
+                article_id: Some(article.id),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(revision_num, 3);
+
+        // test update_article failed(article not found)
+        let update_res = article_revision_dao
+            .update_article(-1, ArticleUpdateParam::default())
+            .await;
+        assert!(update_res.is_err());
+
+        // clean up
+        UserEntity::delete_by_id(author.id)
+            .exec(db_conn.as_ref())
+            .await
+            .unwrap();
+    }
+}
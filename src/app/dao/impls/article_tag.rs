@@ -0,0 +1,445 @@
+// ********************* import ********************* //
+use std::{collections::HashSet, sync::Arc};
+
+use async_trait::async_trait;
+use sea_orm::{
+    ColumnTrait, Condition, EntityTrait, IntoActiveModel, ModelTrait, Order, PaginatorTrait,
+    QueryFilter, QueryOrder, Select, Set, TransactionTrait,
+};
+use sea_query::{Expr, IntoCondition, Query};
+
+use super::{
+    super::{
+        traits::article_tag::ArticleTagDataAccess,
+        types::{
+            article::prelude::*, article_tag::prelude::*, tag::prelude::*, OrderParam,
+            PaginateParam,
+        },
+    },
+    DBConnProvider, RelationDataAccessImpl,
+};
+use crate::app::{
+    common::prelude::*,
+    db::prelude::{
+        ArticleColumn, ArticleEntity, ArticleTagActiveModel, ArticleTagColumn, ArticleTagEntity,
+        ArticleToTagLink, DatabaseConnection, TagColumn,
+    },
+};
+
+// ********************* content ********************* //
+// params
+impl IntoCondition for ArticleTagFilterParam {
+    fn into_condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(article_id) = self.article_id {
+            condition = condition.add(ArticleTagColumn::ArticleId.eq(article_id));
+        }
+        if let Some(tag_id) = self.tag_id {
+            condition = condition.add(ArticleTagColumn::TagId.eq(tag_id));
+        }
+        if let Some(tag_ids) = self.tag_ids {
+            condition = condition.add(ArticleTagColumn::TagId.is_in(tag_ids));
+        }
+        if let Some(is_pinned) = self.is_pinned {
+            condition = condition.add(match is_pinned {
+                true => ArticleTagColumn::PinPriority.is_not_null(),
+                false => ArticleTagColumn::PinPriority.is_null(),
+            });
+        }
+        condition
+    }
+}
+
+impl IntoActiveModel<ArticleTagActiveModel> for ArticleTagCreateParam {
+    fn into_active_model(self) -> ArticleTagActiveModel {
+        ArticleTagActiveModel {
+            article_id: Set(self.article_id),
+            tag_id: Set(self.tag_id),
+            ..Default::default()
+        }
+    }
+}
+
+fn dedup(tag_ids: Vec<i32>) -> Vec<i32> {
+    let mut seen = HashSet::new();
+    tag_ids.into_iter().filter(|id| seen.insert(*id)).collect()
+}
+
+// a subquery instead of a join with DISTINCT, which would restrict ORDER BY to the
+// selected columns
+fn select_articles(tag_ids: Vec<i32>, filter: ArticleFilterParam) -> Select<ArticleEntity> {
+    let article_ids = Query::select()
+        .column(ArticleTagColumn::ArticleId)
+        .from(ArticleTagEntity)
+        .and_where(ArticleTagColumn::TagId.is_in(tag_ids))
+        .to_owned();
+    ArticleEntity::find()
+        .filter(ArticleColumn::Id.in_subquery(article_ids))
+        .filter(filter)
+}
+
+// dao
+pub struct ArticleTagDAO {
+    db_conn: Arc<DatabaseConnection>,
+}
+
+impl ArticleTagDAO {
+    pub fn new(db_conn: Arc<DatabaseConnection>) -> Self {
+        Self { db_conn }
+    }
+}
+
+impl DBConnProvider for ArticleTagDAO {
+    fn db_conn(&self) -> &DatabaseConnection {
+        &self.db_conn
+    }
+}
+
+#[async_trait]
+impl RelationDataAccessImpl for ArticleTagDAO {
+    type FilterParam = ArticleTagFilterParam;
+    type CreateParam = ArticleTagCreateParam;
+    type Model = ArticleTagDataModel;
+    type Entity = ArticleTagEntity;
+    type ActiveModel = ArticleTagActiveModel;
+}
+
+#[async_trait]
+impl ArticleTagDataAccess for ArticleTagDAO {
+    async fn replace_tags(&self, article_id: i32, tag_ids: Vec<i32>) -> AppResult<()> {
+        let txn = self.db_conn().begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        let filter = ArticleTagFilterParam {
+            article_id: Some(article_id),
+            ..Default::default()
+        };
+        <Self as RelationDataAccessImpl>::delete_all(self, &txn, filter).await?;
+        let create_param = dedup(tag_ids)
+            .into_iter()
+            .map(|tag_id| ArticleTagCreateParam { article_id, tag_id })
+            .collect();
+        <Self as RelationDataAccessImpl>::create_many(self, &txn, create_param).await?;
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )
+    }
+
+    async fn add_tags(&self, article_id: i32, tag_ids: Vec<i32>) -> AppResult<u64> {
+        let txn = self.db_conn().begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        let filter = ArticleTagFilterParam {
+            article_id: Some(article_id),
+            tag_ids: Some(tag_ids.clone()),
+            ..Default::default()
+        };
+        let existing_ids: HashSet<i32> =
+            <Self as RelationDataAccessImpl>::list_all(self, &txn, filter)
+                .await?
+                .into_iter()
+                .map(|model| model.tag_id)
+                .collect();
+        let create_param = dedup(tag_ids)
+            .into_iter()
+            .filter(|tag_id| !existing_ids.contains(tag_id))
+            .map(|tag_id| ArticleTagCreateParam { article_id, tag_id })
+            .collect();
+        let create_num =
+            <Self as RelationDataAccessImpl>::create_many(self, &txn, create_param).await?;
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        Ok(create_num)
+    }
+
+    async fn remove_tags(&self, article_id: i32, tag_ids: Vec<i32>) -> AppResult<u64> {
+        let filter = ArticleTagFilterParam {
+            article_id: Some(article_id),
+            tag_ids: Some(tag_ids),
+            ..Default::default()
+        };
+        <Self as RelationDataAccessImpl>::delete_all(self, self.db_conn(), filter).await
+    }
+
+    async fn list_tags(&self, article_id: i32) -> AppResult<Vec<TagDataModel>> {
+        ArticleEntity::find_by_id(article_id)
+            .one(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?
+            .ok_or_else(|| {
+                AppError::new(
+                    format!("Data not found, article_id: {}", article_id),
+                    AppErrorKind::ResourceNotFound,
+                )
+            })?
+            .find_linked(ArticleToTagLink)
+            .order_by_asc(TagColumn::Id)
+            .all(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn count_articles(
+        &self,
+        tag_ids: Vec<i32>,
+        filter: ArticleFilterParam,
+    ) -> AppResult<u64> {
+        select_articles(tag_ids, filter)
+            .count(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn list_articles(
+        &self,
+        tag_ids: Vec<i32>,
+        filter: ArticleFilterParam,
+        order: OrderParam<ArticleAttr>,
+        paginate: PaginateParam,
+    ) -> AppResult<Vec<ArticleDataModel>> {
+        order
+            .apply(select_articles(tag_ids, filter))
+            .paginate(self.db_conn(), paginate.page_size)
+            .fetch_page(paginate.page_num.saturating_sub(1))
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn replace_pins(&self, tag_id: i32, article_ids: Vec<i32>) -> AppResult<()> {
+        let txn = self.db_conn().begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        ArticleTagEntity::update_many()
+            .col_expr(
+                ArticleTagColumn::PinPriority,
+                Expr::value(Option::<i32>::None),
+            )
+            .filter(ArticleTagFilterParam {
+                tag_id: Some(tag_id),
+                is_pinned: Some(true),
+                ..Default::default()
+            })
+            .exec(&txn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?;
+        let article_ids = dedup(article_ids);
+        let pin_num = article_ids.len() as i32;
+        for (index, article_id) in article_ids.into_iter().enumerate() {
+            let update_res = ArticleTagEntity::update_many()
+                .col_expr(
+                    ArticleTagColumn::PinPriority,
+                    Expr::value(pin_num - index as i32),
+                )
+                .filter(ArticleTagFilterParam {
+                    article_id: Some(article_id),
+                    tag_id: Some(tag_id),
+                    ..Default::default()
+                })
+                .exec(&txn)
+                .await
+                .with_err_kind(AppErrorKind::DBOperationError)?;
+            if update_res.rows_affected == 0 {
+                return Err(AppError::new(
+                    format!(
+                        "Data not found, article_id: {}, tag_id: {}",
+                        article_id, tag_id
+                    ),
+                    AppErrorKind::ResourceNotFound,
+                ));
+            }
+        }
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )
+    }
+
+    async fn list_pins(&self, tag_id: i32) -> AppResult<Vec<ArticleDataModel>> {
+        let article_ids = Query::select()
+            .column(ArticleTagColumn::ArticleId)
+            .from(ArticleTagEntity)
+            .cond_where(ArticleTagFilterParam {
+                tag_id: Some(tag_id),
+                is_pinned: Some(true),
+                ..Default::default()
+            })
+            .to_owned();
+        ArticleEntity::find()
+            .filter(ArticleColumn::Id.in_subquery(article_ids))
+            .order_by(ArticleAttr::TagPinPriority(tag_id), Order::Desc)
+            .all(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::ActiveModelTrait;
+
+    use super::*;
+    use crate::app::{
+        config::AppConfig,
+        dao::{impls::create_test_user, traits::prelude::RelationDataAccess},
+        db::prelude::{create_db_conn, ArticleActiveModel, TagActiveModel, UserEntity},
+    };
+
+    #[tokio::test]
+    async fn test_article_tag_dao() {
+        // init
+        let cfg = AppConfig::init("config/config_test.toml").unwrap();
+        let db_conn = Arc::new(create_db_conn(&cfg.db).await.unwrap());
+        let article_tag_dao = ArticleTagDAO::new(db_conn.clone());
+
+        // prepare an admin author, deleting it cascades to all test data
+        let author = create_test_user(db_conn.as_ref(), "test_article_tag_author", 1).await;
+        let mut articles = Vec::new();
+        for title in ["test_article1", "test_article2"] {
+            let article = ArticleActiveModel {
+                title: Set(title.to_string()),
+                slug: Set(format!("article-tag-{}", title.replace('_', "-"))),
+                description: Set("".to_string()),
+                content: Set("".to_string()),
+                status_type: Set(1),
+                create_user_id: Set(author.id),
+                update_user_id: Set(author.id),
+                ..Default::default()
+            }
+            .insert(db_conn.as_ref())
+            .await
+            .unwrap();
+            articles.push(article);
+        }
+        let mut tags = Vec::new();
+        for name in ["test_tag1", "test_tag2", "test_tag3"] {
+            let tag = TagActiveModel {
+                name: Set(name.to_string()),
+                slug: Set(format!("article-tag-{}", name.replace('_', "-"))),
+                create_user_id: Set(author.id),
+                update_user_id: Set(author.id),
+                ..Default::default()
+            }
+            .insert(db_conn.as_ref())
+            .await
+            .unwrap();
+            tags.push(tag);
+        }
+
+        // test replace_tags
+        article_tag_dao
+            .replace_tags(articles[0].id, vec![tags[0].id, tags[1].id, tags[0].id])
+            .await
+            .unwrap();
+        let tag_list = article_tag_dao.list_tags(articles[0].id).await.unwrap();
+        assert_eq!(tag_list.len(), 2);
+        assert_eq!(tag_list[0].id, tags[0].id);
+        article_tag_dao
+            .replace_tags(articles[0].id, vec![tags[2].id])
+            .await
+            .unwrap();
+        let tag_list = article_tag_dao.list_tags(articles[0].id).await.unwrap();
+        assert_eq!(tag_list.len(), 1);
+        assert_eq!(tag_list[0].id, tags[2].id);
+
+        // test add_tags
+        let add_num = article_tag_dao
+            .add_tags(articles[0].id, vec![tags[1].id, tags[2].id])
+            .await
+            .unwrap();
+        assert_eq!(add_num, 1);
+        article_tag_dao
+            .add_tags(articles[1].id, vec![tags[1].id])
+            .await
+            .unwrap();
+        let relation_num = <ArticleTagDAO as RelationDataAccess>::count(
+            &article_tag_dao,
+            ArticleTagFilterParam {
+                tag_id: Some(tags[1].id),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(relation_num, 2);
+
+        // test count_articles and list_articles
+        let tag_ids = vec![tags[1].id, tags[2].id];
+        let article_num = article_tag_dao
+            .count_articles(tag_ids.clone(), ArticleFilterParam::default())
+            .await
+            .unwrap();
+        assert_eq!(article_num, 2);
+        let article_list = article_tag_dao
+            .list_articles(
+                tag_ids,
+                ArticleFilterParam {
+                    title_search: Some("article1".to_string()),
+                    ..Default::default()
+                },
+                OrderParam::<ArticleAttr>::default(),
+                PaginateParam::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(article_list.len(), 1);
+        assert_eq!(article_list[0].id, articles[0].id);
+
+        // test replace_pins and list_pins
+        article_tag_dao
+            .replace_pins(tags[1].id, vec![articles[1].id, articles[0].id])
+            .await
+            .unwrap();
+        let pin_list = article_tag_dao.list_pins(tags[1].id).await.unwrap();
+        assert_eq!(pin_list.len(), 2);
+        assert_eq!(pin_list[0].id, articles[1].id);
+        article_tag_dao
+            .replace_pins(tags[1].id, vec![articles[0].id])
+            .await
+            .unwrap();
+        let pin_list = article_tag_dao.list_pins(tags[1].id).await.unwrap();
+        assert_eq!(pin_list.len(), 1);
+        assert_eq!(pin_list[0].id, articles[0].id);
+        let article_list = article_tag_dao
+            .list_articles(
+                vec![tags[1].id],
+                ArticleFilterParam::default(),
+                OrderParam {
+                    by: ArticleAttr::TagPinPriority(tags[1].id),
+                    ascending: false,
+                    then: vec![(ArticleAttr::Id, false)],
+                },
+                PaginateParam::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(article_list[0].id, articles[0].id);
+
+        // test replace_pins failed(article not in the tag)
+        let pin_res = article_tag_dao
+            .replace_pins(tags[0].id, vec![articles[1].id])
+            .await;
+        assert!(pin_res.is_err());
+
+        // test remove_tags
+        let remove_num = article_tag_dao
+            .remove_tags(articles[0].id, vec![tags[0].id, tags[1].id])
+            .await
+            .unwrap();
+        assert_eq!(remove_num, 1);
+
+        // test list_tags failed(article not found)
+        assert!(article_tag_dao.list_tags(-1).await.is_err());
+
+        // clean up
+        UserEntity::delete_by_id(author.id)
+            .exec(db_conn.as_ref())
+            .await
+            .unwrap();
+    }
+}
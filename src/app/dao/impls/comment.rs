@@ -0,0 +1,258 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::{ColumnTrait, Condition, IntoActiveModel, IntoSimpleExpr, Set};
+use sea_query::{IntoCondition, SimpleExpr};
+
+use super::{
+    super::{traits::comment::CommentDataAccess, types::comment::prelude::*},
+    DBConnProvider, DataAccessImpl,
+};
+use crate::app::db::prelude::{
+    CommentActiveModel, CommentColumn, CommentEntity, DatabaseConnection,
+};
+
+// ********************* content ********************* //
+// params
+impl IntoCondition for CommentFilterParam {
+    fn into_condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(id) = self.id {
+            condition = condition.add(CommentColumn::Id.eq(id));
+        }
+        if let Some(ids) = self.ids {
+            condition = condition.add(CommentColumn::Id.is_in(ids));
+        }
+        if let Some(article_id) = self.article_id {
+            condition = condition.add(CommentColumn::ArticleId.eq(article_id));
+        }
+        if let Some(parent_comment_id) = self.parent_comment_id {
+            condition = match parent_comment_id {
+                Some(parent_comment_id) => {
+                    condition.add(CommentColumn::ParentCommentId.eq(parent_comment_id))
+                }
+                None => condition.add(CommentColumn::ParentCommentId.is_null()),
+            };
+        }
+        if let Some(create_user_id) = self.create_user_id {
+            condition = condition.add(CommentColumn::CreateUserId.eq(create_user_id));
+        }
+        if let Some(status_type) = self.status_type {
+            condition = condition.add(CommentColumn::StatusType.eq(status_type));
+        }
+        if let Some(status_types) = self.status_types {
+            condition = condition.add(CommentColumn::StatusType.is_in(status_types));
+        }
+        if let Some(content_search) = self.content_search {
+            condition = condition.add(CommentColumn::Content.contains(&content_search));
+        }
+        condition
+    }
+}
+
+impl IntoActiveModel<CommentActiveModel> for CommentCreateParam {
+    fn into_active_model(self) -> CommentActiveModel {
+        CommentActiveModel {
+            article_id: Set(self.article_id),
+            parent_comment_id: Set(self.parent_comment_id),
+            depth: Set(self.depth),
+            content: Set(self.content),
+            status_type: Set(self.status_type),
+            create_user_id: Set(Some(self.create_user_id)),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoActiveModel<CommentActiveModel> for CommentUpdateParam {
+    fn into_active_model(self) -> CommentActiveModel {
+        let mut active_model = <CommentActiveModel as Default>::default();
+        if let Some(content) = self.content {
+            active_model.content = Set(content);
+        }
+        if let Some(status_type) = self.status_type {
+            active_model.status_type = Set(status_type);
+        }
+        active_model
+    }
+}
+
+impl IntoSimpleExpr for CommentAttr {
+    fn into_simple_expr(self) -> SimpleExpr {
+        match self {
+            CommentAttr::Id => CommentColumn::Id,
+            CommentAttr::CreateTime => CommentColumn::CreateTime,
+            CommentAttr::UpdateTime => CommentColumn::UpdateTime,
+        }
+        .into_simple_expr()
+    }
+}
+
+// dao
+pub struct CommentDAO {
+    db_conn: Arc<DatabaseConnection>,
+}
+
+impl CommentDAO {
+    pub fn new(db_conn: Arc<DatabaseConnection>) -> Self {
+        Self { db_conn }
+    }
+}
+
+impl DBConnProvider for CommentDAO {
+    fn db_conn(&self) -> &DatabaseConnection {
+        &self.db_conn
+    }
+}
+
+#[async_trait]
+impl DataAccessImpl for CommentDAO {
+    type DataAttr = CommentAttr;
+    type FilterParam = CommentFilterParam;
+    type CreateParam = CommentCreateParam;
+    type UpdateParam = CommentUpdateParam;
+    type Model = CommentDataModel;
+    type Entity = CommentEntity;
+    type ActiveModel = CommentActiveModel;
+}
+
+impl CommentDataAccess for CommentDAO {}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{ActiveModelTrait, EntityTrait};
+
+    use super::*;
+    use crate::app::{
+        config::AppConfig,
+        dao::{impls::create_test_user, traits::prelude::DataAccess, types::OrderParam},
+        db::prelude::{create_db_conn, ArticleActiveModel, UserEntity},
+    };
+
+    #[tokio::test]
+    async fn test_comment_dao() {
+        // init
+        let cfg = AppConfig::init("config/config_test.toml").unwrap();
+        let db_conn = Arc::new(create_db_conn(&cfg.db).await.unwrap());
+        let comment_dao = CommentDAO::new(db_conn.clone());
+
+        // prepare an author and an article, deleting the author cascades to all test data
+        let author = create_test_user(db_conn.as_ref(), "test_comment_author", 0).await;
+        let article = ArticleActiveModel {
+            title: Set("test_comment_article".to_string()),
+            slug: Set("test-comment-article".to_string()),
+            description: Set("".to_string()),
+            content: Set("".to_string()),
+            status_type: Set(1),
+            create_user_id: Set(author.id),
+            update_user_id: Set(author.id),
+            ..Default::default()
+        }
+        .insert(db_conn.as_ref())
+        .await
+        .unwrap();
+
+        // test create
+        let root = <CommentDAO as DataAccess>::create(
+            &comment_dao,
+            CommentCreateParam {
+                article_id: article.id,
+                parent_comment_id: None,
+                depth: 1,
+                content: "test_root".to_string(),
+                status_type: 1,
+                create_user_id: author.id,
+            },
+        )
+        .await
+        .unwrap();
+        let reply = <CommentDAO as DataAccess>::create(
+            &comment_dao,
+            CommentCreateParam {
+                article_id: article.id,
+                parent_comment_id: Some(root.id),
+                depth: 2,
+                content: "test_reply".to_string(),
+                status_type: 0,
+                create_user_id: author.id,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(reply.parent_comment_id, Some(root.id));
+
+        // test list_all by parent
+        let top_level = <CommentDAO as DataAccess>::list_all(
+            &comment_dao,
+            CommentFilterParam {
+                article_id: Some(article.id),
+                parent_comment_id: Some(None),
+                ..Default::default()
+            },
+            OrderParam::<CommentAttr>::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(top_level.len(), 1);
+        assert_eq!(top_level[0].id, root.id);
+
+        // test update_all(approve pending ones only)
+        let approve_num = <CommentDAO as DataAccess>::update_all(
+            &comment_dao,
+            CommentFilterParam {
+                ids: Some(vec![root.id, reply.id]),
+                status_type: Some(0),
+                ..Default::default()
+            },
+            CommentUpdateParam {
+                status_type: Some(1),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(approve_num, 1);
+
+        // test count with content search
+        let comment_num = <CommentDAO as DataAccess>::count(
+            &comment_dao,
+            CommentFilterParam {
+                article_id: Some(article.id),
+                status_type: Some(1),
+                content_search: Some("reply".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(comment_num, 1);
+
+        // test delete cascades to replies
+        <CommentDAO as DataAccess>::delete(
+            &comment_dao,
+            CommentFilterParam {
+                id: Some(root.id),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let comment_num = <CommentDAO as DataAccess>::count(
+            &comment_dao,
+            CommentFilterParam {
+                article_id: Some(article.id),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(comment_num, 0);
+
+        // clean up
+        UserEntity::delete_by_id(author.id)
+            .exec(db_conn.as_ref())
+            .await
+            .unwrap();
+    }
+}
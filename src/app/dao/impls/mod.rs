@@ -1,33 +1,75 @@
 // ********************* mod ********************* //
+pub mod article;
+pub mod article_reaction;
+pub mod article_revision;
+pub mod article_tag;
+pub mod comment;
+pub mod series;
+pub mod series_article;
+pub mod slug_history;
+pub mod tag;
+pub mod upload;
 pub mod user;
 
 pub mod prelude {
-    pub use super::{user::UserDAO, DataAccessImpl};
+    pub use super::{
+        article::ArticleDAO, article_reaction::ArticleReactionDAO,
+        article_revision::ArticleRevisionDAO, article_tag::ArticleTagDAO, comment::CommentDAO,
+        series::SeriesDAO, series_article::SeriesArticleDAO, slug_history::SlugHistoryDAO,
+        tag::TagDAO, upload::UploadDAO, user::UserDAO, DataAccessImpl, RelationDataAccessImpl,
+        SoftDeleteImpl,
+    };
 }
 
 // ********************* import ********************* //
 use std::fmt::Debug;
 
 use async_trait::async_trait;
+use chrono::Local;
 use sea_orm::{
-    prelude::*, FromQueryResult, IntoActiveModel, IntoSimpleExpr, Order, QueryOrder,
+    prelude::*, Condition, FromQueryResult, IntoActiveModel, IntoSimpleExpr, Order, QueryOrder,
     TransactionTrait,
 };
-use sea_query::IntoCondition;
+use sea_query::{IntoCondition, SimpleExpr};
 
 use super::{
-    traits::DataAccess,
+    traits::{DataAccess, RelationDataAccess, SoftDeleteDataAccess},
     types::{OrderParam, PaginateParam},
 };
 use crate::app::{common::prelude::*, db::prelude::DBConnProvider};
 
 // ********************* content ********************* //
-impl<T> OrderParam<T> {
-    fn order(&self) -> Order {
-        match self.ascending {
-            true => Order::Asc,
-            false => Order::Desc,
-        }
+fn order(ascending: bool) -> Order {
+    match ascending {
+        true => Order::Asc,
+        false => Order::Desc,
+    }
+}
+
+impl<T: IntoSimpleExpr> OrderParam<T> {
+    fn apply<Q: QueryOrder>(self, query: Q) -> Q {
+        self.then.into_iter().fold(
+            query.order_by(self.by, order(self.ascending)),
+            |query, (by, ascending)| query.order_by(by, order(ascending)),
+        )
+    }
+}
+
+// update and delete of a single data refuse to touch any other number of rows
+fn check_single<F: Debug>(count: u64, filter: &F) -> AppResult<()> {
+    match count {
+        0 => Err(AppError::new(
+            format!("Data not found, filter: {:?}", filter),
+            AppErrorKind::ResourceNotFound,
+        )),
+        1 => Ok(()),
+        _ => Err(AppError::new(
+            format!(
+                "Multiple data found, count: {:?}, filter: {:?}",
+                count, filter
+            ),
+            AppErrorKind::ResourceConflict,
+        )),
     }
 }
 
@@ -83,15 +125,27 @@ pub trait DataAccessImpl {
         order: OrderParam<Self::DataAttr>,
         paginate: PaginateParam,
     ) -> AppResult<Vec<Self::Model>> {
-        Self::Entity::find()
-            .filter(filter)
-            .order_by(order.clone().by, order.order())
+        order
+            .apply(Self::Entity::find().filter(filter))
             .paginate(db_conn, paginate.page_size)
             .fetch_page(paginate.page_num.saturating_sub(1))
             .await
             .with_err_kind(AppErrorKind::DBOperationError)
     }
 
+    async fn list_all<C: ConnectionTrait>(
+        &self,
+        db_conn: &C,
+        filter: Self::FilterParam,
+        order: OrderParam<Self::DataAttr>,
+    ) -> AppResult<Vec<Self::Model>> {
+        order
+            .apply(Self::Entity::find().filter(filter))
+            .all(db_conn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
     async fn create<C: ConnectionTrait>(
         &self,
         db_conn: &C,
@@ -132,24 +186,7 @@ pub trait DataAccessImpl {
             AppErrorKind::DBOperationError,
         )?;
         let count = self.count(&txn, filter.clone()).await?;
-        match count {
-            0 => {
-                return Err(AppError::new(
-                    format!("Data not found, filter: {:?}", filter),
-                    AppErrorKind::ResourceNotFound,
-                ));
-            }
-            1 => {}
-            _ => {
-                return Err(AppError::new(
-                    format!(
-                        "Multiple data found, count: {:?}, filter: {:?}",
-                        count, filter
-                    ),
-                    AppErrorKind::ResourceConflict,
-                ));
-            }
-        }
+        check_single(count, &filter)?;
         self.update_all(&txn, filter, update_param).await?;
         txn.commit().await.wrap(
             "Failed to commit db transaction.",
@@ -182,24 +219,7 @@ pub trait DataAccessImpl {
             AppErrorKind::DBOperationError,
         )?;
         let count = self.count(&txn, filter.clone()).await?;
-        match count {
-            0 => {
-                return Err(AppError::new(
-                    format!("Data not found, filter: {:?}", filter),
-                    AppErrorKind::ResourceNotFound,
-                ));
-            }
-            1 => {}
-            _ => {
-                return Err(AppError::new(
-                    format!(
-                        "Multiple data found, count: {:?}, filter: {:?}",
-                        count, filter
-                    ),
-                    AppErrorKind::ResourceConflict,
-                ));
-            }
-        }
+        check_single(count, &filter)?;
         self.delete_all(&txn, filter).await?;
         txn.commit().await.wrap(
             "Failed to commit db transaction.",
@@ -246,6 +266,14 @@ impl<T: DataAccessImpl + DBConnProvider + Sync> DataAccess for T {
         <Self as DataAccessImpl>::list(self, self.db_conn(), filter, order, paginate).await
     }
 
+    async fn list_all(
+        &self,
+        filter: Self::FilterParam,
+        order: OrderParam<Self::DataAttr>,
+    ) -> AppResult<Vec<Self::DataModel>> {
+        <Self as DataAccessImpl>::list_all(self, self.db_conn(), filter, order).await
+    }
+
     async fn create(&self, create_param: Self::CreateParam) -> AppResult<Self::DataModel> {
         <Self as DataAccessImpl>::create(self, self.db_conn(), create_param).await
     }
@@ -278,3 +306,276 @@ impl<T: DataAccessImpl + DBConnProvider + Sync> DataAccess for T {
         <Self as DataAccessImpl>::delete_all(self, self.db_conn(), filter).await
     }
 }
+
+#[async_trait]
+pub trait SoftDeleteImpl: DataAccessImpl {
+    const DELETED_STATUS_TYPE: i32;
+    // restored data is not made public again by itself
+    const RESTORED_STATUS_TYPE: i32;
+
+    fn status_type_column() -> <Self::Entity as EntityTrait>::Column;
+    fn delete_time_column() -> <Self::Entity as EntityTrait>::Column;
+    fn delete_user_id_column() -> <Self::Entity as EntityTrait>::Column;
+    // datetime columns set to NULL whenever the data enters or leaves the trash
+    fn cleared_columns() -> Vec<<Self::Entity as EntityTrait>::Column> {
+        Vec::new()
+    }
+
+    // move exactly one data matching the condition from `from_status_type` to
+    // `to_status_type`, setting the delete columns to the given values
+    async fn move_single<C: ConnectionTrait + TransactionTrait>(
+        &self,
+        db_conn: &C,
+        filter: Self::FilterParam,
+        from_status_type: SimpleExpr,
+        to_status_type: i32,
+        delete_time: Option<DateTime>,
+        delete_user_id: Option<i32>,
+    ) -> AppResult<()> {
+        let condition = filter.clone().into_condition().add(from_status_type);
+        let txn = db_conn.begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        let count = Self::Entity::find()
+            .filter(condition.clone())
+            .count(&txn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?;
+        check_single(count, &filter)?;
+        Self::cleared_columns()
+            .into_iter()
+            .fold(Self::Entity::update_many(), |update, column| {
+                update.col_expr(column, Expr::value(Option::<DateTime>::None))
+            })
+            .col_expr(Self::status_type_column(), Expr::value(to_status_type))
+            .col_expr(Self::delete_time_column(), Expr::value(delete_time))
+            .col_expr(Self::delete_user_id_column(), Expr::value(delete_user_id))
+            .filter(condition)
+            .exec(&txn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?;
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )
+    }
+
+    async fn soft_delete<C: ConnectionTrait + TransactionTrait>(
+        &self,
+        db_conn: &C,
+        filter: Self::FilterParam,
+        delete_user_id: i32,
+    ) -> AppResult<()> {
+        self.move_single(
+            db_conn,
+            filter,
+            Self::status_type_column().ne(Self::DELETED_STATUS_TYPE),
+            Self::DELETED_STATUS_TYPE,
+            Some(Local::now().naive_local()),
+            Some(delete_user_id),
+        )
+        .await
+    }
+
+    async fn restore<C: ConnectionTrait + TransactionTrait>(
+        &self,
+        db_conn: &C,
+        filter: Self::FilterParam,
+    ) -> AppResult<()> {
+        self.move_single(
+            db_conn,
+            filter,
+            Self::status_type_column().eq(Self::DELETED_STATUS_TYPE),
+            Self::RESTORED_STATUS_TYPE,
+            None,
+            None,
+        )
+        .await
+    }
+
+    // runs in the purge transaction before the expired data is deleted, for data whose
+    // foreign keys would otherwise cascade to what other users own
+    async fn before_purge<C: ConnectionTrait>(
+        &self,
+        _db_conn: &C,
+        _expired: Condition,
+    ) -> AppResult<()> {
+        Ok(())
+    }
+
+    async fn purge<C: ConnectionTrait + TransactionTrait>(
+        &self,
+        db_conn: &C,
+        deleted_before: DateTime,
+    ) -> AppResult<u64> {
+        let expired = Condition::all()
+            .add(Self::status_type_column().eq(Self::DELETED_STATUS_TYPE))
+            // rows deleted before the delete time was recorded count as expired
+            .add(
+                Condition::any()
+                    .add(Self::delete_time_column().lte(deleted_before))
+                    .add(Self::delete_time_column().is_null()),
+            );
+        let txn = db_conn.begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        self.before_purge(&txn, expired.clone()).await?;
+        let purge_num = Self::Entity::delete_many()
+            .filter(expired)
+            .exec(&txn)
+            .await
+            .map(|delete_res| delete_res.rows_affected)
+            .with_err_kind(AppErrorKind::DBOperationError)?;
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        Ok(purge_num)
+    }
+}
+
+#[async_trait]
+impl<T: SoftDeleteImpl + DBConnProvider + Sync> SoftDeleteDataAccess for T {
+    async fn soft_delete(
+        &self,
+        filter: <Self as DataAccess>::FilterParam,
+        delete_user_id: i32,
+    ) -> AppResult<()> {
+        <Self as SoftDeleteImpl>::soft_delete(self, self.db_conn(), filter, delete_user_id).await
+    }
+
+    async fn restore(&self, filter: <Self as DataAccess>::FilterParam) -> AppResult<()> {
+        <Self as SoftDeleteImpl>::restore(self, self.db_conn(), filter).await
+    }
+
+    async fn purge(&self, deleted_before: DateTime) -> AppResult<u64> {
+        <Self as SoftDeleteImpl>::purge(self, self.db_conn(), deleted_before).await
+    }
+}
+
+#[async_trait]
+pub trait RelationDataAccessImpl {
+    // RelationDataAccess
+    type FilterParam: IntoCondition + Clone + Debug + Send;
+    type CreateParam: IntoActiveModel<Self::ActiveModel> + Send;
+    // Sea-orm
+    type Model: ModelTrait<Entity = Self::Entity>
+        + FromQueryResult
+        + IntoActiveModel<Self::ActiveModel>
+        + Sync;
+    type Entity: EntityTrait<Model = Self::Model>;
+    type ActiveModel: ActiveModelTrait<Entity = Self::Entity> + ActiveModelBehavior + Send;
+
+    async fn count<C: ConnectionTrait>(
+        &self,
+        db_conn: &C,
+        filter: Self::FilterParam,
+    ) -> AppResult<u64> {
+        Self::Entity::find()
+            .filter(filter)
+            .count(db_conn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn list_all<C: ConnectionTrait>(
+        &self,
+        db_conn: &C,
+        filter: Self::FilterParam,
+    ) -> AppResult<Vec<Self::Model>> {
+        Self::Entity::find()
+            .filter(filter)
+            .all(db_conn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn create_many<C: ConnectionTrait>(
+        &self,
+        db_conn: &C,
+        create_param: Vec<Self::CreateParam>,
+    ) -> AppResult<u64> {
+        // sea-orm refuses to build an insert statement without rows
+        if create_param.is_empty() {
+            return Ok(0);
+        }
+        let len = create_param.len() as u64;
+        Self::Entity::insert_many(
+            create_param
+                .into_iter()
+                .map(<Self::CreateParam>::into_active_model),
+        )
+        .exec_without_returning(db_conn)
+        .await
+        .map(|_| len)
+        .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn delete_all<C: ConnectionTrait>(
+        &self,
+        db_conn: &C,
+        filter: Self::FilterParam,
+    ) -> AppResult<u64> {
+        Self::Entity::delete_many()
+            .filter(filter)
+            .exec(db_conn)
+            .await
+            .map(|delete_res| delete_res.rows_affected)
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+}
+
+#[async_trait]
+impl<T: RelationDataAccessImpl + DBConnProvider + Sync> RelationDataAccess for T {
+    type DataModel = T::Model;
+    type FilterParam = T::FilterParam;
+    type CreateParam = T::CreateParam;
+
+    async fn count(&self, filter: Self::FilterParam) -> AppResult<u64> {
+        <Self as RelationDataAccessImpl>::count(self, self.db_conn(), filter).await
+    }
+
+    async fn list_all(&self, filter: Self::FilterParam) -> AppResult<Vec<Self::DataModel>> {
+        <Self as RelationDataAccessImpl>::list_all(self, self.db_conn(), filter).await
+    }
+
+    async fn create_many(&self, create_param: Vec<Self::CreateParam>) -> AppResult<u64> {
+        <Self as RelationDataAccessImpl>::create_many(self, self.db_conn(), create_param).await
+    }
+
+    async fn delete_all(&self, filter: Self::FilterParam) -> AppResult<u64> {
+        <Self as RelationDataAccessImpl>::delete_all(self, self.db_conn(), filter).await
+    }
+}
+
+// a fresh user for the dao tests, an earlier user with the same name is deleted first,
+// which cascades to everything it created
+#[cfg(test)]
+pub(super) async fn create_test_user(
+    db_conn: &crate::app::db::prelude::DatabaseConnection,
+    username: &str,
+    group_type: i32,
+) -> crate::app::db::prelude::UserModel {
+    use sea_orm::{ActiveModelTrait, Set};
+
+    use crate::app::db::prelude::{UserActiveModel, UserColumn, UserEntity};
+
+    UserEntity::delete_many()
+        .filter(UserColumn::Username.eq(username))
+        .exec(db_conn)
+        .await
+        .unwrap();
+    UserActiveModel {
+        username: Set(username.to_string()),
+        nickname: Set(username.to_string()),
+        password: Set("test".to_string()),
+        email: Set(format!("test@{}.com", username)),
+        group_type: Set(group_type),
+        ..Default::default()
+    }
+    .insert(db_conn)
+    .await
+    .unwrap()
+}
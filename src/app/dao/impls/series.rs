@@ -0,0 +1,188 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::{ColumnTrait, Condition, IntoActiveModel, IntoSimpleExpr, Set};
+use sea_query::{IntoCondition, SimpleExpr};
+
+use super::{
+    super::{traits::series::SeriesDataAccess, types::series::prelude::*},
+    DBConnProvider, DataAccessImpl,
+};
+use crate::app::db::prelude::{DatabaseConnection, SeriesActiveModel, SeriesColumn, SeriesEntity};
+
+// ********************* content ********************* //
+// params
+impl IntoCondition for SeriesFilterParam {
+    fn into_condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(id) = self.id {
+            condition = condition.add(SeriesColumn::Id.eq(id));
+        }
+        if let Some(ids) = self.ids {
+            condition = condition.add(SeriesColumn::Id.is_in(ids));
+        }
+        if let Some(create_user_id) = self.create_user_id {
+            condition = condition.add(SeriesColumn::CreateUserId.eq(create_user_id));
+        }
+        if let Some(title_search) = self.title_search {
+            condition = condition.add(SeriesColumn::Title.contains(&title_search));
+        }
+        condition
+    }
+}
+
+impl IntoActiveModel<SeriesActiveModel> for SeriesCreateParam {
+    fn into_active_model(self) -> SeriesActiveModel {
+        SeriesActiveModel {
+            title: Set(self.title),
+            description: Set(self.description),
+            create_user_id: Set(self.create_user_id),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoActiveModel<SeriesActiveModel> for SeriesUpdateParam {
+    fn into_active_model(self) -> SeriesActiveModel {
+        let mut active_model = <SeriesActiveModel as Default>::default();
+        if let Some(title) = self.title {
+            active_model.title = Set(title);
+        }
+        if let Some(description) = self.description {
+            active_model.description = Set(description);
+        }
+        active_model
+    }
+}
+
+impl IntoSimpleExpr for SeriesAttr {
+    fn into_simple_expr(self) -> SimpleExpr {
+        match self {
+            SeriesAttr::Id => SeriesColumn::Id,
+            SeriesAttr::Title => SeriesColumn::Title,
+            SeriesAttr::CreateTime => SeriesColumn::CreateTime,
+            SeriesAttr::UpdateTime => SeriesColumn::UpdateTime,
+        }
+        .into_simple_expr()
+    }
+}
+
+// dao
+pub struct SeriesDAO {
+    db_conn: Arc<DatabaseConnection>,
+}
+
+impl SeriesDAO {
+    pub fn new(db_conn: Arc<DatabaseConnection>) -> Self {
+        Self { db_conn }
+    }
+}
+
+impl DBConnProvider for SeriesDAO {
+    fn db_conn(&self) -> &DatabaseConnection {
+        &self.db_conn
+    }
+}
+
+#[async_trait]
+impl DataAccessImpl for SeriesDAO {
+    type DataAttr = SeriesAttr;
+    type FilterParam = SeriesFilterParam;
+    type CreateParam = SeriesCreateParam;
+    type UpdateParam = SeriesUpdateParam;
+    type Model = SeriesDataModel;
+    type Entity = SeriesEntity;
+    type ActiveModel = SeriesActiveModel;
+}
+
+impl SeriesDataAccess for SeriesDAO {}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::EntityTrait;
+
+    use super::*;
+    use crate::app::{
+        config::AppConfig,
+        dao::{impls::create_test_user, traits::prelude::DataAccess, types::OrderParam},
+        db::prelude::{create_db_conn, UserEntity},
+    };
+
+    #[tokio::test]
+    async fn test_series_dao() {
+        // init
+        let cfg = AppConfig::init("config/config_test.toml").unwrap();
+        let db_conn = Arc::new(create_db_conn(&cfg.db).await.unwrap());
+        let series_dao = SeriesDAO::new(db_conn.clone());
+
+        // prepare an author, deleting it cascades to all test series
+        let author = create_test_user(db_conn.as_ref(), "test_series_author", 0).await;
+        let test_filter = SeriesFilterParam {
+            create_user_id: Some(author.id),
+            ..Default::default()
+        };
+
+        // test create
+        let create_param = SeriesCreateParam {
+            title: "test_series".to_string(),
+            description: "test".to_string(),
+            create_user_id: author.id,
+        };
+        let series = <SeriesDAO as DataAccess>::create(&series_dao, create_param.clone())
+            .await
+            .unwrap();
+        assert_eq!(series.title, "test_series");
+        <SeriesDAO as DataAccess>::create(
+            &series_dao,
+            SeriesCreateParam {
+                title: "test_other".to_string(),
+                ..create_param
+            },
+        )
+        .await
+        .unwrap();
+
+        // test filter
+        let series_list = <SeriesDAO as DataAccess>::list_all(
+            &series_dao,
+            SeriesFilterParam {
+                title_search: Some("series".to_string()),
+                ..test_filter.clone()
+            },
+            OrderParam::<SeriesAttr>::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(series_list.len(), 1);
+        assert_eq!(series_list[0].id, series.id);
+
+        // test update
+        let series_filter = SeriesFilterParam {
+            id: Some(series.id),
+            ..Default::default()
+        };
+        <SeriesDAO as DataAccess>::update(
+            &series_dao,
+            series_filter.clone(),
+            SeriesUpdateParam {
+                description: Some("updated".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let series = <SeriesDAO as DataAccess>::get(&series_dao, series_filter.clone()).await;
+        let series = series.unwrap();
+        assert_eq!(series.title, "test_series");
+        assert_eq!(series.description, "updated");
+
+        // clean up
+        let delete_num = <SeriesDAO as DataAccess>::delete_all(&series_dao, test_filter).await;
+        assert_eq!(delete_num.unwrap(), 2);
+        UserEntity::delete_by_id(author.id)
+            .exec(db_conn.as_ref())
+            .await
+            .unwrap();
+    }
+}
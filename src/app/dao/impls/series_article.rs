@@ -0,0 +1,275 @@
+// ********************* import ********************* //
+use std::{collections::HashSet, sync::Arc};
+
+use async_trait::async_trait;
+use sea_orm::{
+    ColumnTrait, Condition, EntityTrait, IntoActiveModel, JoinType, Linked, QueryFilter,
+    QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use sea_query::IntoCondition;
+
+use super::{
+    super::{
+        traits::series_article::SeriesArticleDataAccess,
+        types::{article::prelude::*, series::prelude::*, series_article::prelude::*},
+    },
+    DBConnProvider, RelationDataAccessImpl,
+};
+use crate::app::{
+    common::prelude::*,
+    db::prelude::{
+        ArticleEntity, ArticleToSeriesLink, DatabaseConnection, SeriesArticleActiveModel,
+        SeriesArticleColumn, SeriesArticleEntity, SeriesEntity, SeriesToArticleLink,
+    },
+};
+
+// ********************* content ********************* //
+// params
+impl IntoCondition for SeriesArticleFilterParam {
+    fn into_condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(series_id) = self.series_id {
+            condition = condition.add(SeriesArticleColumn::SeriesId.eq(series_id));
+        }
+        if let Some(article_id) = self.article_id {
+            condition = condition.add(SeriesArticleColumn::ArticleId.eq(article_id));
+        }
+        if let Some(article_ids) = self.article_ids {
+            condition = condition.add(SeriesArticleColumn::ArticleId.is_in(article_ids));
+        }
+        condition
+    }
+}
+
+impl IntoActiveModel<SeriesArticleActiveModel> for SeriesArticleCreateParam {
+    fn into_active_model(self) -> SeriesArticleActiveModel {
+        SeriesArticleActiveModel {
+            series_id: Set(self.series_id),
+            article_id: Set(self.article_id),
+            position: Set(self.position),
+        }
+    }
+}
+
+// dao
+pub struct SeriesArticleDAO {
+    db_conn: Arc<DatabaseConnection>,
+}
+
+impl SeriesArticleDAO {
+    pub fn new(db_conn: Arc<DatabaseConnection>) -> Self {
+        Self { db_conn }
+    }
+}
+
+impl DBConnProvider for SeriesArticleDAO {
+    fn db_conn(&self) -> &DatabaseConnection {
+        &self.db_conn
+    }
+}
+
+#[async_trait]
+impl RelationDataAccessImpl for SeriesArticleDAO {
+    type FilterParam = SeriesArticleFilterParam;
+    type CreateParam = SeriesArticleCreateParam;
+    type Model = SeriesArticleDataModel;
+    type Entity = SeriesArticleEntity;
+    type ActiveModel = SeriesArticleActiveModel;
+}
+
+#[async_trait]
+impl SeriesArticleDataAccess for SeriesArticleDAO {
+    async fn replace_articles(&self, series_id: i32, article_ids: Vec<i32>) -> AppResult<()> {
+        let txn = self.db_conn().begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        let filter = SeriesArticleFilterParam {
+            series_id: Some(series_id),
+            ..Default::default()
+        };
+        <Self as RelationDataAccessImpl>::delete_all(self, &txn, filter).await?;
+        let mut seen = HashSet::new();
+        let create_param = article_ids
+            .into_iter()
+            .filter(|id| seen.insert(*id))
+            .enumerate()
+            .map(|(position, article_id)| SeriesArticleCreateParam {
+                series_id,
+                article_id,
+                position: position as i32,
+            })
+            .collect();
+        <Self as RelationDataAccessImpl>::create_many(self, &txn, create_param).await?;
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )
+    }
+
+    async fn list_articles(
+        &self,
+        series_id: i32,
+        filter: ArticleFilterParam,
+    ) -> AppResult<Vec<ArticleDataModel>> {
+        // walk SeriesToArticleLink backwards so that the selected entity is the article
+        SeriesToArticleLink
+            .link()
+            .into_iter()
+            .rev()
+            .fold(ArticleEntity::find(), |select, rel| {
+                select.join_rev(JoinType::InnerJoin, rel)
+            })
+            .filter(SeriesArticleColumn::SeriesId.eq(series_id))
+            .filter(filter)
+            .order_by_asc(SeriesArticleColumn::Position)
+            .all(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn find_series(&self, article_id: i32) -> AppResult<Option<SeriesDataModel>> {
+        ArticleToSeriesLink
+            .link()
+            .into_iter()
+            .rev()
+            .fold(SeriesEntity::find(), |select, rel| {
+                select.join_rev(JoinType::InnerJoin, rel)
+            })
+            .filter(SeriesArticleColumn::ArticleId.eq(article_id))
+            .one(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::ActiveModelTrait;
+
+    use super::*;
+    use crate::app::{
+        config::AppConfig,
+        dao::{impls::create_test_user, traits::prelude::RelationDataAccess},
+        db::prelude::{create_db_conn, ArticleActiveModel, SeriesActiveModel, UserEntity},
+    };
+
+    #[tokio::test]
+    async fn test_series_article_dao() {
+        // init
+        let cfg = AppConfig::init("config/config_test.toml").unwrap();
+        let db_conn = Arc::new(create_db_conn(&cfg.db).await.unwrap());
+        let series_article_dao = SeriesArticleDAO::new(db_conn.clone());
+
+        // prepare an author, deleting it cascades to all test data
+        let author = create_test_user(db_conn.as_ref(), "test_series_article_author", 0).await;
+        let series = SeriesActiveModel {
+            title: Set("test_series".to_string()),
+            description: Set("".to_string()),
+            create_user_id: Set(author.id),
+            ..Default::default()
+        }
+        .insert(db_conn.as_ref())
+        .await
+        .unwrap();
+        let mut articles = Vec::new();
+        for (title, status_type) in [("test_part1", 1), ("test_part2", 0), ("test_part3", 1)] {
+            let article = ArticleActiveModel {
+                title: Set(title.to_string()),
+                slug: Set(format!("series-article-{}", title.replace('_', "-"))),
+                description: Set("".to_string()),
+                content: Set("".to_string()),
+                status_type: Set(status_type),
+                create_user_id: Set(author.id),
+                update_user_id: Set(author.id),
+                ..Default::default()
+            }
+            .insert(db_conn.as_ref())
+            .await
+            .unwrap();
+            articles.push(article);
+        }
+        let series_filter = SeriesArticleFilterParam {
+            series_id: Some(series.id),
+            ..Default::default()
+        };
+
+        // test replace_articles, duplicates are dropped
+        series_article_dao
+            .replace_articles(
+                series.id,
+                vec![
+                    articles[2].id,
+                    articles[0].id,
+                    articles[1].id,
+                    articles[0].id,
+                ],
+            )
+            .await
+            .unwrap();
+        let count = <SeriesArticleDAO as RelationDataAccess>::count(
+            &series_article_dao,
+            series_filter.clone(),
+        );
+        assert_eq!(count.await.unwrap(), 3);
+
+        // test list_articles in position order
+        let parts = series_article_dao
+            .list_articles(series.id, ArticleFilterParam::default())
+            .await
+            .unwrap();
+        let part_ids: Vec<i32> = parts.iter().map(|model| model.id).collect();
+        assert_eq!(
+            part_ids,
+            vec![articles[2].id, articles[0].id, articles[1].id]
+        );
+        let published_parts = series_article_dao
+            .list_articles(
+                series.id,
+                ArticleFilterParam {
+                    status_type: Some(1),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(published_parts.len(), 2);
+
+        // test reorder
+        series_article_dao
+            .replace_articles(series.id, vec![articles[0].id, articles[2].id])
+            .await
+            .unwrap();
+        let parts = series_article_dao
+            .list_articles(series.id, ArticleFilterParam::default())
+            .await
+            .unwrap();
+        assert_eq!(parts[0].id, articles[0].id);
+        assert_eq!(parts.len(), 2);
+
+        // test find_series
+        let found = series_article_dao
+            .find_series(articles[2].id)
+            .await
+            .unwrap();
+        assert_eq!(found.map(|model| model.id), Some(series.id));
+        let found = series_article_dao
+            .find_series(articles[1].id)
+            .await
+            .unwrap();
+        assert!(found.is_none());
+
+        // test delete_all
+        let delete_num = <SeriesArticleDAO as RelationDataAccess>::delete_all(
+            &series_article_dao,
+            series_filter,
+        );
+        assert_eq!(delete_num.await.unwrap(), 2);
+
+        // clean up
+        UserEntity::delete_by_id(author.id)
+            .exec(db_conn.as_ref())
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,290 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, IntoActiveModel, IntoSimpleExpr,
+    QueryFilter, Set,
+};
+use sea_query::{Expr, IntoCondition, Query, SimpleExpr};
+
+use super::{
+    super::{traits::slug_history::SlugHistoryDataAccess, types::slug_history::prelude::*},
+    DBConnProvider, DataAccessImpl,
+};
+use crate::app::{
+    common::prelude::*,
+    db::prelude::{
+        ArticleColumn, ArticleEntity, DatabaseConnection, SlugHistoryActiveModel,
+        SlugHistoryColumn, SlugHistoryEntity, TagColumn, TagEntity,
+    },
+};
+
+// ********************* content ********************* //
+// params
+impl IntoCondition for SlugHistoryFilterParam {
+    fn into_condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(id) = self.id {
+            condition = condition.add(SlugHistoryColumn::Id.eq(id));
+        }
+        if let Some(resource_type) = self.resource_type {
+            condition = condition.add(SlugHistoryColumn::ResourceType.eq(resource_type));
+        }
+        if let Some(resource_id) = self.resource_id {
+            condition = condition.add(SlugHistoryColumn::ResourceId.eq(resource_id));
+        }
+        if let Some(slug) = self.slug {
+            condition = condition.add(SlugHistoryColumn::Slug.eq(slug));
+        }
+        if let Some(slug_prefix) = self.slug_prefix {
+            condition = condition.add(SlugHistoryColumn::Slug.starts_with(&slug_prefix));
+        }
+        condition
+    }
+}
+
+impl IntoActiveModel<SlugHistoryActiveModel> for SlugHistoryCreateParam {
+    fn into_active_model(self) -> SlugHistoryActiveModel {
+        SlugHistoryActiveModel {
+            resource_type: Set(self.resource_type),
+            resource_id: Set(self.resource_id),
+            slug: Set(self.slug),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoActiveModel<SlugHistoryActiveModel> for SlugHistoryUpdateParam {
+    fn into_active_model(self) -> SlugHistoryActiveModel {
+        <SlugHistoryActiveModel as Default>::default()
+    }
+}
+
+impl IntoSimpleExpr for SlugHistoryAttr {
+    fn into_simple_expr(self) -> SimpleExpr {
+        match self {
+            SlugHistoryAttr::Id => SlugHistoryColumn::Id,
+            SlugHistoryAttr::CreateTime => SlugHistoryColumn::CreateTime,
+        }
+        .into_simple_expr()
+    }
+}
+
+// dao
+pub struct SlugHistoryDAO {
+    db_conn: Arc<DatabaseConnection>,
+}
+
+impl SlugHistoryDAO {
+    pub fn new(db_conn: Arc<DatabaseConnection>) -> Self {
+        Self { db_conn }
+    }
+}
+
+impl DBConnProvider for SlugHistoryDAO {
+    fn db_conn(&self) -> &DatabaseConnection {
+        &self.db_conn
+    }
+}
+
+#[async_trait]
+impl DataAccessImpl for SlugHistoryDAO {
+    type DataAttr = SlugHistoryAttr;
+    type FilterParam = SlugHistoryFilterParam;
+    type CreateParam = SlugHistoryCreateParam;
+    type UpdateParam = SlugHistoryUpdateParam;
+    type Model = SlugHistoryDataModel;
+    type Entity = SlugHistoryEntity;
+    type ActiveModel = SlugHistoryActiveModel;
+}
+
+#[async_trait]
+impl SlugHistoryDataAccess for SlugHistoryDAO {
+    async fn delete_orphans(&self) -> AppResult<u64> {
+        let article_ids = Query::select()
+            .column(ArticleColumn::Id)
+            .from(ArticleEntity)
+            .to_owned();
+        let tag_ids = Query::select()
+            .column(TagColumn::Id)
+            .from(TagEntity)
+            .to_owned();
+        SlugHistoryEntity::delete_many()
+            .filter(
+                Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(SlugHistoryColumn::ResourceType.eq(SLUG_RESOURCE_ARTICLE))
+                            .add(SlugHistoryColumn::ResourceId.not_in_subquery(article_ids)),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(SlugHistoryColumn::ResourceType.eq(SLUG_RESOURCE_TAG))
+                            .add(SlugHistoryColumn::ResourceId.not_in_subquery(tag_ids)),
+                    ),
+            )
+            .exec(self.db_conn())
+            .await
+            .map(|delete_res| delete_res.rows_affected)
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+}
+
+// the daos of resources with a slug call these inside their own transactions, so the
+// history never disagrees with the current slugs
+
+// links to the resource may have the new slug dropped from its history, the old slug leads
+// to the resource from now on
+pub(super) async fn record_slug_change<C: ConnectionTrait>(
+    db_conn: &C,
+    resource_type: i32,
+    resource_id: i32,
+    old_slug: String,
+    new_slug: &str,
+) -> AppResult<()> {
+    SlugHistoryEntity::delete_many()
+        .filter(SlugHistoryColumn::ResourceType.eq(resource_type))
+        .filter(SlugHistoryColumn::ResourceId.eq(resource_id))
+        .filter(SlugHistoryColumn::Slug.eq(new_slug))
+        .exec(db_conn)
+        .await
+        .with_err_kind(AppErrorKind::DBOperationError)?;
+    SlugHistoryEntity::insert(
+        SlugHistoryCreateParam {
+            resource_type,
+            resource_id,
+            slug: old_slug,
+        }
+        .into_active_model(),
+    )
+    .exec(db_conn)
+    .await
+    .with_err_kind(AppErrorKind::DBOperationError)?;
+    Ok(())
+}
+
+// the current slug and the history of the source resource lead to the target from now on
+pub(super) async fn redirect_slugs<C: ConnectionTrait>(
+    db_conn: &C,
+    resource_type: i32,
+    source_id: i32,
+    source_slug: String,
+    target_id: i32,
+) -> AppResult<()> {
+    SlugHistoryEntity::update_many()
+        .col_expr(SlugHistoryColumn::ResourceId, Expr::value(target_id))
+        .filter(SlugHistoryColumn::ResourceType.eq(resource_type))
+        .filter(SlugHistoryColumn::ResourceId.eq(source_id))
+        .exec(db_conn)
+        .await
+        .with_err_kind(AppErrorKind::DBOperationError)?;
+    SlugHistoryEntity::insert(
+        SlugHistoryCreateParam {
+            resource_type,
+            resource_id: target_id,
+            slug: source_slug,
+        }
+        .into_active_model(),
+    )
+    .exec(db_conn)
+    .await
+    .with_err_kind(AppErrorKind::DBOperationError)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{
+        config::AppConfig,
+        dao::{traits::prelude::DataAccess, types::OrderParam},
+        db::prelude::create_db_conn,
+    };
+
+    #[tokio::test]
+    async fn test_slug_history_dao() {
+        // init
+        let cfg = AppConfig::init("config/config_test.toml").unwrap();
+        let db_conn = Arc::new(create_db_conn(&cfg.db).await.unwrap());
+        let slug_history_dao = SlugHistoryDAO::new(db_conn);
+
+        // delete all test data
+        let test_filter = SlugHistoryFilterParam {
+            slug_prefix: Some("test-slug".to_string()),
+            ..Default::default()
+        };
+        <SlugHistoryDAO as DataAccess>::delete_all(&slug_history_dao, test_filter.clone())
+            .await
+            .unwrap();
+
+        // test create success
+        let create_param = SlugHistoryCreateParam {
+            resource_type: 0,
+            resource_id: 1,
+            slug: "test-slug".to_string(),
+        };
+        <SlugHistoryDAO as DataAccess>::create(&slug_history_dao, create_param.clone())
+            .await
+            .unwrap();
+
+        // test create failed(slug already recorded for the same resource type)
+        let create_res =
+            <SlugHistoryDAO as DataAccess>::create(&slug_history_dao, create_param.clone()).await;
+        assert!(create_res.is_err());
+
+        // test the same slug for another resource type
+        <SlugHistoryDAO as DataAccess>::create(
+            &slug_history_dao,
+            SlugHistoryCreateParam {
+                resource_type: 1,
+                slug: "test-slug-2".to_string(),
+                ..create_param
+            },
+        )
+        .await
+        .unwrap();
+
+        // test list by prefix and type
+        let histories = <SlugHistoryDAO as DataAccess>::list_all(
+            &slug_history_dao,
+            SlugHistoryFilterParam {
+                resource_type: Some(0),
+                ..test_filter.clone()
+            },
+            OrderParam::<SlugHistoryAttr>::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(histories.len(), 1);
+        assert_eq!(histories[0].slug, "test-slug");
+
+        // clean up
+        let delete_num =
+            <SlugHistoryDAO as DataAccess>::delete_all(&slug_history_dao, test_filter).await;
+        assert_eq!(delete_num.unwrap(), 2);
+
+        // test delete orphans, the history of a resource that no longer exists goes away
+        let orphan = <SlugHistoryDAO as DataAccess>::create(
+            &slug_history_dao,
+            SlugHistoryCreateParam {
+                resource_type: SLUG_RESOURCE_ARTICLE,
+                resource_id: -1,
+                slug: "test-slug-orphan".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let delete_num = slug_history_dao.delete_orphans().await.unwrap();
+        assert!(delete_num >= 1);
+        let orphan_res = <SlugHistoryDAO as DataAccess>::get(
+            &slug_history_dao,
+            SlugHistoryFilterParam {
+                id: Some(orphan.id),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert!(orphan_res.is_err());
+    }
+}
@@ -0,0 +1,743 @@
+// ********************* import ********************* //
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, IntoActiveModel, IntoSimpleExpr,
+    ModelTrait, QueryFilter, QuerySelect, Set, TransactionTrait,
+};
+use sea_query::{Expr, IntoCondition, SimpleExpr};
+
+use super::{
+    super::{
+        traits::tag::TagDataAccess,
+        types::{slug_history::prelude::SLUG_RESOURCE_TAG, tag::prelude::*},
+    },
+    slug_history::{record_slug_change, redirect_slugs},
+    DBConnProvider, DataAccessImpl, SoftDeleteImpl,
+};
+use crate::app::{
+    common::prelude::*,
+    db::prelude::{
+        ArticleTagColumn, ArticleTagEntity, DatabaseConnection, TagActiveModel, TagColumn,
+        TagEntity, TagToChildTagLink, TagToParentTagLink,
+    },
+};
+
+// ********************* content ********************* //
+// params
+impl IntoCondition for TagFilterParam {
+    fn into_condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(id) = self.id {
+            condition = condition.add(TagColumn::Id.eq(id));
+        }
+        if let Some(ids) = self.ids {
+            condition = condition.add(TagColumn::Id.is_in(ids));
+        }
+        if let Some(name) = self.name {
+            condition = condition.add(TagColumn::Name.eq(name));
+        }
+        if let Some(slug) = self.slug {
+            condition = condition.add(TagColumn::Slug.eq(slug));
+        }
+        if let Some(slug_prefix) = self.slug_prefix {
+            condition = condition.add(TagColumn::Slug.starts_with(&slug_prefix));
+        }
+        if let Some(parent_tag_id) = self.parent_tag_id {
+            condition = match parent_tag_id {
+                Some(parent_tag_id) => condition.add(TagColumn::ParentTagId.eq(parent_tag_id)),
+                None => condition.add(TagColumn::ParentTagId.is_null()),
+            };
+        }
+        if let Some(status_type) = self.status_type {
+            condition = condition.add(TagColumn::StatusType.eq(status_type));
+        }
+        if let Some(status_types) = self.status_types {
+            condition = condition.add(TagColumn::StatusType.is_in(status_types));
+        }
+        if let Some(name_search) = self.name_search {
+            condition = condition.add(TagColumn::Name.contains(&name_search));
+        }
+        condition
+    }
+}
+
+impl IntoActiveModel<TagActiveModel> for TagCreateParam {
+    fn into_active_model(self) -> TagActiveModel {
+        TagActiveModel {
+            name: Set(self.name),
+            slug: Set(self.slug),
+            parent_tag_id: Set(self.parent_tag_id),
+            thumbnail_url: Set(self.thumbnail_url),
+            create_user_id: Set(self.create_user_id),
+            update_user_id: Set(self.create_user_id),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoActiveModel<TagActiveModel> for TagUpdateParam {
+    fn into_active_model(self) -> TagActiveModel {
+        let mut active_model = <TagActiveModel as Default>::default();
+        if let Some(name) = self.name {
+            active_model.name = Set(name);
+        }
+        if let Some(slug) = self.slug {
+            active_model.slug = Set(slug);
+        }
+        if let Some(parent_tag_id) = self.parent_tag_id {
+            active_model.parent_tag_id = Set(parent_tag_id);
+        }
+        if let Some(thumbnail_url) = self.thumbnail_url {
+            active_model.thumbnail_url = Set(thumbnail_url);
+        }
+        if let Some(status_type) = self.status_type {
+            active_model.status_type = Set(status_type);
+        }
+        if let Some(update_user_id) = self.update_user_id {
+            active_model.update_user_id = Set(update_user_id);
+        }
+        active_model
+    }
+}
+
+impl IntoSimpleExpr for TagAttr {
+    fn into_simple_expr(self) -> SimpleExpr {
+        match self {
+            TagAttr::Id => TagColumn::Id,
+            TagAttr::Name => TagColumn::Name,
+            TagAttr::CreateTime => TagColumn::CreateTime,
+            TagAttr::UpdateTime => TagColumn::UpdateTime,
+            TagAttr::DeleteTime => TagColumn::DeleteTime,
+        }
+        .into_simple_expr()
+    }
+}
+
+// the whole hierarchy read inside a transaction, its rows stay locked until the transaction
+// ends, so concurrent restructures are checked against the tree they are going to change
+struct TagHierarchy {
+    models: HashMap<i32, TagDataModel>,
+    children: HashMap<i32, Vec<i32>>,
+}
+
+impl TagHierarchy {
+    async fn lock<C: ConnectionTrait>(db_conn: &C) -> AppResult<Self> {
+        let tag_models = TagEntity::find()
+            .lock_exclusive()
+            .all(db_conn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?;
+        let mut children: HashMap<i32, Vec<i32>> = HashMap::new();
+        for model in &tag_models {
+            if let Some(parent_tag_id) = model.parent_tag_id {
+                children.entry(parent_tag_id).or_default().push(model.id);
+            }
+        }
+        Ok(Self {
+            models: tag_models
+                .into_iter()
+                .map(|model| (model.id, model))
+                .collect(),
+            children,
+        })
+    }
+
+    fn get_alive(&self, id: i32) -> AppResult<&TagDataModel> {
+        self.models
+            .get(&id)
+            .filter(|model| model.status_type != 2)
+            .ok_or_else(|| {
+                AppError::new(
+                    format!("Data not found, tag_id: {}", id),
+                    AppErrorKind::ResourceNotFound,
+                )
+            })
+    }
+
+    // the tag itself followed by its ancestors, up to the top-level tag
+    fn ancestors(&self, id: i32) -> AppResult<Vec<i32>> {
+        let mut path = vec![id];
+        while let Some(parent_tag_id) = self
+            .models
+            .get(&path[path.len() - 1])
+            .and_then(|model| model.parent_tag_id)
+        {
+            if path.contains(&parent_tag_id) {
+                return Err(AppError::new(
+                    format!("Cycle detected above tag {}", id),
+                    AppErrorKind::TagHierarchyConflict,
+                ));
+            }
+            path.push(parent_tag_id);
+        }
+        Ok(path)
+    }
+
+    // the levels below the tag, tags in the trash included since a restore brings them back
+    fn descendant_levels(&self, id: i32) -> AppResult<Vec<Vec<i32>>> {
+        let mut visited = HashSet::from([id]);
+        let mut levels = Vec::new();
+        let mut level = vec![id];
+        loop {
+            level = level
+                .iter()
+                .flat_map(|tag_id| self.children.get(tag_id).into_iter().flatten().copied())
+                .collect();
+            if level.is_empty() {
+                return Ok(levels);
+            }
+            if !level.iter().all(|tag_id| visited.insert(*tag_id)) {
+                return Err(AppError::new(
+                    format!("Cycle detected below tag {}", id),
+                    AppErrorKind::TagHierarchyConflict,
+                ));
+            }
+            levels.push(level.clone());
+        }
+    }
+
+    // the number of levels of the subtree rooted at the tag, 1 for a leaf
+    fn height(&self, id: i32) -> AppResult<usize> {
+        Ok(1 + self.descendant_levels(id)?.len())
+    }
+
+    // the tag itself is not counted, neither are tags in the trash
+    fn count_live_descendants(&self, id: i32) -> AppResult<u64> {
+        Ok(self
+            .descendant_levels(id)?
+            .iter()
+            .flatten()
+            .filter(|tag_id| self.models[tag_id].status_type != 2)
+            .count() as u64)
+    }
+}
+
+fn check_depth(depth: usize, max_depth: usize) -> AppResult<()> {
+    if depth > max_depth {
+        return Err(AppError::new(
+            format!(
+                "Tag depth {} exceeds the maximum depth {}",
+                depth, max_depth
+            ),
+            AppErrorKind::TagHierarchyConflict,
+        ));
+    }
+    Ok(())
+}
+
+// dao
+pub struct TagDAO {
+    db_conn: Arc<DatabaseConnection>,
+}
+
+impl TagDAO {
+    pub fn new(db_conn: Arc<DatabaseConnection>) -> Self {
+        Self { db_conn }
+    }
+
+    async fn reparent_children<C: ConnectionTrait>(
+        db_conn: &C,
+        id: i32,
+        parent_tag_id: Option<i32>,
+        update_user_id: i32,
+    ) -> AppResult<()> {
+        TagEntity::update_many()
+            .col_expr(TagColumn::ParentTagId, Expr::value(parent_tag_id))
+            .col_expr(TagColumn::UpdateUserId, Expr::value(update_user_id))
+            .filter(TagColumn::ParentTagId.eq(id))
+            .exec(db_conn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?;
+        Ok(())
+    }
+}
+
+impl DBConnProvider for TagDAO {
+    fn db_conn(&self) -> &DatabaseConnection {
+        &self.db_conn
+    }
+}
+
+#[async_trait]
+impl DataAccessImpl for TagDAO {
+    type DataAttr = TagAttr;
+    type FilterParam = TagFilterParam;
+    type CreateParam = TagCreateParam;
+    type UpdateParam = TagUpdateParam;
+    type Model = TagDataModel;
+    type Entity = TagEntity;
+    type ActiveModel = TagActiveModel;
+}
+
+impl SoftDeleteImpl for TagDAO {
+    const DELETED_STATUS_TYPE: i32 = 2;
+    // restored tags come back hidden
+    const RESTORED_STATUS_TYPE: i32 = 1;
+
+    fn status_type_column() -> TagColumn {
+        TagColumn::StatusType
+    }
+
+    fn delete_time_column() -> TagColumn {
+        TagColumn::DeleteTime
+    }
+
+    fn delete_user_id_column() -> TagColumn {
+        TagColumn::DeleteUserId
+    }
+}
+
+#[async_trait]
+impl TagDataAccess for TagDAO {
+    async fn edit(
+        &self,
+        filter: TagFilterParam,
+        update_param: TagUpdateParam,
+    ) -> AppResult<TagDataModel> {
+        let txn = self.db_conn().begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        let old_model = <Self as DataAccessImpl>::get(self, &txn, filter.clone()).await?;
+        <Self as DataAccessImpl>::update(self, &txn, filter.clone(), update_param).await?;
+        let tag_model = <Self as DataAccessImpl>::get(self, &txn, filter).await?;
+        if tag_model.slug != old_model.slug {
+            record_slug_change(
+                &txn,
+                SLUG_RESOURCE_TAG,
+                tag_model.id,
+                old_model.slug,
+                &tag_model.slug,
+            )
+            .await?;
+        }
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        Ok(tag_model)
+    }
+
+    async fn get_parent(&self, id: i32) -> AppResult<Option<TagDataModel>> {
+        let filter = TagFilterParam {
+            id: Some(id),
+            ..Default::default()
+        };
+        <Self as DataAccessImpl>::get(self, self.db_conn(), filter)
+            .await?
+            .find_linked(TagToParentTagLink)
+            .one(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn list_children(&self, id: i32) -> AppResult<Vec<TagDataModel>> {
+        let filter = TagFilterParam {
+            id: Some(id),
+            ..Default::default()
+        };
+        <Self as DataAccessImpl>::get(self, self.db_conn(), filter)
+            .await?
+            .find_linked(TagToChildTagLink)
+            .all(self.db_conn())
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)
+    }
+
+    async fn move_subtree(
+        &self,
+        id: i32,
+        parent_tag_id: Option<i32>,
+        max_depth: usize,
+        update_user_id: i32,
+    ) -> AppResult<TagRestructureSummary> {
+        let txn = self.db_conn().begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        let hierarchy = TagHierarchy::lock(&txn).await?;
+        hierarchy.get_alive(id)?;
+        let parent_depth = match parent_tag_id {
+            Some(parent_tag_id) => {
+                hierarchy.get_alive(parent_tag_id)?;
+                let ancestors = hierarchy.ancestors(parent_tag_id)?;
+                if ancestors.contains(&id) {
+                    return Err(AppError::new(
+                        format!(
+                            "Moving tag {} under tag {} would create a cycle",
+                            id, parent_tag_id
+                        ),
+                        AppErrorKind::TagHierarchyConflict,
+                    ));
+                }
+                ancestors.len()
+            }
+            None => 0,
+        };
+        check_depth(parent_depth + hierarchy.height(id)?, max_depth)?;
+        <Self as DataAccessImpl>::update(
+            self,
+            &txn,
+            TagFilterParam {
+                id: Some(id),
+                ..Default::default()
+            },
+            TagUpdateParam {
+                parent_tag_id: Some(parent_tag_id),
+                update_user_id: Some(update_user_id),
+                ..Default::default()
+            },
+        )
+        .await?;
+        let moved_tags = 1 + hierarchy.count_live_descendants(id)?;
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        Ok(TagRestructureSummary {
+            moved_tags,
+            ..Default::default()
+        })
+    }
+
+    async fn merge(
+        &self,
+        source_id: i32,
+        target_id: i32,
+        max_depth: usize,
+        update_user_id: i32,
+    ) -> AppResult<TagRestructureSummary> {
+        let txn = self.db_conn().begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        let hierarchy = TagHierarchy::lock(&txn).await?;
+        hierarchy.get_alive(source_id)?;
+        hierarchy.get_alive(target_id)?;
+        let target_path = hierarchy.ancestors(target_id)?;
+        if target_path.contains(&source_id) {
+            return Err(AppError::new(
+                format!(
+                    "Merging tag {} into its descendant {} would create a cycle",
+                    source_id, target_id
+                ),
+                AppErrorKind::TagHierarchyConflict,
+            ));
+        }
+        // the children of the source tag take its place below the target tag
+        check_depth(
+            target_path.len() + hierarchy.height(source_id)? - 1,
+            max_depth,
+        )?;
+        let target_article_ids: Vec<i32> = ArticleTagEntity::find()
+            .filter(ArticleTagColumn::TagId.eq(target_id))
+            .all(&txn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?
+            .into_iter()
+            .map(|model| model.article_id)
+            .collect();
+        let dropped_articles = ArticleTagEntity::delete_many()
+            .filter(ArticleTagColumn::TagId.eq(source_id))
+            .filter(ArticleTagColumn::ArticleId.is_in(target_article_ids))
+            .exec(&txn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?
+            .rows_affected;
+        let moved_articles = ArticleTagEntity::update_many()
+            .col_expr(ArticleTagColumn::TagId, Expr::value(target_id))
+            .col_expr(
+                ArticleTagColumn::PinPriority,
+                Expr::value(Option::<i32>::None),
+            )
+            .filter(ArticleTagColumn::TagId.eq(source_id))
+            .exec(&txn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?
+            .rows_affected;
+        let moved_tags = hierarchy.count_live_descendants(source_id)?;
+        Self::reparent_children(&txn, source_id, Some(target_id), update_user_id).await?;
+        let source_filter = TagFilterParam {
+            id: Some(source_id),
+            ..Default::default()
+        };
+        let source_model = <Self as DataAccessImpl>::get(self, &txn, source_filter.clone()).await?;
+        <Self as SoftDeleteImpl>::soft_delete(self, &txn, source_filter, update_user_id).await?;
+        redirect_slugs(
+            &txn,
+            SLUG_RESOURCE_TAG,
+            source_id,
+            source_model.slug,
+            target_id,
+        )
+        .await?;
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        Ok(TagRestructureSummary {
+            moved_tags,
+            moved_articles,
+            dropped_articles,
+            deleted_tags: 1,
+        })
+    }
+
+    async fn dissolve(&self, id: i32, delete_user_id: i32) -> AppResult<TagRestructureSummary> {
+        let filter = TagFilterParam {
+            id: Some(id),
+            ..Default::default()
+        };
+        let txn = self.db_conn().begin().await.wrap(
+            "Failed to start db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        let hierarchy = TagHierarchy::lock(&txn).await?;
+        let parent_tag_id = hierarchy.get_alive(id)?.parent_tag_id;
+        let moved_tags = hierarchy.count_live_descendants(id)?;
+        Self::reparent_children(&txn, id, parent_tag_id, delete_user_id).await?;
+        <Self as SoftDeleteImpl>::soft_delete(self, &txn, filter, delete_user_id).await?;
+        txn.commit().await.wrap(
+            "Failed to commit db transaction.",
+            AppErrorKind::DBOperationError,
+        )?;
+        Ok(TagRestructureSummary {
+            moved_tags,
+            deleted_tags: 1,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{EntityTrait, QueryFilter};
+
+    use super::*;
+    use crate::app::{
+        config::AppConfig,
+        dao::{impls::create_test_user, traits::prelude::DataAccess, types::OrderParam},
+        db::prelude::{create_db_conn, SlugHistoryColumn, SlugHistoryEntity, UserEntity},
+    };
+
+    #[tokio::test]
+    async fn test_tag_dao() {
+        // init
+        let cfg = AppConfig::init("config/config_test.toml").unwrap();
+        let db_conn = Arc::new(create_db_conn(&cfg.db).await.unwrap());
+        let tag_dao = TagDAO::new(db_conn.clone());
+
+        // the slug history has no foreign key, so it is cleaned up separately
+        let delete_history = SlugHistoryEntity::delete_many()
+            .filter(SlugHistoryColumn::ResourceType.eq(SLUG_RESOURCE_TAG))
+            .filter(SlugHistoryColumn::Slug.starts_with("test-"));
+        delete_history.clone().exec(db_conn.as_ref()).await.unwrap();
+        // prepare an admin author, deleting it cascades to all test tags
+        let author = create_test_user(db_conn.as_ref(), "test_tag_author", 1).await;
+
+        // test create
+        let root = <TagDAO as DataAccess>::create(
+            &tag_dao,
+            TagCreateParam {
+                name: "test_root".to_string(),
+                slug: "test-root".to_string(),
+                parent_tag_id: None,
+                thumbnail_url: None,
+                create_user_id: author.id,
+            },
+        )
+        .await
+        .unwrap();
+        let child = <TagDAO as DataAccess>::create(
+            &tag_dao,
+            TagCreateParam {
+                name: "test_child".to_string(),
+                slug: "test-child".to_string(),
+                parent_tag_id: Some(root.id),
+                thumbnail_url: None,
+                create_user_id: author.id,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(child.parent_tag_id, Some(root.id));
+
+        // test get_parent
+        let parent = tag_dao.get_parent(child.id).await.unwrap();
+        assert_eq!(parent.map(|tag| tag.id), Some(root.id));
+        let parent = tag_dao.get_parent(root.id).await.unwrap();
+        assert!(parent.is_none());
+
+        // test list_children
+        let children = tag_dao.list_children(root.id).await.unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].id, child.id);
+        let children = tag_dao.list_children(child.id).await.unwrap();
+        assert!(children.is_empty());
+
+        // test list_all
+        let tag_list = <TagDAO as DataAccess>::list_all(
+            &tag_dao,
+            TagFilterParam {
+                ids: Some(vec![root.id, child.id]),
+                ..Default::default()
+            },
+            OrderParam {
+                by: TagAttr::Id,
+                ascending: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(tag_list.len(), 2);
+        assert_eq!(tag_list[0].id, root.id);
+
+        // test move_subtree, the tag takes its descendants along
+        let leaf = <TagDAO as DataAccess>::create(
+            &tag_dao,
+            TagCreateParam {
+                name: "test_leaf".to_string(),
+                slug: "test-leaf".to_string(),
+                parent_tag_id: Some(child.id),
+                thumbnail_url: None,
+                create_user_id: author.id,
+            },
+        )
+        .await
+        .unwrap();
+        let other = <TagDAO as DataAccess>::create(
+            &tag_dao,
+            TagCreateParam {
+                name: "test_other".to_string(),
+                slug: "test-other".to_string(),
+                parent_tag_id: None,
+                thumbnail_url: None,
+                create_user_id: author.id,
+            },
+        )
+        .await
+        .unwrap();
+        let summary = tag_dao
+            .move_subtree(child.id, Some(other.id), 3, author.id)
+            .await
+            .unwrap();
+        assert_eq!(summary.moved_tags, 2);
+        let parent = tag_dao.get_parent(leaf.id).await.unwrap();
+        assert_eq!(parent.map(|tag| tag.id), Some(child.id));
+
+        // test move_subtree failed(cycle or too deep), nothing changes
+        assert!(tag_dao
+            .move_subtree(other.id, Some(leaf.id), 5, author.id)
+            .await
+            .is_err());
+        assert!(tag_dao
+            .move_subtree(other.id, Some(root.id), 3, author.id)
+            .await
+            .is_err());
+        let parent = tag_dao.get_parent(other.id).await.unwrap();
+        assert!(parent.is_none());
+
+        // test merge, the children of the source tag go over to the target tag
+        let summary = tag_dao
+            .merge(other.id, root.id, 3, author.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            summary,
+            TagRestructureSummary {
+                moved_tags: 2,
+                deleted_tags: 1,
+                ..Default::default()
+            }
+        );
+        let parent = tag_dao.get_parent(child.id).await.unwrap();
+        assert_eq!(parent.map(|tag| tag.id), Some(root.id));
+        // links to the source tag lead to the target tag
+        let history = SlugHistoryEntity::find()
+            .filter(SlugHistoryColumn::ResourceType.eq(SLUG_RESOURCE_TAG))
+            .filter(SlugHistoryColumn::Slug.eq("test-other"))
+            .one(db_conn.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(history.map(|history| history.resource_id), Some(root.id));
+
+        // test merge failed(source tag already in the trash)
+        assert!(tag_dao
+            .merge(other.id, root.id, 3, author.id)
+            .await
+            .is_err());
+
+        // test dissolve, the children of the tag go over to its parent
+        let summary = tag_dao.dissolve(child.id, author.id).await.unwrap();
+        assert_eq!(summary.moved_tags, 1);
+        assert_eq!(summary.deleted_tags, 1);
+        let parent = tag_dao.get_parent(leaf.id).await.unwrap();
+        assert_eq!(parent.map(|tag| tag.id), Some(root.id));
+
+        // test update(move to top level)
+        let child_filter = TagFilterParam {
+            id: Some(child.id),
+            ..Default::default()
+        };
+        <TagDAO as DataAccess>::update(
+            &tag_dao,
+            child_filter.clone(),
+            TagUpdateParam {
+                parent_tag_id: Some(None),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let top_level_num = <TagDAO as DataAccess>::count(
+            &tag_dao,
+            TagFilterParam {
+                ids: Some(vec![root.id, child.id]),
+                parent_tag_id: Some(None),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(top_level_num, 2);
+
+        // test edit, the old slug goes into the slug history
+        let child_model = tag_dao
+            .edit(
+                child_filter.clone(),
+                TagUpdateParam {
+                    slug: Some("test-child-renamed".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(child_model.slug, "test-child-renamed");
+        let history = SlugHistoryEntity::find()
+            .filter(SlugHistoryColumn::ResourceType.eq(SLUG_RESOURCE_TAG))
+            .filter(SlugHistoryColumn::Slug.eq("test-child"))
+            .one(db_conn.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(history.map(|history| history.resource_id), Some(child.id));
+
+        // test get_parent failed(tag not found)
+        <TagDAO as DataAccess>::delete(&tag_dao, child_filter)
+            .await
+            .unwrap();
+        assert!(tag_dao.get_parent(child.id).await.is_err());
+
+        // clean up
+        UserEntity::delete_by_id(author.id)
+            .exec(db_conn.as_ref())
+            .await
+            .unwrap();
+        delete_history.exec(db_conn.as_ref()).await.unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+// ********************* import ********************* //
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::{ColumnTrait, Condition, IntoActiveModel, IntoSimpleExpr, Set};
+use sea_query::{IntoCondition, SimpleExpr};
+
+use super::{
+    super::{traits::upload::UploadDataAccess, types::upload::prelude::*},
+    DBConnProvider, DataAccessImpl,
+};
+use crate::app::db::prelude::{DatabaseConnection, UploadActiveModel, UploadColumn, UploadEntity};
+
+// ********************* content ********************* //
+// params
+impl IntoCondition for UploadFilterParam {
+    fn into_condition(self) -> Condition {
+        let mut condition = Condition::all();
+        if let Some(id) = self.id {
+            condition = condition.add(UploadColumn::Id.eq(id));
+        }
+        if let Some(storage_key) = self.storage_key {
+            condition = condition.add(UploadColumn::StorageKey.eq(storage_key));
+        }
+        if let Some(create_user_id) = self.create_user_id {
+            condition = condition.add(UploadColumn::CreateUserId.eq(create_user_id));
+        }
+        condition
+    }
+}
+
+impl IntoActiveModel<UploadActiveModel> for UploadCreateParam {
+    fn into_active_model(self) -> UploadActiveModel {
+        UploadActiveModel {
+            storage_key: Set(self.storage_key),
+            file_name: Set(self.file_name),
+            mime_type: Set(self.mime_type),
+            size: Set(self.size),
+            create_user_id: Set(self.create_user_id),
+            ..Default::default()
+        }
+    }
+}
+
+impl IntoActiveModel<UploadActiveModel> for UploadUpdateParam {
+    fn into_active_model(self) -> UploadActiveModel {
+        <UploadActiveModel as Default>::default()
+    }
+}
+
+impl IntoSimpleExpr for UploadAttr {
+    fn into_simple_expr(self) -> SimpleExpr {
+        match self {
+            UploadAttr::Id => UploadColumn::Id,
+            UploadAttr::CreateTime => UploadColumn::CreateTime,
+        }
+        .into_simple_expr()
+    }
+}
+
+// dao
+pub struct UploadDAO {
+    db_conn: Arc<DatabaseConnection>,
+}
+
+impl UploadDAO {
+    pub fn new(db_conn: Arc<DatabaseConnection>) -> Self {
+        Self { db_conn }
+    }
+}
+
+impl DBConnProvider for UploadDAO {
+    fn db_conn(&self) -> &DatabaseConnection {
+        &self.db_conn
+    }
+}
+
+#[async_trait]
+impl DataAccessImpl for UploadDAO {
+    type DataAttr = UploadAttr;
+    type FilterParam = UploadFilterParam;
+    type CreateParam = UploadCreateParam;
+    type UpdateParam = UploadUpdateParam;
+    type Model = UploadDataModel;
+    type Entity = UploadEntity;
+    type ActiveModel = UploadActiveModel;
+}
+
+impl UploadDataAccess for UploadDAO {}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::EntityTrait;
+
+    use super::*;
+    use crate::app::{
+        config::AppConfig,
+        dao::{impls::create_test_user, traits::prelude::DataAccess, types::OrderParam},
+        db::prelude::{create_db_conn, UserEntity},
+    };
+
+    #[tokio::test]
+    async fn test_upload_dao() {
+        // init
+        let cfg = AppConfig::init("config/config_test.toml").unwrap();
+        let db_conn = Arc::new(create_db_conn(&cfg.db).await.unwrap());
+        let upload_dao = UploadDAO::new(db_conn.clone());
+
+        // prepare an uploader, deleting it cascades to all test uploads
+        let uploader = create_test_user(db_conn.as_ref(), "test_uploader", 0).await;
+        let test_filter = UploadFilterParam {
+            create_user_id: Some(uploader.id),
+            ..Default::default()
+        };
+
+        // test create, the same content may be uploaded more than once
+        let create_param = UploadCreateParam {
+            storage_key: "test_upload.png".to_string(),
+            file_name: "a.png".to_string(),
+            mime_type: "image/png".to_string(),
+            size: 42,
+            create_user_id: uploader.id,
+        };
+        let upload = <UploadDAO as DataAccess>::create(&upload_dao, create_param.clone())
+            .await
+            .unwrap();
+        assert_eq!(upload.size, 42);
+        <UploadDAO as DataAccess>::create(
+            &upload_dao,
+            UploadCreateParam {
+                file_name: "b.png".to_string(),
+                ..create_param
+            },
+        )
+        .await
+        .unwrap();
+
+        // test get by storage key
+        let uploads = <UploadDAO as DataAccess>::list_all(
+            &upload_dao,
+            UploadFilterParam {
+                storage_key: Some("test_upload.png".to_string()),
+                ..test_filter.clone()
+            },
+            OrderParam::<UploadAttr>::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(uploads.len(), 2);
+        assert_eq!(uploads[0].id, upload.id);
+
+        // clean up
+        let delete_num = <UploadDAO as DataAccess>::delete_all(&upload_dao, test_filter).await;
+        assert_eq!(delete_num.unwrap(), 2);
+        UserEntity::delete_by_id(uploader.id)
+            .exec(db_conn.as_ref())
+            .await
+            .unwrap();
+    }
+}
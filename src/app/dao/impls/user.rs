@@ -2,14 +2,23 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use sea_orm::{ColumnTrait, Condition, IntoActiveModel, IntoSimpleExpr, Set};
-use sea_query::{IntoCondition, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, IntoActiveModel, IntoSimpleExpr,
+    QueryFilter, Set,
+};
+use sea_query::{Expr, IntoCondition, Query, SimpleExpr};
 
 use super::{
     super::{traits::user::UserDataAccess, types::user::prelude::*},
-    DBConnProvider, DataAccessImpl,
+    DBConnProvider, DataAccessImpl, SoftDeleteImpl,
+};
+use crate::app::{
+    common::prelude::*,
+    db::prelude::{
+        ArticleColumn, ArticleEntity, CommentColumn, CommentEntity, DatabaseConnection, TagColumn,
+        TagEntity, UserActiveModel, UserColumn, UserEntity,
+    },
 };
-use crate::app::db::prelude::{DatabaseConnection, UserActiveModel, UserColumn, UserEntity};
 
 // ********************* content ********************* //
 // params
@@ -31,6 +40,9 @@ impl IntoCondition for UserFilterParam {
         if let Some(status_type) = self.status_type {
             condition = condition.add(UserColumn::StatusType.eq(status_type));
         }
+        if let Some(status_types) = self.status_types {
+            condition = condition.add(UserColumn::StatusType.is_in(status_types));
+        }
         if let Some(name_search) = self.name_search {
             let name_search_cond = Condition::any()
                 .add(UserColumn::Username.contains(&name_search))
@@ -89,6 +101,7 @@ impl IntoSimpleExpr for UserAttr {
             UserAttr::Nickname => UserColumn::Nickname,
             UserAttr::CreateTime => UserColumn::CreateTime,
             UserAttr::UpdateTime => UserColumn::UpdateTime,
+            UserAttr::DeleteTime => UserColumn::DeleteTime,
         }
         .into_simple_expr()
     }
@@ -122,19 +135,97 @@ impl DataAccessImpl for UserDAO {
     type ActiveModel = UserActiveModel;
 }
 
+#[async_trait]
+impl SoftDeleteImpl for UserDAO {
+    const DELETED_STATUS_TYPE: i32 = 3;
+    // restored users come back disabled
+    const RESTORED_STATUS_TYPE: i32 = 2;
+
+    fn status_type_column() -> UserColumn {
+        UserColumn::StatusType
+    }
+
+    fn delete_time_column() -> UserColumn {
+        UserColumn::DeleteTime
+    }
+
+    fn delete_user_id_column() -> UserColumn {
+        UserColumn::DeleteUserId
+    }
+
+    // what the purged users created goes with them, but articles and tags they only
+    // edited go back to their authors, and their comments stay as deleted placeholders so
+    // that replies of other users keep their place, revisions lose their editor by the
+    // foreign key
+    async fn before_purge<C: ConnectionTrait>(
+        &self,
+        db_conn: &C,
+        expired: Condition,
+    ) -> AppResult<()> {
+        let purged_ids = Query::select()
+            .column(UserColumn::Id)
+            .from(UserEntity)
+            .cond_where(expired)
+            .to_owned();
+        ArticleEntity::update_many()
+            .col_expr(
+                ArticleColumn::UpdateUserId,
+                Expr::col(ArticleColumn::CreateUserId).into(),
+            )
+            .col_expr(
+                ArticleColumn::UpdateTime,
+                Expr::col(ArticleColumn::UpdateTime).into(),
+            )
+            .filter(ArticleColumn::UpdateUserId.in_subquery(purged_ids.clone()))
+            .exec(db_conn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?;
+        TagEntity::update_many()
+            .col_expr(
+                TagColumn::UpdateUserId,
+                Expr::col(TagColumn::CreateUserId).into(),
+            )
+            .col_expr(
+                TagColumn::UpdateTime,
+                Expr::col(TagColumn::UpdateTime).into(),
+            )
+            .filter(TagColumn::UpdateUserId.in_subquery(purged_ids.clone()))
+            .exec(db_conn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?;
+        // t_space_comment.status_type 3: deleted, create_user_id is set to NULL by the
+        // foreign key
+        CommentEntity::update_many()
+            .col_expr(CommentColumn::Content, Expr::value(""))
+            .col_expr(CommentColumn::StatusType, Expr::value(3))
+            .filter(CommentColumn::CreateUserId.in_subquery(purged_ids))
+            .exec(db_conn)
+            .await
+            .with_err_kind(AppErrorKind::DBOperationError)?;
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl UserDataAccess for UserDAO {}
 
 #[cfg(test)]
 mod tests {
+    use chrono::{Duration, Local};
+    use sea_orm::ActiveModelTrait;
+
     use super::*;
     use crate::app::{
         config::AppConfig,
         dao::{
-            traits::prelude::DataAccess,
+            impls::create_test_user,
+            traits::prelude::{DataAccess, SoftDeleteDataAccess},
             types::{OrderParam, PaginateParam},
         },
-        db::prelude::create_db_conn,
+        db::prelude::{
+            create_db_conn, ArticleActiveModel, ArticleRevisionActiveModel, ArticleRevisionEntity,
+            CommentActiveModel,
+        },
     };
 
     #[tokio::test]
@@ -272,7 +363,7 @@ mod tests {
         }
 
         // test delete success
-        <UserDAO as DataAccess>::delete(&user_dao, user_filter)
+        <UserDAO as DataAccess>::delete(&user_dao, user_filter.clone())
             .await
             .unwrap();
         let user_num = <UserDAO as DataAccess>::count(&user_dao, test_filter.clone())
@@ -294,9 +385,183 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(delete_num, 2);
+        let user_num = <UserDAO as DataAccess>::count(&user_dao, test_filter.clone())
+            .await
+            .unwrap();
+        assert_eq!(user_num, 0);
+
+        // test soft_delete success
+        let user = <UserDAO as DataAccess>::create(&user_dao, create_param)
+            .await
+            .unwrap();
+        <UserDAO as SoftDeleteDataAccess>::soft_delete(&user_dao, user_filter.clone(), user.id)
+            .await
+            .unwrap();
+        let user = <UserDAO as DataAccess>::get(&user_dao, user_filter.clone())
+            .await
+            .unwrap();
+        assert_eq!(user.status_type, 3);
+        assert_eq!(user.delete_user_id, Some(user.id));
+        assert!(user.delete_time.is_some());
+        // login only finds users that are waiting or activated
+        let login_filter = UserFilterParam {
+            username: Some("test_user".to_string()),
+            status_types: Some(vec![0, 1]),
+            ..Default::default()
+        };
+        let login_res = <UserDAO as DataAccess>::get(&user_dao, login_filter.clone()).await;
+        assert!(login_res.is_err());
+
+        // test soft_delete failed(already in the trash)
+        let soft_delete_res =
+            <UserDAO as SoftDeleteDataAccess>::soft_delete(&user_dao, user_filter.clone(), user.id)
+                .await;
+        assert!(soft_delete_res.is_err());
+
+        // test restore success
+        <UserDAO as SoftDeleteDataAccess>::restore(&user_dao, user_filter.clone())
+            .await
+            .unwrap();
+        let user = <UserDAO as DataAccess>::get(&user_dao, user_filter.clone())
+            .await
+            .unwrap();
+        assert_eq!(user.status_type, 2);
+        assert_eq!(user.delete_user_id, None);
+        assert_eq!(user.delete_time, None);
+        // restored users come back disabled and still can not log in
+        let login_res = <UserDAO as DataAccess>::get(&user_dao, login_filter).await;
+        assert!(login_res.is_err());
+
+        // test restore failed(not in the trash)
+        let restore_res =
+            <UserDAO as SoftDeleteDataAccess>::restore(&user_dao, user_filter.clone()).await;
+        assert!(restore_res.is_err());
+
+        // test purge, only data deleted before the given time is purged
+        <UserDAO as SoftDeleteDataAccess>::soft_delete(&user_dao, user_filter, user.id)
+            .await
+            .unwrap();
+        let now = Local::now().naive_local();
+        <UserDAO as SoftDeleteDataAccess>::purge(&user_dao, now - Duration::days(1))
+            .await
+            .unwrap();
+        let user_num = <UserDAO as DataAccess>::count(&user_dao, test_filter.clone())
+            .await
+            .unwrap();
+        assert_eq!(user_num, 1);
+        let purge_num =
+            <UserDAO as SoftDeleteDataAccess>::purge(&user_dao, now + Duration::minutes(1))
+                .await
+                .unwrap();
+        assert!(purge_num >= 1);
         let user_num = <UserDAO as DataAccess>::count(&user_dao, test_filter)
             .await
             .unwrap();
         assert_eq!(user_num, 0);
     }
+
+    #[tokio::test]
+    async fn test_user_purge() {
+        // init
+        let cfg = AppConfig::init("config/config_test.toml").unwrap();
+        let db_conn = Arc::new(create_db_conn(&cfg.db).await.unwrap());
+        let user_dao = UserDAO::new(db_conn.clone());
+
+        // prepare an article of the author, last edited by the editor, and a comment of
+        // the editor with a reply of the author
+        let author = create_test_user(db_conn.as_ref(), "test_purge_author", 0).await;
+        let editor = create_test_user(db_conn.as_ref(), "test_purge_editor", 0).await;
+        let article = ArticleActiveModel {
+            title: Set("test_purge_article".to_string()),
+            slug: Set("test-purge-article".to_string()),
+            description: Set("".to_string()),
+            content: Set("".to_string()),
+            create_user_id: Set(author.id),
+            update_user_id: Set(editor.id),
+            ..Default::default()
+        }
+        .insert(db_conn.as_ref())
+        .await
+        .unwrap();
+        let revision = ArticleRevisionActiveModel {
+            article_id: Set(article.id),
+            title: Set("test_purge_article".to_string()),
+            description: Set("".to_string()),
+            content: Set("".to_string()),
+            update_user_id: Set(Some(editor.id)),
+            ..Default::default()
+        }
+        .insert(db_conn.as_ref())
+        .await
+        .unwrap();
+        let comment = CommentActiveModel {
+            article_id: Set(article.id),
+            content: Set("test".to_string()),
+            status_type: Set(1),
+            create_user_id: Set(Some(editor.id)),
+            ..Default::default()
+        }
+        .insert(db_conn.as_ref())
+        .await
+        .unwrap();
+        let reply = CommentActiveModel {
+            article_id: Set(article.id),
+            parent_comment_id: Set(Some(comment.id)),
+            depth: Set(2),
+            content: Set("test".to_string()),
+            status_type: Set(1),
+            create_user_id: Set(Some(author.id)),
+            ..Default::default()
+        }
+        .insert(db_conn.as_ref())
+        .await
+        .unwrap();
+
+        // test purge, the editor goes without taking the data of the author along
+        let editor_filter = UserFilterParam {
+            id: Some(editor.id),
+            ..Default::default()
+        };
+        <UserDAO as SoftDeleteDataAccess>::soft_delete(&user_dao, editor_filter.clone(), author.id)
+            .await
+            .unwrap();
+        let now = Local::now().naive_local();
+        <UserDAO as SoftDeleteDataAccess>::purge(&user_dao, now + Duration::minutes(1))
+            .await
+            .unwrap();
+        assert!(<UserDAO as DataAccess>::get(&user_dao, editor_filter)
+            .await
+            .is_err());
+        let article = ArticleEntity::find_by_id(article.id)
+            .one(db_conn.as_ref())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(article.update_user_id, author.id);
+        let revision = ArticleRevisionEntity::find_by_id(revision.id)
+            .one(db_conn.as_ref())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(revision.update_user_id, None);
+        let comment = CommentEntity::find_by_id(comment.id)
+            .one(db_conn.as_ref())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(comment.status_type, 3);
+        assert_eq!(comment.content, "");
+        assert_eq!(comment.create_user_id, None);
+        let reply = CommentEntity::find_by_id(reply.id)
+            .one(db_conn.as_ref())
+            .await
+            .unwrap();
+        assert!(reply.is_some());
+
+        // clean up
+        UserEntity::delete_by_id(author.id)
+            .exec(db_conn.as_ref())
+            .await
+            .unwrap();
+    }
 }
@@ -0,0 +1,41 @@
+// ********************* import ********************* //
+use async_trait::async_trait;
+use sea_orm::prelude::DateTime;
+
+use super::{
+    super::types::{article::prelude::*, user::prelude::*, PaginateParam},
+    SoftDeleteDataAccess,
+};
+use crate::app::common::prelude::AppResult;
+
+// ********************* content ********************* //
+#[async_trait]
+pub trait ArticleDataAccess:
+    SoftDeleteDataAccess<
+    DataModel = ArticleDataModel,
+    DataAttr = ArticleAttr,
+    FilterParam = ArticleFilterParam,
+    CreateParam = ArticleCreateParam,
+    UpdateParam = ArticleUpdateParam,
+>
+{
+    // full-text search on title and content, the backend decides how words are matched
+    async fn count_fulltext(&self, query: String, filter: ArticleFilterParam) -> AppResult<u64>;
+    // ordered by relevance, most relevant first
+    async fn list_fulltext(
+        &self,
+        query: String,
+        filter: ArticleFilterParam,
+        paginate: PaginateParam,
+    ) -> AppResult<Vec<ArticleDataModel>>;
+    // add to the current value in place, so concurrent flushes do not overwrite each other
+    async fn add_view_count(&self, id: i32, delta: i64) -> AppResult<()>;
+    async fn get_create_user(&self, id: i32) -> AppResult<UserDataModel>;
+    // slug and update time of each article ordered by id, without loading the content
+    async fn list_slugs(&self, filter: ArticleFilterParam) -> AppResult<Vec<(String, DateTime)>>;
+    // (year, month, count) grouped by publish time, the latest month first
+    async fn count_by_month(&self, filter: ArticleFilterParam) -> AppResult<Vec<(i32, i32, i64)>>;
+    // unpin all articles then pin the given ones, the first gets the highest priority,
+    // in a single transaction
+    async fn replace_pins(&self, ids: Vec<i32>) -> AppResult<()>;
+}
@@ -0,0 +1,20 @@
+// ********************* import ********************* //
+use async_trait::async_trait;
+
+use super::{super::types::article_reaction::prelude::*, RelationDataAccess};
+use crate::app::common::prelude::AppResult;
+
+// ********************* content ********************* //
+#[async_trait]
+pub trait ArticleReactionDataAccess:
+    RelationDataAccess<
+    DataModel = ArticleReactionDataModel,
+    FilterParam = ArticleReactionFilterParam,
+    CreateParam = ArticleReactionCreateParam,
+>
+{
+    // return false if the user already reacted with the kind
+    async fn add_reaction(&self, article_id: i32, user_id: i32, kind: String) -> AppResult<bool>;
+    // number of reactions per kind, kinds without reactions are left out
+    async fn count_by_kind(&self, article_id: i32) -> AppResult<Vec<(String, i64)>>;
+}
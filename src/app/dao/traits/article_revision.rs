@@ -0,0 +1,30 @@
+// ********************* import ********************* //
+use async_trait::async_trait;
+
+use super::{
+    super::types::{article::prelude::*, article_revision::prelude::*},
+    DataAccess,
+};
+use crate::app::common::prelude::AppResult;
+
+// ********************* content ********************* //
+#[async_trait]
+pub trait ArticleRevisionDataAccess:
+    DataAccess<
+    DataModel = ArticleRevisionDataModel,
+    DataAttr = ArticleRevisionAttr,
+    FilterParam = ArticleRevisionFilterParam,
+    CreateParam = ArticleRevisionCreateParam,
+    UpdateParam = ArticleRevisionUpdateParam,
+>
+{
+    // update the article in one transaction, the result is snapshotted when the title,
+    // description or content changed, an article without any revision gets its previous
+    // state snapshotted first, a changed slug goes into the slug history, the first
+    // publish is recorded as the publish time
+    async fn update_article(
+        &self,
+        article_id: i32,
+        update_param: ArticleUpdateParam,
+    ) -> AppResult<ArticleDataModel>;
+}
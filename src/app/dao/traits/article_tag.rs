@@ -0,0 +1,43 @@
+// ********************* import ********************* //
+use async_trait::async_trait;
+
+use super::{
+    super::types::{
+        article::prelude::*, article_tag::prelude::*, tag::prelude::*, OrderParam, PaginateParam,
+    },
+    RelationDataAccess,
+};
+use crate::app::common::prelude::AppResult;
+
+// ********************* content ********************* //
+#[async_trait]
+pub trait ArticleTagDataAccess:
+    RelationDataAccess<
+    DataModel = ArticleTagDataModel,
+    FilterParam = ArticleTagFilterParam,
+    CreateParam = ArticleTagCreateParam,
+>
+{
+    // the following three run in a single transaction each
+    async fn replace_tags(&self, article_id: i32, tag_ids: Vec<i32>) -> AppResult<()>;
+    // return the number of newly associated tags
+    async fn add_tags(&self, article_id: i32, tag_ids: Vec<i32>) -> AppResult<u64>;
+    // return the number of removed associations
+    async fn remove_tags(&self, article_id: i32, tag_ids: Vec<i32>) -> AppResult<u64>;
+    async fn list_tags(&self, article_id: i32) -> AppResult<Vec<TagDataModel>>;
+    // articles associated with any of the given tags
+    async fn count_articles(&self, tag_ids: Vec<i32>, filter: ArticleFilterParam)
+        -> AppResult<u64>;
+    async fn list_articles(
+        &self,
+        tag_ids: Vec<i32>,
+        filter: ArticleFilterParam,
+        order: OrderParam<ArticleAttr>,
+        paginate: PaginateParam,
+    ) -> AppResult<Vec<ArticleDataModel>>;
+    // unpin all articles within the tag then pin the given ones, the first gets the highest
+    // priority, in a single transaction
+    async fn replace_pins(&self, tag_id: i32, article_ids: Vec<i32>) -> AppResult<()>;
+    // articles pinned within the tag, the highest priority first
+    async fn list_pins(&self, tag_id: i32) -> AppResult<Vec<ArticleDataModel>>;
+}
@@ -0,0 +1,17 @@
+// ********************* import ********************* //
+use async_trait::async_trait;
+
+use super::{super::types::comment::prelude::*, DataAccess};
+
+// ********************* content ********************* //
+#[async_trait]
+pub trait CommentDataAccess:
+    DataAccess<
+    DataModel = CommentDataModel,
+    DataAttr = CommentAttr,
+    FilterParam = CommentFilterParam,
+    CreateParam = CommentCreateParam,
+    UpdateParam = CommentUpdateParam,
+>
+{
+}
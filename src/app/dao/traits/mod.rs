@@ -1,12 +1,30 @@
 // ********************* mod ********************* //
+pub mod article;
+pub mod article_reaction;
+pub mod article_revision;
+pub mod article_tag;
+pub mod comment;
+pub mod series;
+pub mod series_article;
+pub mod slug_history;
+pub mod tag;
+pub mod upload;
 pub mod user;
 
 pub mod prelude {
-    pub use super::{user::UserDataAccess, DataAccess};
+    pub use super::{
+        article::ArticleDataAccess, article_reaction::ArticleReactionDataAccess,
+        article_revision::ArticleRevisionDataAccess, article_tag::ArticleTagDataAccess,
+        comment::CommentDataAccess, series::SeriesDataAccess,
+        series_article::SeriesArticleDataAccess, slug_history::SlugHistoryDataAccess,
+        tag::TagDataAccess, upload::UploadDataAccess, user::UserDataAccess, DataAccess,
+        RelationDataAccess, SoftDeleteDataAccess,
+    };
 }
 
 // ********************* import ********************* //
 use async_trait::async_trait;
+use sea_orm::prelude::DateTime;
 
 use super::types::{OrderParam, PaginateParam};
 use crate::app::common::prelude::AppResult;
@@ -28,6 +46,11 @@ pub trait DataAccess {
         order: OrderParam<Self::DataAttr>,
         paginate: PaginateParam,
     ) -> AppResult<Vec<Self::DataModel>>;
+    async fn list_all(
+        &self,
+        filter: Self::FilterParam,
+        order: OrderParam<Self::DataAttr>,
+    ) -> AppResult<Vec<Self::DataModel>>;
     async fn create(&self, create_param: Self::CreateParam) -> AppResult<Self::DataModel>;
     async fn create_many(&self, create_param: Vec<Self::CreateParam>) -> AppResult<u64>;
     // return ResourceConflict error if multiple data found
@@ -45,3 +68,31 @@ pub trait DataAccess {
     async fn delete(&self, filter: Self::FilterParam) -> AppResult<()>;
     async fn delete_all(&self, filter: Self::FilterParam) -> AppResult<u64>;
 }
+
+// data moved into the trash keeps its row with the deleted status, along with who
+// deleted it and when, until it is purged
+#[async_trait]
+pub trait SoftDeleteDataAccess: DataAccess {
+    // return ResourceConflict error if multiple data found, data already in the trash
+    // is not found
+    async fn soft_delete(&self, filter: Self::FilterParam, delete_user_id: i32) -> AppResult<()>;
+    // return ResourceConflict error if multiple data found, only data in the trash is
+    // found
+    async fn restore(&self, filter: Self::FilterParam) -> AppResult<()>;
+    // hard-delete data moved into the trash before the given time, return the number
+    // of data deleted
+    async fn purge(&self, deleted_before: DateTime) -> AppResult<u64>;
+}
+
+// data access for association tables keyed by a composite primary key, whose rows
+// are only ever inserted or deleted
+#[async_trait]
+pub trait RelationDataAccess {
+    type DataModel;
+    type FilterParam;
+    type CreateParam;
+    async fn count(&self, filter: Self::FilterParam) -> AppResult<u64>;
+    async fn list_all(&self, filter: Self::FilterParam) -> AppResult<Vec<Self::DataModel>>;
+    async fn create_many(&self, create_param: Vec<Self::CreateParam>) -> AppResult<u64>;
+    async fn delete_all(&self, filter: Self::FilterParam) -> AppResult<u64>;
+}
@@ -0,0 +1,17 @@
+// ********************* import ********************* //
+use async_trait::async_trait;
+
+use super::{super::types::series::prelude::*, DataAccess};
+
+// ********************* content ********************* //
+#[async_trait]
+pub trait SeriesDataAccess:
+    DataAccess<
+    DataModel = SeriesDataModel,
+    DataAttr = SeriesAttr,
+    FilterParam = SeriesFilterParam,
+    CreateParam = SeriesCreateParam,
+    UpdateParam = SeriesUpdateParam,
+>
+{
+}
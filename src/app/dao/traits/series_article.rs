@@ -0,0 +1,29 @@
+// ********************* import ********************* //
+use async_trait::async_trait;
+
+use super::{
+    super::types::{article::prelude::*, series::prelude::*, series_article::prelude::*},
+    RelationDataAccess,
+};
+use crate::app::common::prelude::AppResult;
+
+// ********************* content ********************* //
+#[async_trait]
+pub trait SeriesArticleDataAccess:
+    RelationDataAccess<
+    DataModel = SeriesArticleDataModel,
+    FilterParam = SeriesArticleFilterParam,
+    CreateParam = SeriesArticleCreateParam,
+>
+{
+    // rewrite the membership in a single transaction, positions follow the given order
+    async fn replace_articles(&self, series_id: i32, article_ids: Vec<i32>) -> AppResult<()>;
+    // ordered by position
+    async fn list_articles(
+        &self,
+        series_id: i32,
+        filter: ArticleFilterParam,
+    ) -> AppResult<Vec<ArticleDataModel>>;
+    // an article belongs to at most one series
+    async fn find_series(&self, article_id: i32) -> AppResult<Option<SeriesDataModel>>;
+}
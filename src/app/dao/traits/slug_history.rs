@@ -0,0 +1,21 @@
+// ********************* import ********************* //
+use async_trait::async_trait;
+
+use super::{super::types::slug_history::prelude::*, DataAccess};
+use crate::app::common::prelude::AppResult;
+
+// ********************* content ********************* //
+#[async_trait]
+pub trait SlugHistoryDataAccess:
+    DataAccess<
+    DataModel = SlugHistoryDataModel,
+    DataAttr = SlugHistoryAttr,
+    FilterParam = SlugHistoryFilterParam,
+    CreateParam = SlugHistoryCreateParam,
+    UpdateParam = SlugHistoryUpdateParam,
+>
+{
+    // purging articles, tags or their authors leaves their history behind, delete the
+    // history of resources that no longer exist, return the number of data deleted
+    async fn delete_orphans(&self) -> AppResult<u64>;
+}
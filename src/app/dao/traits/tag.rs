@@ -0,0 +1,50 @@
+// ********************* import ********************* //
+use async_trait::async_trait;
+
+use super::{super::types::tag::prelude::*, SoftDeleteDataAccess};
+use crate::app::common::prelude::AppResult;
+
+// ********************* content ********************* //
+#[async_trait]
+pub trait TagDataAccess:
+    SoftDeleteDataAccess<
+    DataModel = TagDataModel,
+    DataAttr = TagAttr,
+    FilterParam = TagFilterParam,
+    CreateParam = TagCreateParam,
+    UpdateParam = TagUpdateParam,
+>
+{
+    // update a single tag, return the updated tag, a changed slug goes into the slug
+    // history in the same transaction
+    async fn edit(
+        &self,
+        filter: TagFilterParam,
+        update_param: TagUpdateParam,
+    ) -> AppResult<TagDataModel>;
+    // return None if the tag is a top-level tag
+    async fn get_parent(&self, id: i32) -> AppResult<Option<TagDataModel>>;
+    async fn list_children(&self, id: i32) -> AppResult<Vec<TagDataModel>>;
+    // the tag takes its descendants along, None moves it to the top level, a cycle or a
+    // tree deeper than `max_depth` is refused within the transaction
+    async fn move_subtree(
+        &self,
+        id: i32,
+        parent_tag_id: Option<i32>,
+        max_depth: usize,
+        update_user_id: i32,
+    ) -> AppResult<TagRestructureSummary>;
+    // the articles and children of the source tag go over to the target tag, then the
+    // source tag is moved into the trash, pins within the source tag are dropped, links to
+    // the source tag lead to the target tag from now on, checked like `move_subtree`
+    async fn merge(
+        &self,
+        source_id: i32,
+        target_id: i32,
+        max_depth: usize,
+        update_user_id: i32,
+    ) -> AppResult<TagRestructureSummary>;
+    // the children of the tag go over to its parent, then the tag is moved into the
+    // trash, so purging it later does not cascade to them
+    async fn dissolve(&self, id: i32, delete_user_id: i32) -> AppResult<TagRestructureSummary>;
+}
@@ -0,0 +1,17 @@
+// ********************* import ********************* //
+use async_trait::async_trait;
+
+use super::{super::types::upload::prelude::*, DataAccess};
+
+// ********************* content ********************* //
+#[async_trait]
+pub trait UploadDataAccess:
+    DataAccess<
+    DataModel = UploadDataModel,
+    DataAttr = UploadAttr,
+    FilterParam = UploadFilterParam,
+    CreateParam = UploadCreateParam,
+    UpdateParam = UploadUpdateParam,
+>
+{
+}
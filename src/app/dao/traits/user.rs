@@ -1,12 +1,12 @@
 // ********************* import ********************* //
 use async_trait::async_trait;
 
-use super::{super::types::user::prelude::*, DataAccess};
+use super::{super::types::user::prelude::*, SoftDeleteDataAccess};
 
 // ********************* content ********************* //
 #[async_trait]
 pub trait UserDataAccess:
-    DataAccess<
+    SoftDeleteDataAccess<
     DataModel = UserDataModel,
     DataAttr = UserAttr,
     FilterParam = UserFilterParam,
@@ -0,0 +1,85 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        Attr as ArticleAttr, CreateParam as ArticleCreateParam, DataModel as ArticleDataModel,
+        FilterParam as ArticleFilterParam, UpdateParam as ArticleUpdateParam,
+    };
+}
+
+// ********************* import ********************* //
+use sea_orm::prelude::DateTime;
+
+// ********************* content ********************* //
+pub type DataModel = crate::app::db::prelude::ArticleModel;
+
+#[derive(Clone, Debug, Default)]
+pub struct FilterParam {
+    pub id: Option<i32>,
+    pub ids: Option<Vec<i32>>,
+    pub slug: Option<String>,
+    pub slug_prefix: Option<String>,
+    pub title_search: Option<String>,
+    pub status_type: Option<i32>,
+    pub status_types: Option<Vec<i32>>,
+    pub is_top: Option<bool>,
+    pub create_user_id: Option<i32>,
+    pub create_time_start: Option<DateTime>,
+    pub create_time_end: Option<DateTime>,
+    // articles published before the publish time was recorded count from their create time
+    pub publish_time_start: Option<DateTime>,
+    pub publish_time_end: Option<DateTime>,
+    pub publish_at_end: Option<DateTime>,
+    pub unpublish_at_end: Option<DateTime>,
+    // neither waiting for publish_at nor past unpublish_at at the given time, and the
+    // author is not in the trash, articles of trashed users stay hidden until restored
+    pub live_at: Option<DateTime>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateParam {
+    pub title: String,
+    pub slug: String,
+    pub description: String,
+    pub content: String,
+    pub is_top: bool,
+    pub status_type: i32,
+    pub publish_at: Option<DateTime>,
+    pub unpublish_at: Option<DateTime>,
+    pub publish_time: Option<DateTime>,
+    pub create_user_id: i32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UpdateParam {
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub description: Option<String>,
+    pub content: Option<String>,
+    pub is_top: Option<bool>,
+    pub pin_priority: Option<i32>,
+    pub status_type: Option<i32>,
+    // Some(None) clears the schedule
+    pub publish_at: Option<Option<DateTime>>,
+    pub unpublish_at: Option<Option<DateTime>>,
+    // the first time the article is published, it does not move on republishing
+    pub publish_time: Option<DateTime>,
+    // only set when importing, update_time is refreshed by the database otherwise
+    pub create_time: Option<DateTime>,
+    pub update_time: Option<DateTime>,
+    pub update_user_id: Option<i32>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Attr {
+    #[default]
+    Id,
+    Title,
+    IsTop,
+    PinPriority,
+    // priority pinned within the tag, -1 when not pinned there
+    TagPinPriority(i32),
+    CreateTime,
+    PublishTime,
+    UpdateTime,
+    DeleteTime,
+}
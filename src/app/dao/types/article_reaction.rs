@@ -0,0 +1,24 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        CreateParam as ArticleReactionCreateParam, DataModel as ArticleReactionDataModel,
+        FilterParam as ArticleReactionFilterParam,
+    };
+}
+
+// ********************* content ********************* //
+pub type DataModel = crate::app::db::prelude::ArticleReactionModel;
+
+#[derive(Clone, Debug, Default)]
+pub struct FilterParam {
+    pub article_id: Option<i32>,
+    pub user_id: Option<i32>,
+    pub kind: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateParam {
+    pub article_id: i32,
+    pub user_id: i32,
+    pub kind: String,
+}
@@ -0,0 +1,38 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        Attr as ArticleRevisionAttr, CreateParam as ArticleRevisionCreateParam,
+        DataModel as ArticleRevisionDataModel, FilterParam as ArticleRevisionFilterParam,
+        UpdateParam as ArticleRevisionUpdateParam,
+    };
+}
+
+// ********************* content ********************* //
+pub type DataModel = crate::app::db::prelude::ArticleRevisionModel;
+
+#[derive(Clone, Debug, Default)]
+pub struct FilterParam {
+    pub id: Option<i32>,
+    pub ids: Option<Vec<i32>>,
+    pub article_id: Option<i32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateParam {
+    pub article_id: i32,
+    pub title: String,
+    pub description: String,
+    pub content: String,
+    pub update_user_id: i32,
+}
+
+// revisions are append-only, there is nothing to update
+#[derive(Clone, Debug, Default)]
+pub struct UpdateParam {}
+
+#[derive(Clone, Debug, Default)]
+pub enum Attr {
+    #[default]
+    Id,
+    CreateTime,
+}
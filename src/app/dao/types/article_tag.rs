@@ -0,0 +1,24 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        CreateParam as ArticleTagCreateParam, DataModel as ArticleTagDataModel,
+        FilterParam as ArticleTagFilterParam,
+    };
+}
+
+// ********************* content ********************* //
+pub type DataModel = crate::app::db::prelude::ArticleTagModel;
+
+#[derive(Clone, Debug, Default)]
+pub struct FilterParam {
+    pub article_id: Option<i32>,
+    pub tag_id: Option<i32>,
+    pub tag_ids: Option<Vec<i32>>,
+    pub is_pinned: Option<bool>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateParam {
+    pub article_id: i32,
+    pub tag_id: i32,
+}
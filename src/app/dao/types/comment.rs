@@ -0,0 +1,47 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        Attr as CommentAttr, CreateParam as CommentCreateParam, DataModel as CommentDataModel,
+        FilterParam as CommentFilterParam, UpdateParam as CommentUpdateParam,
+    };
+}
+
+// ********************* content ********************* //
+pub type DataModel = crate::app::db::prelude::CommentModel;
+
+#[derive(Clone, Debug, Default)]
+pub struct FilterParam {
+    pub id: Option<i32>,
+    pub ids: Option<Vec<i32>>,
+    pub article_id: Option<i32>,
+    // Some(None) matches top-level comments
+    pub parent_comment_id: Option<Option<i32>>,
+    pub create_user_id: Option<i32>,
+    pub status_type: Option<i32>,
+    pub status_types: Option<Vec<i32>>,
+    pub content_search: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateParam {
+    pub article_id: i32,
+    pub parent_comment_id: Option<i32>,
+    pub depth: i32,
+    pub content: String,
+    pub status_type: i32,
+    pub create_user_id: i32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UpdateParam {
+    pub content: Option<String>,
+    pub status_type: Option<i32>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub enum Attr {
+    #[default]
+    Id,
+    CreateTime,
+    UpdateTime,
+}
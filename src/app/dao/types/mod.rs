@@ -1,8 +1,23 @@
 // ********************* mod ********************* //
+pub mod article;
+pub mod article_reaction;
+pub mod article_revision;
+pub mod article_tag;
+pub mod comment;
+pub mod series;
+pub mod series_article;
+pub mod slug_history;
+pub mod tag;
+pub mod upload;
 pub mod user;
 
 pub mod prelude {
-    pub use super::{user::prelude::*, OrderParam, PaginateParam};
+    pub use super::{
+        article::prelude::*, article_reaction::prelude::*, article_revision::prelude::*,
+        article_tag::prelude::*, comment::prelude::*, series::prelude::*,
+        series_article::prelude::*, slug_history::prelude::*, tag::prelude::*, upload::prelude::*,
+        user::prelude::*, OrderParam, PaginateParam,
+    };
 }
 
 // ********************* content ********************* //
@@ -25,4 +40,6 @@ impl Default for PaginateParam {
 pub struct OrderParam<T> {
     pub by: T,
     pub ascending: bool,
+    // (attr, ascending) applied in sequence to break ties left by `by`
+    pub then: Vec<(T, bool)>,
 }
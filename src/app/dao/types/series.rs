@@ -0,0 +1,40 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        Attr as SeriesAttr, CreateParam as SeriesCreateParam, DataModel as SeriesDataModel,
+        FilterParam as SeriesFilterParam, UpdateParam as SeriesUpdateParam,
+    };
+}
+
+// ********************* content ********************* //
+pub type DataModel = crate::app::db::prelude::SeriesModel;
+
+#[derive(Clone, Debug, Default)]
+pub struct FilterParam {
+    pub id: Option<i32>,
+    pub ids: Option<Vec<i32>>,
+    pub create_user_id: Option<i32>,
+    pub title_search: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateParam {
+    pub title: String,
+    pub description: String,
+    pub create_user_id: i32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UpdateParam {
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub enum Attr {
+    #[default]
+    Id,
+    Title,
+    CreateTime,
+    UpdateTime,
+}
@@ -0,0 +1,24 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        CreateParam as SeriesArticleCreateParam, DataModel as SeriesArticleDataModel,
+        FilterParam as SeriesArticleFilterParam,
+    };
+}
+
+// ********************* content ********************* //
+pub type DataModel = crate::app::db::prelude::SeriesArticleModel;
+
+#[derive(Clone, Debug, Default)]
+pub struct FilterParam {
+    pub series_id: Option<i32>,
+    pub article_id: Option<i32>,
+    pub article_ids: Option<Vec<i32>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateParam {
+    pub series_id: i32,
+    pub article_id: i32,
+    pub position: i32,
+}
@@ -0,0 +1,42 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        Attr as SlugHistoryAttr, CreateParam as SlugHistoryCreateParam,
+        DataModel as SlugHistoryDataModel, FilterParam as SlugHistoryFilterParam,
+        UpdateParam as SlugHistoryUpdateParam, ARTICLE_RESOURCE_TYPE as SLUG_RESOURCE_ARTICLE,
+        TAG_RESOURCE_TYPE as SLUG_RESOURCE_TAG,
+    };
+}
+
+// ********************* content ********************* //
+pub type DataModel = crate::app::db::prelude::SlugHistoryModel;
+
+pub const ARTICLE_RESOURCE_TYPE: i32 = 0;
+pub const TAG_RESOURCE_TYPE: i32 = 1;
+
+#[derive(Clone, Debug, Default)]
+pub struct FilterParam {
+    pub id: Option<i32>,
+    pub resource_type: Option<i32>,
+    pub resource_id: Option<i32>,
+    pub slug: Option<String>,
+    pub slug_prefix: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateParam {
+    pub resource_type: i32,
+    pub resource_id: i32,
+    pub slug: String,
+}
+
+// old slugs are only ever recorded or released, there is nothing to update
+#[derive(Clone, Debug, Default)]
+pub struct UpdateParam {}
+
+#[derive(Clone, Debug, Default)]
+pub enum Attr {
+    #[default]
+    Id,
+    CreateTime,
+}
@@ -0,0 +1,67 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        Attr as TagAttr, CreateParam as TagCreateParam, DataModel as TagDataModel,
+        FilterParam as TagFilterParam, RestructureSummary as TagRestructureSummary,
+        UpdateParam as TagUpdateParam,
+    };
+}
+
+// ********************* content ********************* //
+pub type DataModel = crate::app::db::prelude::TagModel;
+
+#[derive(Clone, Debug, Default)]
+pub struct FilterParam {
+    pub id: Option<i32>,
+    pub ids: Option<Vec<i32>>,
+    pub name: Option<String>,
+    pub slug: Option<String>,
+    pub slug_prefix: Option<String>,
+    // Some(None) matches top-level tags
+    pub parent_tag_id: Option<Option<i32>>,
+    pub status_type: Option<i32>,
+    pub status_types: Option<Vec<i32>>,
+    pub name_search: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateParam {
+    pub name: String,
+    pub slug: String,
+    pub parent_tag_id: Option<i32>,
+    pub thumbnail_url: Option<String>,
+    pub create_user_id: i32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UpdateParam {
+    pub name: Option<String>,
+    pub slug: Option<String>,
+    pub parent_tag_id: Option<Option<i32>>,
+    pub thumbnail_url: Option<Option<String>>,
+    pub status_type: Option<i32>,
+    pub update_user_id: Option<i32>,
+}
+
+// rows affected by a change to the shape of the tag tree
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RestructureSummary {
+    // tags that ended up somewhere else in the tree, descendants included
+    pub moved_tags: u64,
+    // article links moved over to another tag
+    pub moved_articles: u64,
+    // article links dropped as the article already had the other tag
+    pub dropped_articles: u64,
+    // tags moved into the trash
+    pub deleted_tags: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub enum Attr {
+    #[default]
+    Id,
+    Name,
+    CreateTime,
+    UpdateTime,
+    DeleteTime,
+}
@@ -0,0 +1,37 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        Attr as UploadAttr, CreateParam as UploadCreateParam, DataModel as UploadDataModel,
+        FilterParam as UploadFilterParam, UpdateParam as UploadUpdateParam,
+    };
+}
+
+// ********************* content ********************* //
+pub type DataModel = crate::app::db::prelude::UploadModel;
+
+#[derive(Clone, Debug, Default)]
+pub struct FilterParam {
+    pub id: Option<i32>,
+    pub storage_key: Option<String>,
+    pub create_user_id: Option<i32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateParam {
+    pub storage_key: String,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size: i64,
+    pub create_user_id: i32,
+}
+
+// an upload is a record of what was stored, it never changes afterwards
+#[derive(Clone, Debug, Default)]
+pub struct UpdateParam {}
+
+#[derive(Clone, Debug, Default)]
+pub enum Attr {
+    #[default]
+    Id,
+    CreateTime,
+}
@@ -16,6 +16,7 @@ pub struct FilterParam {
     pub nickname: Option<String>,
     pub group_type: Option<i32>,
     pub status_type: Option<i32>,
+    pub status_types: Option<Vec<i32>>,
     pub name_search: Option<String>,
 }
 
@@ -45,4 +46,5 @@ pub enum Attr {
     Nickname,
     CreateTime,
     UpdateTime,
+    DeleteTime,
 }
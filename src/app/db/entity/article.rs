@@ -8,16 +8,25 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32, // 文章id
     pub title: String, // 文章标题
+    #[sea_orm(unique)]
+    pub slug: String, // 文章别名
     #[sea_orm(column_type = "Text")]
     pub description: String, // 文章描述
     #[sea_orm(column_type = "custom(\"MEDIUMTEXT\")")]
-    pub content: String, // 文章内容
+    pub content: String, // 文章内容（Markdown）
     pub is_top: bool,  // 是否置顶
+    pub pin_priority: i32, // 置顶优先级，越大越靠前
     pub status_type: i32, // 文章状态，0.草稿、1.已发布、2.隐藏、3.删除
+    pub publish_at: Option<DateTime>, // 定时发布时间
+    pub unpublish_at: Option<DateTime>, // 定时下线时间
+    pub publish_time: Option<DateTime>, // 首次发布时间
+    pub view_count: i64, // 浏览次数
     pub create_time: DateTime, // 创建时间
     pub update_time: DateTime, // 更新时间
     pub create_user_id: i32, // 创建用户id
     pub update_user_id: i32, // 更新用户id
+    pub delete_time: Option<DateTime>, // 删除时间
+    pub delete_user_id: Option<i32>, // 删除用户id
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -38,6 +47,14 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     UpdateUser,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::DeleteUserId",
+        to = "super::user::Column::Id",
+        on_update = "Restrict",
+        on_delete = "SetNull"
+    )]
+    DeleteUser,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -9,6 +9,7 @@ pub struct Model {
     pub article_id: i32,
     #[sea_orm(primary_key, auto_increment = false)]
     pub tag_id: i32,
+    pub pin_priority: Option<i32>, // 标签内置顶优先级，NULL表示未置顶
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
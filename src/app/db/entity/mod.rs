@@ -1,6 +1,13 @@
 pub mod article;
+pub mod article_reaction;
+pub mod article_revision;
 pub mod article_tag;
+pub mod comment;
+pub mod series;
+pub mod series_article;
+pub mod slug_history;
 pub mod tag;
+pub mod upload;
 pub mod user;
 
 pub mod prelude {
@@ -8,9 +15,41 @@ pub mod prelude {
         ActiveModel as ArticleActiveModel, Column as ArticleColumn, Entity as ArticleEntity,
         Model as ArticleModel,
     };
+    pub use super::article_reaction::{
+        ActiveModel as ArticleReactionActiveModel, Column as ArticleReactionColumn,
+        Entity as ArticleReactionEntity, Model as ArticleReactionModel,
+    };
+    pub use super::article_revision::{
+        ActiveModel as ArticleRevisionActiveModel, Column as ArticleRevisionColumn,
+        Entity as ArticleRevisionEntity, Model as ArticleRevisionModel,
+    };
+    pub use super::article_tag::{
+        ActiveModel as ArticleTagActiveModel, Column as ArticleTagColumn,
+        Entity as ArticleTagEntity, Model as ArticleTagModel,
+    };
+    pub use super::comment::{
+        ActiveModel as CommentActiveModel, Column as CommentColumn, Entity as CommentEntity,
+        Model as CommentModel,
+    };
+    pub use super::series::{
+        ActiveModel as SeriesActiveModel, Column as SeriesColumn, Entity as SeriesEntity,
+        Model as SeriesModel,
+    };
+    pub use super::series_article::{
+        ActiveModel as SeriesArticleActiveModel, Column as SeriesArticleColumn,
+        Entity as SeriesArticleEntity, Model as SeriesArticleModel,
+    };
+    pub use super::slug_history::{
+        ActiveModel as SlugHistoryActiveModel, Column as SlugHistoryColumn,
+        Entity as SlugHistoryEntity, Model as SlugHistoryModel,
+    };
     pub use super::tag::{
         ActiveModel as TagActiveModel, Column as TagColumn, Entity as TagEntity, Model as TagModel,
     };
+    pub use super::upload::{
+        ActiveModel as UploadActiveModel, Column as UploadColumn, Entity as UploadEntity,
+        Model as UploadModel,
+    };
     pub use super::user::{
         ActiveModel as UserActiveModel, Column as UserColumn, Entity as UserEntity,
         Model as UserModel,
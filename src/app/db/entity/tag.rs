@@ -7,14 +7,18 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32, // 标签id
-    pub name: String,                  // 标签名称
-    pub parent_tag_id: Option<i32>,    // 父标签id
+    pub name: String, // 标签名称
+    #[sea_orm(unique)]
+    pub slug: String, // 标签别名
+    pub parent_tag_id: Option<i32>, // 父标签id
     pub thumbnail_url: Option<String>, // 缩略图url
-    pub status_type: i32,              // 标签状态，0.正常、1.隐藏、2.删除
-    pub create_time: DateTime,         // 创建时间
-    pub update_time: DateTime,         // 更新时间
-    pub create_user_id: i32,           // 创建用户id
-    pub update_user_id: i32,           // 更新用户id
+    pub status_type: i32, // 标签状态，0.正常、1.隐藏、2.删除
+    pub create_time: DateTime, // 创建时间
+    pub update_time: DateTime, // 更新时间
+    pub create_user_id: i32, // 创建用户id
+    pub update_user_id: i32, // 更新用户id
+    pub delete_time: Option<DateTime>, // 删除时间
+    pub delete_user_id: Option<i32>, // 删除用户id
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -43,6 +47,14 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     UpdateUser,
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::DeleteUserId",
+        to = "super::user::Column::Id",
+        on_update = "Restrict",
+        on_delete = "SetNull"
+    )]
+    DeleteUser,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,6 +1,6 @@
 use sea_orm::entity::prelude::*;
 
-use super::super::entity::{article, article_tag, tag, user};
+use super::super::entity::{article, article_tag, series, series_article, tag, user};
 
 pub struct CreateUserLink;
 impl Linked for CreateUserLink {
@@ -31,3 +31,15 @@ impl Linked for TagLink {
         ]
     }
 }
+
+pub struct SeriesLink;
+impl Linked for SeriesLink {
+    type FromEntity = article::Entity;
+    type ToEntity = series::Entity;
+    fn link(&self) -> Vec<RelationDef> {
+        vec![
+            series_article::Relation::Article.def().rev(),
+            series_article::Relation::Series.def(),
+        ]
+    }
+}
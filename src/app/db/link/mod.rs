@@ -1,11 +1,15 @@
 pub mod article;
+pub mod series;
 pub mod tag;
 pub mod user;
 
 pub mod prelude {
     pub use super::article::{
-        CreateUserLink as ArticleToCreateUserLink, TagLink as ArticleToTagLink,
-        UpdateUserLink as ArticleToUpdateUserLink,
+        CreateUserLink as ArticleToCreateUserLink, SeriesLink as ArticleToSeriesLink,
+        TagLink as ArticleToTagLink, UpdateUserLink as ArticleToUpdateUserLink,
+    };
+    pub use super::series::{
+        ArticleLink as SeriesToArticleLink, CreateUserLink as SeriesToCreateUserLink,
     };
     pub use super::tag::{
         ArticleLink as TagToArticleLink, ChildTagLink as TagToChildTagLink,
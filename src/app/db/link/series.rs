@@ -0,0 +1,24 @@
+use sea_orm::entity::prelude::*;
+
+use super::super::entity::{article, series, series_article, user};
+
+pub struct CreateUserLink;
+impl Linked for CreateUserLink {
+    type FromEntity = series::Entity;
+    type ToEntity = user::Entity;
+    fn link(&self) -> Vec<RelationDef> {
+        vec![series::Relation::CreateUser.def()]
+    }
+}
+
+pub struct ArticleLink;
+impl Linked for ArticleLink {
+    type FromEntity = series::Entity;
+    type ToEntity = article::Entity;
+    fn link(&self) -> Vec<RelationDef> {
+        vec![
+            series_article::Relation::Series.def().rev(),
+            series_article::Relation::Article.def(),
+        ]
+    }
+}
@@ -17,27 +17,161 @@ pub mod prelude {
     pub use super::middleware::prelude::*;
     pub use super::service::prelude::*;
     pub use super::utils::prelude::*;
-    pub use super::App;
+    pub use super::{App, Command};
 }
 
 // ********************* import ********************* //
-use std::{ops::Deref, sync::Arc};
+use std::{net::SocketAddr, ops::Deref, sync::Arc};
 
 use axum::{Extension, Router};
 
 use prelude::{
-    create_db_conn, init_logging, user_admin_router, user_public_router, AppConfig, AppErrorKind,
-    AppResult, IntoAppResult, JwtTokenUtils, Pbkdf2CryptoUtils, RedisCacheUtils, UserDAO,
-    UserService,
+    article_admin_router, article_public_router, comment_admin_router, comment_public_router,
+    create_db_conn, init_logging, series_public_router, site_router, tag_admin_router,
+    tag_public_router, transfer_admin_router, trash_admin_router, upload_public_router,
+    user_admin_router, user_public_router, AnyStorageBackend, AppConfig, AppError, AppErrorKind,
+    AppResult, ArticleDAO, ArticleReactionDAO, ArticleRevisionDAO, ArticleService, ArticleTagDAO,
+    CommentDAO, CommentService, DataAccess, IntoAppResult, InvertedSearchIndex, JwtTokenUtils,
+    MarkdownRenderUtils, Pbkdf2CryptoUtils, RasterImageUtils, RedisCacheUtils, SeriesArticleDAO,
+    SeriesDAO, SeriesService, SiteService, SlugHistoryDAO, TagDAO, TagService, TransferService,
+    TrashService, TrustedProxies, UploadDAO, UploadService, UserDAO, UserFilterParam, UserService,
+    WrapToAppResult,
 };
 
 // ********************* content ********************* //
+const CFG_FILE_PATH: &str = "config/config_test.toml";
+
+pub const USAGE: &str = "\
+usage:
+  space-backend                          start the server
+  space-backend export <file.zip>        export all articles
+  space-backend import <file.zip> --author <username> [--upsert]
+                                         import articles, <username> is the author of
+                                         files without one";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Serve,
+    Export {
+        path: String,
+    },
+    Import {
+        path: String,
+        author: String,
+        upsert: bool,
+    },
+}
+
+impl Command {
+    // the arguments after the program name
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> AppResult<Self> {
+        let mut args = args.into_iter();
+        let invalid = || AppError::new(USAGE, AppErrorKind::RequestParamInvalid);
+        let command = match args.next().as_deref() {
+            None => Command::Serve,
+            Some("export") => Command::Export {
+                path: args.next().ok_or_else(invalid)?,
+            },
+            Some("import") => {
+                let path = args.next().ok_or_else(invalid)?;
+                let (mut author, mut upsert) = (None, false);
+                while let Some(arg) = args.next() {
+                    match arg.as_str() {
+                        "--author" => author = Some(args.next().ok_or_else(invalid)?),
+                        "--upsert" => upsert = true,
+                        _ => return Err(invalid()),
+                    }
+                }
+                Command::Import {
+                    path,
+                    author: author.ok_or_else(invalid)?,
+                    upsert,
+                }
+            }
+            Some(_) => return Err(invalid()),
+        };
+        match args.next() {
+            Some(_) => Err(invalid()),
+            None => Ok(command),
+        }
+    }
+}
+
 pub struct App;
 
 impl App {
+    pub async fn exec(command: Command) -> AppResult<()> {
+        match command {
+            Command::Serve => Self::run().await,
+            command => Self::transfer(command).await,
+        }
+    }
+
+    // export and import go through the same service as the admin endpoints, running
+    // servers rebuild their search index once they see the new article generation
+    async fn transfer(command: Command) -> AppResult<()> {
+        let cfg = AppConfig::init(CFG_FILE_PATH)?;
+        let cache_utils = Arc::new(RedisCacheUtils::new(&cfg.cache).await?);
+        let render_utils = Arc::new(MarkdownRenderUtils::new(&cfg.render));
+        let token_utils = Arc::new(JwtTokenUtils::new(cache_utils.clone()).await?);
+        let db_conn = Arc::new(create_db_conn(&cfg.db).await?);
+        let transfer_service = TransferService::new(
+            Arc::new(ArticleDAO::new(db_conn.clone())),
+            Arc::new(ArticleRevisionDAO::new(db_conn.clone())),
+            Arc::new(ArticleTagDAO::new(db_conn.clone())),
+            Arc::new(TagDAO::new(db_conn.clone())),
+            Arc::new(UserDAO::new(db_conn)),
+            cache_utils,
+            render_utils,
+            token_utils,
+        );
+        match command {
+            Command::Export { path } => {
+                let data = transfer_service.export_zip().await?;
+                std::fs::write(&path, data).wrap_with(
+                    || format!("Failed to write {}", path),
+                    AppErrorKind::default(),
+                )?;
+                println!("exported to {}", path);
+            }
+            Command::Import {
+                path,
+                author,
+                upsert,
+            } => {
+                let data = std::fs::read(&path).wrap_with(
+                    || format!("Failed to read {}", path),
+                    AppErrorKind::default(),
+                )?;
+                // users in the trash cannot own the imported articles
+                let author = transfer_service
+                    .user_dao
+                    .get(UserFilterParam {
+                        username: Some(author),
+                        status_types: Some(vec![0, 1, 2]),
+                        ..Default::default()
+                    })
+                    .await?;
+                let res_form = transfer_service.import_zip(data, upsert, author.id).await?;
+                println!(
+                    "created: {}, updated: {}, created tags: {}, skipped: {}",
+                    res_form.created,
+                    res_form.updated,
+                    res_form.created_tags,
+                    res_form.skipped.len()
+                );
+                for skip_info in res_form.skipped {
+                    println!("  {}: {}", skip_info.file, skip_info.reason);
+                }
+            }
+            Command::Serve => {}
+        }
+        Ok(())
+    }
+
     pub async fn run() -> AppResult<()> {
         // config
-        let cfg = AppConfig::init("config/config_test.toml")?;
+        let cfg = AppConfig::init(CFG_FILE_PATH)?;
 
         // log
         init_logging(&cfg.log).with_err_kind(AppErrorKind::default())?;
@@ -45,31 +179,158 @@ impl App {
         // utils
         let cache_utils = Arc::new(RedisCacheUtils::new(&cfg.cache).await?);
         let crypto_utils = Arc::new(Pbkdf2CryptoUtils::default());
-        let token_utils = Arc::new(JwtTokenUtils::new(cache_utils).await?);
+        let image_utils = Arc::new(RasterImageUtils::new(&cfg.image)?);
+        let render_utils = Arc::new(MarkdownRenderUtils::new(&cfg.render));
+        let search_index = Arc::new(InvertedSearchIndex::new());
+        let storage_backend = Arc::new(AnyStorageBackend::new(&cfg.storage).await?);
+        let token_utils = Arc::new(JwtTokenUtils::new(cache_utils.clone()).await?);
 
         // db
         let db_conn = Arc::new(create_db_conn(&cfg.db).await?);
 
         // dao
-        let user_dao = Arc::new(UserDAO::new(db_conn));
+        let user_dao = Arc::new(UserDAO::new(db_conn.clone()));
+        let article_dao = Arc::new(ArticleDAO::new(db_conn.clone()));
+        let article_reaction_dao = Arc::new(ArticleReactionDAO::new(db_conn.clone()));
+        let article_revision_dao = Arc::new(ArticleRevisionDAO::new(db_conn.clone()));
+        let article_tag_dao = Arc::new(ArticleTagDAO::new(db_conn.clone()));
+        let comment_dao = Arc::new(CommentDAO::new(db_conn.clone()));
+        let series_dao = Arc::new(SeriesDAO::new(db_conn.clone()));
+        let series_article_dao = Arc::new(SeriesArticleDAO::new(db_conn.clone()));
+        let slug_history_dao = Arc::new(SlugHistoryDAO::new(db_conn.clone()));
+        let tag_dao = Arc::new(TagDAO::new(db_conn.clone()));
+        let upload_dao = Arc::new(UploadDAO::new(db_conn));
 
         // service
-        let user_service = Arc::new(UserService::new(user_dao, crypto_utils, token_utils));
+        let user_service = Arc::new(UserService::new(
+            user_dao.clone(),
+            crypto_utils,
+            cache_utils.clone(),
+            token_utils.clone(),
+            &cfg.service,
+            &cfg.storage,
+            &cfg.image,
+        ));
+        let article_service = Arc::new(ArticleService::new(
+            article_dao.clone(),
+            article_revision_dao.clone(),
+            article_tag_dao.clone(),
+            article_reaction_dao,
+            series_article_dao.clone(),
+            tag_dao.clone(),
+            slug_history_dao.clone(),
+            cache_utils.clone(),
+            render_utils.clone(),
+            search_index.clone(),
+            token_utils.clone(),
+            &cfg.article,
+            &cfg.render,
+            &cfg.search,
+        ));
+        article_service.refresh_search_index().await?;
+        let scheduler = article_service.clone();
+        tokio::spawn(async move { scheduler.run_scheduler().await });
+        let view_flusher = article_service.clone();
+        tokio::spawn(async move { view_flusher.run_view_flusher().await });
+        let site_service = Arc::new(SiteService::new(
+            article_dao.clone(),
+            article_tag_dao.clone(),
+            tag_dao.clone(),
+            user_dao.clone(),
+            cache_utils.clone(),
+            render_utils.clone(),
+            &cfg.service,
+            &cfg.site,
+        ));
+        let transfer_service = Arc::new(TransferService::new(
+            article_dao.clone(),
+            article_revision_dao,
+            article_tag_dao,
+            tag_dao.clone(),
+            user_dao.clone(),
+            cache_utils.clone(),
+            render_utils,
+            token_utils.clone(),
+        ));
+        let trash_service = Arc::new(TrashService::new(
+            article_dao.clone(),
+            tag_dao.clone(),
+            user_dao.clone(),
+            slug_history_dao.clone(),
+            cache_utils.clone(),
+            token_utils.clone(),
+            &cfg.trash,
+        ));
+        let purger = trash_service.clone();
+        tokio::spawn(async move { purger.run_purger().await });
+        let series_service = Arc::new(SeriesService::new(
+            series_dao,
+            series_article_dao,
+            article_dao.clone(),
+            token_utils.clone(),
+        ));
+        let comment_service = Arc::new(CommentService::new(
+            comment_dao,
+            article_dao,
+            user_dao,
+            token_utils.clone(),
+            &cfg.comment,
+        ));
+        let tag_service = Arc::new(TagService::new(
+            tag_dao,
+            slug_history_dao,
+            cache_utils,
+            token_utils.clone(),
+            &cfg.tag,
+        ));
+        let upload_service = Arc::new(UploadService::new(
+            upload_dao,
+            storage_backend,
+            image_utils,
+            token_utils,
+            &cfg.service,
+            &cfg.storage,
+            &cfg.upload,
+        ));
 
         // router
-        let app = Router::new().nest(
-            "/api/v1",
-            Router::new()
-                .nest(
-                    "/public/",
-                    Router::new().nest("/user", user_public_router(user_service.deref())),
-                )
-                .nest(
-                    "/admin",
-                    Router::new().nest("/user", user_admin_router(user_service.deref())),
-                )
-                .layer(Extension(user_service)),
-        );
+        let app = Router::new()
+            .merge(site_router(site_service.deref()).layer(Extension(site_service)))
+            .nest(
+                "/api/v1",
+                Router::new()
+                    .nest(
+                        "/public/",
+                        Router::new()
+                            .nest("/user", user_public_router(user_service.deref()))
+                            .nest("/article", article_public_router(article_service.deref()))
+                            .nest("/comment", comment_public_router(comment_service.deref()))
+                            .nest("/series", series_public_router(series_service.deref()))
+                            .nest("/tag", tag_public_router(tag_service.deref()))
+                            .nest("/upload", upload_public_router(upload_service.deref())),
+                    )
+                    .nest(
+                        "/admin",
+                        Router::new()
+                            .nest("/user", user_admin_router(user_service.deref()))
+                            .nest("/article", article_admin_router(article_service.deref()))
+                            .nest("/comment", comment_admin_router(comment_service.deref()))
+                            .nest("/tag", tag_admin_router(tag_service.deref()))
+                            .nest("/transfer", transfer_admin_router(transfer_service.deref()))
+                            .nest("/trash", trash_admin_router(trash_service.deref())),
+                    )
+                    .layer(Extension(user_service))
+                    .layer(Extension(article_service))
+                    .layer(Extension(comment_service))
+                    .layer(Extension(series_service))
+                    .layer(Extension(tag_service))
+                    .layer(Extension(transfer_service))
+                    .layer(Extension(trash_service))
+                    .layer(Extension(upload_service)),
+            )
+            .layer(Extension(TrustedProxies(Arc::new(
+                cfg.service.trusted_proxies.clone(),
+            ))));
 
         // app server
         let addr = format!("{}:{}", cfg.service.host, cfg.service.port);
@@ -77,9 +338,44 @@ impl App {
         let listener = tokio::net::TcpListener::bind(&addr)
             .await
             .with_err_kind(AppErrorKind::default())?;
-        axum::serve(listener, app)
-            .await
-            .with_err_kind(AppErrorKind::default())?;
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .with_err_kind(AppErrorKind::default())?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(Command::parse(args("")).unwrap(), Command::Serve);
+        assert_eq!(
+            Command::parse(args("export out.zip")).unwrap(),
+            Command::Export {
+                path: "out.zip".to_string()
+            }
+        );
+        assert_eq!(
+            Command::parse(args("import in.zip --upsert --author admin")).unwrap(),
+            Command::Import {
+                path: "in.zip".to_string(),
+                author: "admin".to_string(),
+                upsert: true,
+            }
+        );
+        assert!(Command::parse(args("import in.zip")).is_err());
+        assert!(Command::parse(args("export")).is_err());
+        assert!(Command::parse(args("export a.zip b.zip")).is_err());
+        assert!(Command::parse(args("serve")).is_err());
+    }
+}
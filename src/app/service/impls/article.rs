@@ -0,0 +1,1781 @@
+// ********************* import ********************* //
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{Local, Months, NaiveDate};
+use ring::digest::{digest, SHA256};
+use sea_orm::prelude::DateTime;
+use similar::{ChangeTag, TextDiff};
+use tokio::sync::Mutex as AsyncMutex;
+
+use super::{
+    super::{
+        traits::article::ArticleServiceTrait,
+        types::{article::prelude::*, series::prelude::SeriesNavInfo},
+    },
+    series::series_nav,
+};
+use crate::app::{
+    common::prelude::*,
+    dao::{
+        prelude::{
+            ArticleDataAccess, ArticleReactionDataAccess, ArticleRevisionDataAccess,
+            ArticleTagDataAccess, OrderParam, PaginateParam, SeriesArticleDataAccess,
+            SlugHistoryDataAccess, TagDataAccess,
+        },
+        types::{
+            article::prelude::*, article_reaction::prelude::*, article_revision::prelude::*,
+            article_tag::prelude::*, series_article::prelude::*, slug_history::prelude::*,
+            tag::prelude::*,
+        },
+    },
+    utils::prelude::{
+        slugify, unique_slug, CacheUtilsTrait, Claims, Page, RenderConfig, RenderUtilsTrait,
+        SearchConfig, SearchDoc, SearchIndex, TokenUtilsTrait,
+    },
+};
+
+// ********************* content ********************* //
+impl From<ArticleDataModel> for ArticleInfo {
+    fn from(model: ArticleDataModel) -> Self {
+        Self {
+            id: model.id,
+            title: model.title,
+            slug: model.slug,
+            description: model.description,
+            content: model.content,
+            is_top: model.is_top,
+            pin_priority: model.pin_priority,
+            status_type: model.status_type,
+            publish_at: model.publish_at,
+            unpublish_at: model.unpublish_at,
+            publish_time: model.publish_time,
+            view_count: model.view_count,
+            create_time: model.create_time,
+            update_time: model.update_time,
+            create_user_id: model.create_user_id,
+            update_user_id: model.update_user_id,
+        }
+    }
+}
+
+impl From<ArticleRevisionDataModel> for ArticleRevisionInfo {
+    fn from(model: ArticleRevisionDataModel) -> Self {
+        Self {
+            id: model.id,
+            article_id: model.article_id,
+            title: model.title,
+            description: model.description,
+            create_time: model.create_time,
+            update_user_id: model.update_user_id,
+        }
+    }
+}
+
+// t_space_slug_history.resource_type
+const SLUG_RESOURCE_TYPE: i32 = SLUG_RESOURCE_ARTICLE;
+// bumped on every article change, documents built from articles are cached under it
+pub(super) const ARTICLE_GENERATION_KEY: &str = "article:generation";
+const SNIPPET_CHARS: usize = 120;
+const SNIPPET_LEAD_CHARS: usize = 30;
+
+fn escape_html(c: char, out: &mut String) {
+    match c {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        '\'' => out.push_str("&#39;"),
+        c => out.push(c),
+    }
+}
+
+fn lowercase_chars(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+// length of the longest term matching at `pos`, case-insensitive
+fn match_len(lower_chars: &[char], pos: usize, terms: &[Vec<char>]) -> Option<usize> {
+    terms
+        .iter()
+        .filter(|term| lower_chars[pos..].starts_with(term))
+        .map(|term| term.len())
+        .max()
+}
+
+fn highlight(text: &str, terms: &[Vec<char>]) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let lower_chars = lowercase_chars(text);
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0;
+    while pos < chars.len() {
+        match match_len(&lower_chars, pos, terms) {
+            Some(len) => {
+                out.push_str("<mark>");
+                chars[pos..pos + len]
+                    .iter()
+                    .for_each(|c| escape_html(*c, &mut out));
+                out.push_str("</mark>");
+                pos += len;
+            }
+            None => {
+                escape_html(chars[pos], &mut out);
+                pos += 1;
+            }
+        }
+    }
+    out
+}
+
+// a window of the content around the first match, highlighted
+fn snippet(content: &str, terms: &[Vec<char>]) -> String {
+    let content = content.split_whitespace().collect::<Vec<_>>().join(" ");
+    let chars: Vec<char> = content.chars().collect();
+    let lower_chars = lowercase_chars(&content);
+    let first_match = (0..chars.len())
+        .find(|pos| match_len(&lower_chars, *pos, terms).is_some())
+        .unwrap_or(0);
+    let start = first_match.saturating_sub(SNIPPET_LEAD_CHARS);
+    let end = chars.len().min(start + SNIPPET_CHARS);
+    let window: String = chars[start..end].iter().collect();
+    format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        highlight(&window, terms),
+        if end < chars.len() { "…" } else { "" }
+    )
+}
+
+// line numbers start from 1, a line that exists on one side only has no number on the other
+fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .map(|change| DiffLine {
+            tag: match change.tag() {
+                ChangeTag::Equal => "equal",
+                ChangeTag::Delete => "delete",
+                ChangeTag::Insert => "insert",
+            }
+            .to_string(),
+            old_line_num: change.old_index().map(|i| i + 1),
+            new_line_num: change.new_index().map(|i| i + 1),
+            content: change.to_string_lossy().trim_end_matches('\n').to_string(),
+        })
+        .collect()
+}
+
+// return the root tag followed by all of its descendants among `models`
+fn descendant_tag_ids(root_tag_id: i32, models: Vec<TagDataModel>) -> Vec<i32> {
+    let mut children_map: HashMap<i32, Vec<i32>> = HashMap::new();
+    for model in models {
+        if let Some(parent_tag_id) = model.parent_tag_id {
+            children_map
+                .entry(parent_tag_id)
+                .or_default()
+                .push(model.id);
+        }
+    }
+    let mut visited = HashSet::from([root_tag_id]);
+    let mut tag_ids = vec![root_tag_id];
+    let mut i = 0;
+    while i < tag_ids.len() {
+        for child_id in children_map.remove(&tag_ids[i]).unwrap_or_default() {
+            if visited.insert(child_id) {
+                tag_ids.push(child_id);
+            }
+        }
+        i += 1;
+    }
+    tag_ids
+}
+
+// articles pinned within the tag come first, then globally pinned ones, then the requested order
+fn pinned_first(order: OrderParam<ArticleAttr>, tag_id: Option<i32>) -> OrderParam<ArticleAttr> {
+    let mut then = vec![
+        (ArticleAttr::IsTop, false),
+        (ArticleAttr::PinPriority, false),
+    ];
+    then.push((order.by, order.ascending));
+    then.extend(order.then);
+    match tag_id {
+        Some(tag_id) => OrderParam {
+            by: ArticleAttr::TagPinPriority(tag_id),
+            ascending: false,
+            then,
+        },
+        None => OrderParam {
+            by: then.remove(0).0,
+            ascending: false,
+            then,
+        },
+    }
+}
+
+// [start, end) of a calendar month
+fn month_range(year: i32, month: u32) -> Option<(DateTime, DateTime)> {
+    let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let end = start.checked_add_months(Months::new(1))?;
+    Some((start.and_hms_opt(0, 0, 0)?, end.and_hms_opt(0, 0, 0)?))
+}
+
+// views not yet flushed to the database
+fn pending_views_key(id: i32) -> String {
+    format!("article:{}:views", id)
+}
+
+fn reactions_cache_key(id: i32) -> String {
+    format!("article:{}:reactions", id)
+}
+
+// keyed by the content itself, an edit makes the old entry unreachable however soon it
+// follows, and the old entry then expires on its own
+fn render_cache_key(id: i32, content: &str) -> String {
+    let content_hash: String = digest(&SHA256, content.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("article:{}:render:{}", id, content_hash)
+}
+
+pub struct ArticleService<D, V, R, E, P, G, H, C, M, S, T>
+where
+    D: ArticleDataAccess + Sync + Send,
+    V: ArticleRevisionDataAccess + Sync + Send,
+    R: ArticleTagDataAccess + Sync + Send,
+    E: ArticleReactionDataAccess + Sync + Send,
+    P: SeriesArticleDataAccess + Sync + Send,
+    G: TagDataAccess + Sync + Send,
+    H: SlugHistoryDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    M: RenderUtilsTrait + Sync + Send,
+    S: SearchIndex + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    pub article_dao: Arc<D>,
+    pub article_revision_dao: Arc<V>,
+    pub article_tag_dao: Arc<R>,
+    pub article_reaction_dao: Arc<E>,
+    pub series_article_dao: Arc<P>,
+    pub tag_dao: Arc<G>,
+    pub slug_history_dao: Arc<H>,
+    pub cache_utils: Arc<C>,
+    pub render_utils: Arc<M>,
+    pub search_index: Arc<S>,
+    pub token_utils: Arc<T>,
+    pub render_cache_expire_sec: u64,
+    pub use_search_index: bool,
+    pub suggest_limit: usize,
+    pub schedule_interval_sec: u64,
+    pub view_dedup_sec: u64,
+    pub view_flush_interval_sec: u64,
+    pub reaction_kinds: Vec<String>,
+    pub reaction_cache_expire_sec: u64,
+    // articles viewed through this instance since the last flush
+    pub pending_view_ids: Mutex<HashSet<i32>>,
+    // the article generation the search index is in sync with, None before the first build
+    pub search_index_generation: AsyncMutex<Option<i64>>,
+}
+
+impl<D, V, R, E, P, G, H, C, M, S, T> ArticleService<D, V, R, E, P, G, H, C, M, S, T>
+where
+    D: ArticleDataAccess + Sync + Send,
+    V: ArticleRevisionDataAccess + Sync + Send,
+    R: ArticleTagDataAccess + Sync + Send,
+    E: ArticleReactionDataAccess + Sync + Send,
+    P: SeriesArticleDataAccess + Sync + Send,
+    G: TagDataAccess + Sync + Send,
+    H: SlugHistoryDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    M: RenderUtilsTrait + Sync + Send,
+    S: SearchIndex + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        article_dao: Arc<D>,
+        article_revision_dao: Arc<V>,
+        article_tag_dao: Arc<R>,
+        article_reaction_dao: Arc<E>,
+        series_article_dao: Arc<P>,
+        tag_dao: Arc<G>,
+        slug_history_dao: Arc<H>,
+        cache_utils: Arc<C>,
+        render_utils: Arc<M>,
+        search_index: Arc<S>,
+        token_utils: Arc<T>,
+        article_cfg: &ArticleConfig,
+        render_cfg: &RenderConfig,
+        search_cfg: &SearchConfig,
+    ) -> Self {
+        Self {
+            article_dao,
+            article_revision_dao,
+            article_tag_dao,
+            article_reaction_dao,
+            series_article_dao,
+            tag_dao,
+            slug_history_dao,
+            cache_utils,
+            render_utils,
+            search_index,
+            token_utils,
+            render_cache_expire_sec: render_cfg.cache_expire_sec,
+            use_search_index: search_cfg.engine == "index",
+            suggest_limit: search_cfg.suggest_limit,
+            schedule_interval_sec: article_cfg.schedule_interval_sec.max(1),
+            view_dedup_sec: article_cfg.view_dedup_sec,
+            view_flush_interval_sec: article_cfg.view_flush_interval_sec.max(1),
+            reaction_kinds: article_cfg.reaction_kinds.clone(),
+            reaction_cache_expire_sec: article_cfg.reaction_cache_expire_sec,
+            pending_view_ids: Mutex::new(HashSet::new()),
+            search_index_generation: AsyncMutex::new(None),
+        }
+    }
+
+    // instances tick independently, the lock on the current time slot lets only one
+    // of them run the task per slot
+    async fn acquire_tick_lock(&self, task: &str, interval_sec: u64) -> AppResult<bool> {
+        let slot = Local::now().timestamp() as u64 / interval_sec;
+        let lock_key = format!("article:{}:lock:{}", task, slot);
+        self.cache_utils
+            .set_nx(&lock_key, true, Some(interval_sec * 2))
+            .await
+    }
+
+    // each flip happens exactly once however many instances are running
+    pub async fn run_scheduler(&self) {
+        let mut interval = tokio::time::interval(Duration::from_secs(self.schedule_interval_sec));
+        loop {
+            interval.tick().await;
+            let res = match self
+                .acquire_tick_lock("schedule", self.schedule_interval_sec)
+                .await
+            {
+                Ok(true) => self.apply_schedule().await.map(|_| ()),
+                Ok(false) => Ok(()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = res {
+                tracing::error!("Failed to apply article schedule: {:?}", e);
+            }
+        }
+    }
+
+    pub async fn run_view_flusher(&self) {
+        let mut interval = tokio::time::interval(Duration::from_secs(self.view_flush_interval_sec));
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.flush_views().await {
+                tracing::error!("Failed to flush article views: {:?}", e);
+            }
+        }
+    }
+
+    // move the buffered counts of articles viewed through this instance into the
+    // database, counts left by a previous process are picked up on their next view
+    pub async fn flush_views(&self) -> AppResult<usize> {
+        let ids = std::mem::take(&mut *self.pending_view_ids.lock().unwrap());
+        let mut flush_num = 0;
+        for (i, &id) in ids.iter().enumerate() {
+            if let Err(e) = self.flush_article_views(id).await {
+                // retry the rest on the next tick
+                self.pending_view_ids
+                    .lock()
+                    .unwrap()
+                    .extend(ids.iter().skip(i));
+                return Err(e);
+            }
+            flush_num += 1;
+        }
+        Ok(flush_num)
+    }
+
+    async fn flush_article_views(&self, id: i32) -> AppResult<()> {
+        // other instances may hold views of the same article
+        let lock_key = format!("article:{}:views:lock", id);
+        if !self
+            .cache_utils
+            .set_nx(&lock_key, true, Some(self.view_flush_interval_sec))
+            .await?
+        {
+            self.pending_view_ids.lock().unwrap().insert(id);
+            return Ok(());
+        }
+        let key = pending_views_key(id);
+        let views: i64 = self.cache_utils.get(&key).await?.unwrap_or(0);
+        if views > 0 {
+            // views arriving meanwhile stay in the buffer for the next flush
+            self.cache_utils.incr(&key, -views).await?;
+            if let Err(e) = self.article_dao.add_view_count(id, views).await {
+                self.cache_utils.incr(&key, views).await?;
+                return Err(e);
+            }
+        }
+        self.cache_utils.del(&lock_key).await
+    }
+
+    // count the view unless the visitor viewed the article recently, return the
+    // number of views still buffered
+    async fn record_view(&self, id: i32, visitor: &str) -> AppResult<i64> {
+        let visitor_key = format!("article:{}:viewer:{}", id, visitor);
+        let key = pending_views_key(id);
+        let is_new = self
+            .cache_utils
+            .set_nx(&visitor_key, true, Some(self.view_dedup_sec))
+            .await?;
+        if !is_new {
+            return Ok(self.cache_utils.get(&key).await?.unwrap_or(0));
+        }
+        let views = self.cache_utils.incr(&key, 1).await?;
+        self.pending_view_ids.lock().unwrap().insert(id);
+        Ok(views)
+    }
+
+    async fn get_live(&self, id: i32) -> AppResult<ArticleDataModel> {
+        self.article_dao
+            .get(ArticleFilterParam {
+                id: Some(id),
+                status_type: Some(1),
+                live_at: Some(Local::now().naive_local()),
+                ..Default::default()
+            })
+            .await
+    }
+
+    fn verify_reaction_kind(&self, kind: &str) -> AppResult<()> {
+        match self.reaction_kinds.iter().any(|k| k == kind) {
+            true => Ok(()),
+            false => Err(AppError::new(
+                format!("Unsupported reaction kind: {}", kind),
+                AppErrorKind::RequestParamInvalid,
+            )),
+        }
+    }
+
+    // counts of every configured kind in display order, including the empty ones
+    async fn reactions_res(
+        &self,
+        id: i32,
+        user_id: Option<i32>,
+    ) -> AppResult<ArticleReactionsResForm> {
+        let cache_key = reactions_cache_key(id);
+        let counts: HashMap<String, i64> = match self.cache_utils.get(&cache_key).await? {
+            Some(counts) => counts,
+            None => {
+                let counts: HashMap<String, i64> = self
+                    .article_reaction_dao
+                    .count_by_kind(id)
+                    .await?
+                    .into_iter()
+                    .collect();
+                self.cache_utils
+                    .set(&cache_key, &counts, Some(self.reaction_cache_expire_sec))
+                    .await?;
+                counts
+            }
+        };
+        let mine: HashSet<String> = match user_id {
+            Some(user_id) => self
+                .article_reaction_dao
+                .list_all(ArticleReactionFilterParam {
+                    article_id: Some(id),
+                    user_id: Some(user_id),
+                    ..Default::default()
+                })
+                .await?
+                .into_iter()
+                .map(|model| model.kind)
+                .collect(),
+            None => HashSet::new(),
+        };
+        Ok(ArticleReactionsResForm {
+            counts: self
+                .reaction_kinds
+                .iter()
+                .map(|kind| ArticleReactionCount {
+                    kind: kind.clone(),
+                    count: counts.get(kind).copied().unwrap_or(0),
+                })
+                .collect(),
+            mine: self
+                .reaction_kinds
+                .iter()
+                .filter(|kind| mine.contains(*kind))
+                .cloned()
+                .collect(),
+        })
+    }
+
+    async fn find_public(
+        &self,
+        article_model: ArticleDataModel,
+        visitor: &str,
+    ) -> AppResult<ArticleFindResForm> {
+        let pending_views = self.record_view(article_model.id, visitor).await?;
+        let mut res_form = self.find_res(article_model, &[0]).await?;
+        res_form.article_info.view_count += pending_views.max(0);
+        Ok(res_form)
+    }
+
+    // publish drafts whose publish_at has come and hide published articles whose
+    // unpublish_at has passed, each schedule is cleared once applied
+    pub async fn apply_schedule(&self) -> AppResult<usize> {
+        let now = Local::now().naive_local();
+        let due_publish_models = self
+            .article_dao
+            .list_all(
+                ArticleFilterParam {
+                    status_types: Some(vec![0, 1]),
+                    publish_at_end: Some(now),
+                    ..Default::default()
+                },
+                OrderParam::<ArticleAttr>::default(),
+            )
+            .await?;
+        let mut flip_num = 0;
+        for model in due_publish_models {
+            self.update_inner(
+                model.id,
+                ArticleUpdateParam {
+                    status_type: Some(1),
+                    publish_at: Some(None),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            flip_num += 1;
+        }
+        let due_unpublish_models = self
+            .article_dao
+            .list_all(
+                ArticleFilterParam {
+                    status_type: Some(1),
+                    unpublish_at_end: Some(now),
+                    ..Default::default()
+                },
+                OrderParam::<ArticleAttr>::default(),
+            )
+            .await?;
+        for model in due_unpublish_models {
+            self.update_inner(
+                model.id,
+                ArticleUpdateParam {
+                    status_type: Some(2),
+                    unpublish_at: Some(None),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            flip_num += 1;
+        }
+        Ok(flip_num)
+    }
+
+    // the index lives in each instance, so it is rebuilt from the database whenever any
+    // instance, the trash, tag edits or an import moved the article generation since
+    pub async fn refresh_search_index(&self) -> AppResult<()> {
+        if !self.use_search_index {
+            return Ok(());
+        }
+        let mut index_generation = self.search_index_generation.lock().await;
+        // read before loading, a change made meanwhile leads to another rebuild
+        let generation = self
+            .cache_utils
+            .get(ARTICLE_GENERATION_KEY)
+            .await?
+            .unwrap_or(0);
+        if *index_generation == Some(generation) {
+            return Ok(());
+        }
+        let tag_names: HashMap<i32, String> = self
+            .tag_dao
+            .list_all(
+                TagFilterParam {
+                    status_type: Some(0),
+                    ..Default::default()
+                },
+                OrderParam::<TagAttr>::default(),
+            )
+            .await?
+            .into_iter()
+            .map(|tag| (tag.id, tag.name))
+            .collect();
+        let mut article_tags: HashMap<i32, Vec<String>> = HashMap::new();
+        for relation in self
+            .article_tag_dao
+            .list_all(ArticleTagFilterParam::default())
+            .await?
+        {
+            if let Some(name) = tag_names.get(&relation.tag_id) {
+                article_tags
+                    .entry(relation.article_id)
+                    .or_default()
+                    .push(name.clone());
+            }
+        }
+        let article_models = self
+            .article_dao
+            .list_all(
+                ArticleFilterParam {
+                    status_type: Some(1),
+                    live_at: Some(Local::now().naive_local()),
+                    ..Default::default()
+                },
+                OrderParam::<ArticleAttr>::default(),
+            )
+            .await?;
+        let docs = article_models
+            .into_iter()
+            .map(|model| SearchDoc {
+                tags: article_tags.remove(&model.id).unwrap_or_default(),
+                id: model.id,
+                title: model.title,
+                content: model.content,
+            })
+            .collect();
+        self.search_index.replace_all(docs);
+        *index_generation = Some(generation);
+        Ok(())
+    }
+
+    // only published articles are searchable, tag changes are picked up here too
+    fn sync_search_index(&self, res_form: &ArticleFindResForm) {
+        if !self.use_search_index {
+            return;
+        }
+        let article_info = &res_form.article_info;
+        let now = Local::now().naive_local();
+        let is_live = article_info.publish_at.is_none_or(|t| t <= now)
+            && article_info.unpublish_at.is_none_or(|t| t > now);
+        match article_info.status_type {
+            1 if is_live => self.search_index.upsert(SearchDoc {
+                id: article_info.id,
+                title: article_info.title.clone(),
+                content: article_info.content.clone(),
+                tags: res_form
+                    .tags
+                    .iter()
+                    .filter(|tag| tag.status_type == 0)
+                    .map(|tag| tag.name.clone())
+                    .collect(),
+            }),
+            _ => self.search_index.remove(article_info.id),
+        }
+    }
+
+    // held across both steps, so a concurrent rebuild can not drop the change
+    async fn after_change(&self, res_form: &ArticleFindResForm) -> AppResult<()> {
+        let mut index_generation = self.search_index_generation.lock().await;
+        self.sync_search_index(res_form);
+        let generation = self.cache_utils.incr(ARTICLE_GENERATION_KEY, 1).await?;
+        // no rebuild needed when this change is the only one since the index was in sync
+        if *index_generation == Some(generation - 1) {
+            *index_generation = Some(generation);
+        }
+        Ok(())
+    }
+
+    // resolve the tag filter of a search, `None` means no tag filter
+    async fn resolve_tag_ids(
+        &self,
+        tag_id: Option<i32>,
+        include_descendants: bool,
+        tag_status_types: Vec<i32>,
+    ) -> AppResult<Option<Vec<i32>>> {
+        let Some(tag_id) = tag_id else {
+            return Ok(None);
+        };
+        self.tag_dao
+            .get(TagFilterParam {
+                id: Some(tag_id),
+                status_types: Some(tag_status_types.clone()),
+                ..Default::default()
+            })
+            .await?;
+        if !include_descendants {
+            return Ok(Some(vec![tag_id]));
+        }
+        let tag_models = self
+            .tag_dao
+            .list_all(
+                TagFilterParam {
+                    status_types: Some(tag_status_types),
+                    ..Default::default()
+                },
+                OrderParam::<TagAttr>::default(),
+            )
+            .await?;
+        Ok(Some(descendant_tag_ids(tag_id, tag_models)))
+    }
+
+    // public lists put pinned articles in front of it, the lists authors and admins manage
+    // their articles with keep the plain order so that pins do not hide recent drafts
+    fn latest_first() -> OrderParam<ArticleAttr> {
+        OrderParam {
+            by: ArticleAttr::CreateTime,
+            ascending: false,
+            ..Default::default()
+        }
+    }
+
+    async fn search_inner(
+        &self,
+        filter: ArticleFilterParam,
+        tag_ids: Option<Vec<i32>>,
+        order: OrderParam<ArticleAttr>,
+        paginate: PaginateParam,
+    ) -> AppResult<ArticleSearchResForm> {
+        let (record_total, models) = match tag_ids {
+            Some(tag_ids) => (
+                self.article_tag_dao
+                    .count_articles(tag_ids.clone(), filter.clone())
+                    .await?,
+                self.article_tag_dao
+                    .list_articles(tag_ids, filter, order, paginate.clone())
+                    .await?,
+            ),
+            None => (
+                self.article_dao.count(filter.clone()).await?,
+                self.article_dao
+                    .list(filter, order, paginate.clone())
+                    .await?,
+            ),
+        };
+        let model_infos = models.into_iter().map(|model| model.into()).collect();
+        let page = Page::new(
+            paginate.page_num,
+            paginate.page_size,
+            record_total,
+            model_infos,
+        )
+        .wrap(
+            "Invalid pagination parameters",
+            AppErrorKind::RequestParamInvalid,
+        )?;
+        Ok(page)
+    }
+
+    // only the author can modify an article, and deleted articles are read-only
+    async fn verify_owner(&self, id: i32, token: &str) -> AppResult<(Claims, ArticleDataModel)> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        let article_model = self
+            .article_dao
+            .get(ArticleFilterParam {
+                id: Some(id),
+                status_types: Some(vec![0, 1, 2]),
+                ..Default::default()
+            })
+            .await?;
+        if article_model.create_user_id != claims.user_id {
+            return Err(AppError::new(
+                "You can change only your own articles",
+                AppErrorKind::PermissionDenied,
+            ));
+        }
+        Ok((claims, article_model))
+    }
+
+    // tag ids to be attached must refer to tags that are not deleted
+    async fn verify_tags(&self, tag_ids: &[i32]) -> AppResult<()> {
+        let unique_ids: HashSet<i32> = tag_ids.iter().copied().collect();
+        if unique_ids.is_empty() {
+            return Ok(());
+        }
+        let tag_num = self
+            .tag_dao
+            .count(TagFilterParam {
+                ids: Some(unique_ids.iter().copied().collect()),
+                status_types: Some(vec![0, 1]),
+                ..Default::default()
+            })
+            .await?;
+        if tag_num != unique_ids.len() as u64 {
+            return Err(AppError::new(
+                format!("Some of the tags are not found, tag_ids: {:?}", tag_ids),
+                AppErrorKind::ResourceNotFound,
+            ));
+        }
+        Ok(())
+    }
+
+    async fn find_res(
+        &self,
+        article_model: ArticleDataModel,
+        tag_status_types: &[i32],
+    ) -> AppResult<ArticleFindResForm> {
+        let tags = self
+            .article_tag_dao
+            .list_tags(article_model.id)
+            .await?
+            .into_iter()
+            .filter(|tag| tag_status_types.contains(&tag.status_type))
+            .map(|tag| tag.into())
+            .collect();
+        let series = self.series_nav(article_model.id).await?;
+        Ok(ArticleFindResForm {
+            article_info: article_model.into(),
+            tags,
+            series,
+        })
+    }
+
+    async fn series_nav(&self, id: i32) -> AppResult<Option<SeriesNavInfo>> {
+        let Some(series_model) = self.series_article_dao.find_series(id).await? else {
+            return Ok(None);
+        };
+        let mut part_models = self
+            .series_article_dao
+            .list_all(SeriesArticleFilterParam {
+                series_id: Some(series_model.id),
+                ..Default::default()
+            })
+            .await?;
+        part_models.sort_by_key(|model| model.position);
+        let part_ids: Vec<i32> = part_models.iter().map(|model| model.article_id).collect();
+        let published_models = self
+            .series_article_dao
+            .list_articles(
+                series_model.id,
+                ArticleFilterParam {
+                    status_type: Some(1),
+                    live_at: Some(Local::now().naive_local()),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(Some(series_nav(
+            series_model,
+            id,
+            &part_ids,
+            published_models,
+        )))
+    }
+
+    // slugs already used by other articles, now or in the past, are not available
+    async fn resolve_slug(&self, base: String, id: Option<i32>) -> AppResult<String> {
+        let mut taken: HashSet<String> = self
+            .article_dao
+            .list_all(
+                ArticleFilterParam {
+                    slug_prefix: Some(base.clone()),
+                    ..Default::default()
+                },
+                OrderParam::<ArticleAttr>::default(),
+            )
+            .await?
+            .into_iter()
+            .filter(|model| Some(model.id) != id)
+            .map(|model| model.slug)
+            .collect();
+        taken.extend(
+            self.slug_history_dao
+                .list_all(
+                    SlugHistoryFilterParam {
+                        resource_type: Some(SLUG_RESOURCE_TYPE),
+                        slug_prefix: Some(base.clone()),
+                        ..Default::default()
+                    },
+                    OrderParam::<SlugHistoryAttr>::default(),
+                )
+                .await?
+                .into_iter()
+                .filter(|history| Some(history.resource_id) != id)
+                .map(|history| history.slug),
+        );
+        Ok(unique_slug(&base, &taken))
+    }
+
+    async fn update_inner(
+        &self,
+        id: i32,
+        update_param: ArticleUpdateParam,
+    ) -> AppResult<ArticleFindResForm> {
+        // every update goes through the revision dao, which snapshots content changes
+        let article_model = self
+            .article_revision_dao
+            .update_article(id, update_param)
+            .await?;
+        let res_form = self.find_res(article_model, &[0, 1]).await?;
+        self.after_change(&res_form).await?;
+        Ok(res_form)
+    }
+
+    async fn delete_inner(&self, id: i32, delete_user_id: i32) -> AppResult<ArticleFindResForm> {
+        let filter = ArticleFilterParam {
+            id: Some(id),
+            ..Default::default()
+        };
+        self.article_dao
+            .soft_delete(filter.clone(), delete_user_id)
+            .await?;
+        let article_model = self.article_dao.get(filter).await?;
+        let res_form = self.find_res(article_model, &[0, 1]).await?;
+        self.after_change(&res_form).await?;
+        Ok(res_form)
+    }
+}
+
+#[async_trait]
+impl<D, V, R, E, P, G, H, C, M, S, T> ArticleServiceTrait
+    for ArticleService<D, V, R, E, P, G, H, C, M, S, T>
+where
+    D: ArticleDataAccess + Sync + Send,
+    V: ArticleRevisionDataAccess + Sync + Send,
+    R: ArticleTagDataAccess + Sync + Send,
+    E: ArticleReactionDataAccess + Sync + Send,
+    P: SeriesArticleDataAccess + Sync + Send,
+    G: TagDataAccess + Sync + Send,
+    H: SlugHistoryDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    M: RenderUtilsTrait + Sync + Send,
+    S: SearchIndex + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    async fn search(&self, req_form: ArticleSearchReqForm) -> AppResult<ArticleSearchResForm> {
+        let filter = ArticleFilterParam {
+            title_search: req_form.title_search,
+            create_user_id: req_form.create_user_id,
+            status_type: Some(1),
+            live_at: Some(Local::now().naive_local()),
+            ..Default::default()
+        };
+        let tag_ids = self
+            .resolve_tag_ids(req_form.tag_id, req_form.include_descendants, vec![0])
+            .await?;
+        let by = match req_form.order_by {
+            ArticleOrderBy::CreateTime => ArticleAttr::CreateTime,
+            ArticleOrderBy::UpdateTime => ArticleAttr::UpdateTime,
+            ArticleOrderBy::Title => ArticleAttr::Title,
+        };
+        let order = OrderParam {
+            by,
+            ascending: req_form.ascending,
+            // ties on the requested column stay stable across pages
+            then: vec![(ArticleAttr::Id, req_form.ascending)],
+        };
+        let paginate = PaginateParam {
+            page_num: req_form.page_num,
+            page_size: req_form.page_size,
+        };
+        self.search_inner(
+            filter,
+            tag_ids,
+            pinned_first(order, req_form.tag_id),
+            paginate,
+        )
+        .await
+    }
+
+    async fn fulltext_search(
+        &self,
+        req_form: ArticleFulltextSearchReqForm,
+    ) -> AppResult<ArticleFulltextSearchResForm> {
+        let filter = ArticleFilterParam {
+            status_type: Some(1),
+            live_at: Some(Local::now().naive_local()),
+            ..Default::default()
+        };
+        let paginate = PaginateParam {
+            page_num: req_form.page_num,
+            page_size: req_form.page_size,
+        };
+        let terms: Vec<Vec<char>> = req_form
+            .query
+            .split_whitespace()
+            .map(lowercase_chars)
+            .collect();
+        let (record_total, article_models) = match self.use_search_index {
+            true => {
+                self.refresh_search_index().await?;
+                let offset = paginate.page_num.saturating_sub(1) * paginate.page_size;
+                let (hit_total, hits) = self.search_index.search(
+                    &req_form.query,
+                    offset as usize,
+                    paginate.page_size as usize,
+                );
+                let ids: Vec<i32> = hits.iter().map(|hit| hit.id).collect();
+                let mut article_models = self
+                    .article_dao
+                    .list_all(
+                        ArticleFilterParam {
+                            ids: Some(ids.clone()),
+                            ..filter
+                        },
+                        OrderParam::<ArticleAttr>::default(),
+                    )
+                    .await?;
+                // keep the relevance order of the index
+                article_models.sort_by_key(|model| ids.iter().position(|id| *id == model.id));
+                (hit_total as u64, article_models)
+            }
+            false => (
+                self.article_dao
+                    .count_fulltext(req_form.query.clone(), filter.clone())
+                    .await?,
+                self.article_dao
+                    .list_fulltext(req_form.query, filter, paginate.clone())
+                    .await?,
+            ),
+        };
+        let hit_infos = article_models
+            .into_iter()
+            .map(|model| ArticleHitInfo {
+                id: model.id,
+                title_highlight: highlight(&model.title, &terms),
+                content_snippet: snippet(&model.content, &terms),
+                title: model.title,
+                description: model.description,
+                create_time: model.create_time,
+                update_time: model.update_time,
+                create_user_id: model.create_user_id,
+            })
+            .collect();
+        Page::new(
+            paginate.page_num,
+            paginate.page_size,
+            record_total,
+            hit_infos,
+        )
+        .wrap(
+            "Invalid pagination parameters",
+            AppErrorKind::RequestParamInvalid,
+        )
+    }
+
+    async fn suggest(&self, req_form: ArticleSuggestReqForm) -> AppResult<ArticleSuggestResForm> {
+        if !self.use_search_index {
+            return Err(AppError::new(
+                "Suggestions require the search index engine",
+                AppErrorKind::NotImplemented,
+            ));
+        }
+        self.refresh_search_index().await?;
+        Ok(ArticleSuggestResForm {
+            suggestions: self
+                .search_index
+                .suggest(&req_form.prefix, self.suggest_limit),
+        })
+    }
+
+    async fn archive(&self) -> AppResult<ArticleArchiveResForm> {
+        let filter = ArticleFilterParam {
+            status_type: Some(1),
+            live_at: Some(Local::now().naive_local()),
+            ..Default::default()
+        };
+        let months = self
+            .article_dao
+            .count_by_month(filter)
+            .await?
+            .into_iter()
+            .map(|(year, month, count)| ArticleArchiveMonth { year, month, count })
+            .collect();
+        Ok(ArticleArchiveResForm { months })
+    }
+
+    async fn archive_search(
+        &self,
+        year: i32,
+        month: u32,
+        req_form: ArticleArchiveSearchReqForm,
+    ) -> AppResult<ArticleArchiveSearchResForm> {
+        let (start, end) = month_range(year, month).ok_or_else(|| {
+            AppError::new(
+                format!("Invalid archive month {}-{}", year, month),
+                AppErrorKind::RequestParamInvalid,
+            )
+        })?;
+        let filter = ArticleFilterParam {
+            status_type: Some(1),
+            live_at: Some(Local::now().naive_local()),
+            publish_time_start: Some(start),
+            publish_time_end: Some(end),
+            ..Default::default()
+        };
+        let paginate = PaginateParam {
+            page_num: req_form.page_num,
+            page_size: req_form.page_size,
+        };
+        let latest_published_first = OrderParam {
+            by: ArticleAttr::PublishTime,
+            ascending: false,
+            ..Default::default()
+        };
+        self.search_inner(
+            filter,
+            None,
+            pinned_first(latest_published_first, None),
+            paginate,
+        )
+        .await
+    }
+
+    async fn find(&self, id: i32, visitor: &str) -> AppResult<ArticleFindResForm> {
+        let article_model = self
+            .article_dao
+            .get(ArticleFilterParam {
+                id: Some(id),
+                status_type: Some(1),
+                live_at: Some(Local::now().naive_local()),
+                ..Default::default()
+            })
+            .await?;
+        self.find_public(article_model, visitor).await
+    }
+
+    async fn find_by_slug(&self, slug: String, visitor: &str) -> AppResult<ArticleSlugFindResForm> {
+        let public_filter = ArticleFilterParam {
+            status_type: Some(1),
+            live_at: Some(Local::now().naive_local()),
+            ..Default::default()
+        };
+        let article_model = self
+            .article_dao
+            .list_all(
+                ArticleFilterParam {
+                    slug: Some(slug.clone()),
+                    ..public_filter.clone()
+                },
+                OrderParam::<ArticleAttr>::default(),
+            )
+            .await?
+            .pop();
+        if let Some(article_model) = article_model {
+            return Ok(ArticleSlugFindResForm::Found(Box::new(
+                self.find_public(article_model, visitor).await?,
+            )));
+        }
+        let history = self
+            .slug_history_dao
+            .get(SlugHistoryFilterParam {
+                resource_type: Some(SLUG_RESOURCE_TYPE),
+                slug: Some(slug),
+                ..Default::default()
+            })
+            .await?;
+        let article_model = self
+            .article_dao
+            .get(ArticleFilterParam {
+                id: Some(history.resource_id),
+                ..public_filter
+            })
+            .await?;
+        Ok(ArticleSlugFindResForm::Moved(article_model.slug))
+    }
+
+    async fn render(&self, id: i32) -> AppResult<ArticleRenderResForm> {
+        let article_model = self
+            .article_dao
+            .get(ArticleFilterParam {
+                id: Some(id),
+                status_type: Some(1),
+                live_at: Some(Local::now().naive_local()),
+                ..Default::default()
+            })
+            .await?;
+        let cache_key = render_cache_key(id, &article_model.content);
+        if let Some(rendered) = self.cache_utils.get(&cache_key).await? {
+            return Ok(rendered);
+        }
+        let rendered = self.render_utils.render(&article_model.content);
+        self.cache_utils
+            .set(
+                &cache_key,
+                rendered.clone(),
+                Some(self.render_cache_expire_sec),
+            )
+            .await?;
+        Ok(rendered)
+    }
+
+    async fn mine_search(
+        &self,
+        token: &str,
+        req_form: ArticleMineSearchReqForm,
+    ) -> AppResult<ArticleMineSearchResForm> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        let filter = ArticleFilterParam {
+            title_search: req_form.title_search,
+            create_user_id: Some(claims.user_id),
+            status_type: req_form.status_type,
+            status_types: Some(vec![0, 1, 2]),
+            ..Default::default()
+        };
+        let paginate = PaginateParam {
+            page_num: req_form.page_num,
+            page_size: req_form.page_size,
+        };
+        self.search_inner(filter, None, Self::latest_first(), paginate)
+            .await
+    }
+
+    async fn create(
+        &self,
+        token: &str,
+        req_form: ArticleCreateReqForm,
+    ) -> AppResult<ArticleCreateResForm> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        let description = match req_form.description.is_empty() {
+            true => self.render_utils.excerpt(&req_form.content),
+            false => req_form.description,
+        };
+        let slug = req_form
+            .slug
+            .unwrap_or_else(|| slugify(&req_form.title, "article"));
+        let slug = self.resolve_slug(slug, None).await?;
+        let article_model = self
+            .article_dao
+            .create(ArticleCreateParam {
+                title: req_form.title,
+                slug,
+                description,
+                content: req_form.content,
+                is_top: false,
+                status_type: 0,
+                publish_at: None,
+                unpublish_at: None,
+                publish_time: None,
+                create_user_id: claims.user_id,
+            })
+            .await?;
+        let res_form = ArticleCreateResForm {
+            article_info: article_model.into(),
+            tags: Vec::new(),
+            series: None,
+        };
+        self.after_change(&res_form).await?;
+        Ok(res_form)
+    }
+
+    async fn edit(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleEditReqForm,
+    ) -> AppResult<ArticleEditResForm> {
+        let (claims, article_model) = self.verify_owner(id, token).await?;
+        // an empty description is regenerated from the latest content
+        let description = match req_form.description {
+            Some(description) if description.is_empty() => Some(
+                self.render_utils.excerpt(
+                    req_form
+                        .content
+                        .as_deref()
+                        .unwrap_or(&article_model.content),
+                ),
+            ),
+            description => description,
+        };
+        let slug = match req_form.slug {
+            Some(slug) if slug != article_model.slug => {
+                Some(self.resolve_slug(slug, Some(id)).await?)
+            }
+            _ => None,
+        };
+        self.update_inner(
+            id,
+            ArticleUpdateParam {
+                title: req_form.title,
+                slug,
+                description,
+                content: req_form.content,
+                update_user_id: Some(claims.user_id),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    async fn change_status(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleChangeStatusReqForm,
+    ) -> AppResult<ArticleChangeStatusResForm> {
+        let (claims, _) = self.verify_owner(id, token).await?;
+        self.update_inner(
+            id,
+            ArticleUpdateParam {
+                status_type: Some(req_form.status_type),
+                update_user_id: Some(claims.user_id),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    async fn delete(&self, id: i32, token: &str) -> AppResult<ArticleDeleteResForm> {
+        let (claims, _) = self.verify_owner(id, token).await?;
+        self.delete_inner(id, claims.user_id).await
+    }
+
+    async fn schedule(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleScheduleReqForm,
+    ) -> AppResult<ArticleScheduleResForm> {
+        let (claims, article_model) = self.verify_owner(id, token).await?;
+        if let (Some(publish_at), Some(unpublish_at)) = (req_form.publish_at, req_form.unpublish_at)
+        {
+            if unpublish_at <= publish_at {
+                return Err(AppError::new(
+                    "unpublishAt must be later than publishAt",
+                    AppErrorKind::RequestParamInvalid,
+                ));
+            }
+        }
+        // a published article scheduled for later goes back to draft until then
+        let status_type = match req_form.publish_at {
+            Some(publish_at)
+                if publish_at > Local::now().naive_local() && article_model.status_type == 1 =>
+            {
+                Some(0)
+            }
+            _ => None,
+        };
+        self.update_inner(
+            id,
+            ArticleUpdateParam {
+                status_type,
+                publish_at: Some(req_form.publish_at),
+                unpublish_at: Some(req_form.unpublish_at),
+                update_user_id: Some(claims.user_id),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    async fn replace_tags(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleTagsReqForm,
+    ) -> AppResult<ArticleTagsResForm> {
+        let (_, article_model) = self.verify_owner(id, token).await?;
+        self.verify_tags(&req_form.tag_ids).await?;
+        self.article_tag_dao
+            .replace_tags(id, req_form.tag_ids)
+            .await?;
+        let res_form = self.find_res(article_model, &[0, 1]).await?;
+        self.after_change(&res_form).await?;
+        Ok(res_form)
+    }
+
+    async fn add_tags(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleTagsReqForm,
+    ) -> AppResult<ArticleTagsResForm> {
+        let (_, article_model) = self.verify_owner(id, token).await?;
+        self.verify_tags(&req_form.tag_ids).await?;
+        self.article_tag_dao.add_tags(id, req_form.tag_ids).await?;
+        let res_form = self.find_res(article_model, &[0, 1]).await?;
+        self.after_change(&res_form).await?;
+        Ok(res_form)
+    }
+
+    async fn remove_tags(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleTagsReqForm,
+    ) -> AppResult<ArticleTagsResForm> {
+        let (_, article_model) = self.verify_owner(id, token).await?;
+        self.article_tag_dao
+            .remove_tags(id, req_form.tag_ids)
+            .await?;
+        let res_form = self.find_res(article_model, &[0, 1]).await?;
+        self.after_change(&res_form).await?;
+        Ok(res_form)
+    }
+
+    async fn react(
+        &self,
+        id: i32,
+        kind: String,
+        token: &str,
+    ) -> AppResult<ArticleReactionsResForm> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        self.verify_reaction_kind(&kind)?;
+        self.get_live(id).await?;
+        if self
+            .article_reaction_dao
+            .add_reaction(id, claims.user_id, kind)
+            .await?
+        {
+            self.cache_utils.del(&reactions_cache_key(id)).await?;
+        }
+        self.reactions_res(id, Some(claims.user_id)).await
+    }
+
+    async fn unreact(
+        &self,
+        id: i32,
+        kind: String,
+        token: &str,
+    ) -> AppResult<ArticleReactionsResForm> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        self.verify_reaction_kind(&kind)?;
+        self.get_live(id).await?;
+        let delete_num = self
+            .article_reaction_dao
+            .delete_all(ArticleReactionFilterParam {
+                article_id: Some(id),
+                user_id: Some(claims.user_id),
+                kind: Some(kind),
+            })
+            .await?;
+        if delete_num > 0 {
+            self.cache_utils.del(&reactions_cache_key(id)).await?;
+        }
+        self.reactions_res(id, Some(claims.user_id)).await
+    }
+
+    async fn reactions(&self, id: i32, token: Option<&str>) -> AppResult<ArticleReactionsResForm> {
+        let user_id = match token {
+            Some(token) => Some(self.token_utils.verify_token(token, &[0, 1]).await?.user_id),
+            None => None,
+        };
+        self.get_live(id).await?;
+        self.reactions_res(id, user_id).await
+    }
+
+    async fn revision_search(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleRevisionSearchReqForm,
+    ) -> AppResult<ArticleRevisionSearchResForm> {
+        self.verify_owner(id, token).await?;
+        let filter = ArticleRevisionFilterParam {
+            article_id: Some(id),
+            ..Default::default()
+        };
+        let record_total = self.article_revision_dao.count(filter.clone()).await?;
+        let model_infos = self
+            .article_revision_dao
+            .list(
+                filter,
+                OrderParam {
+                    by: ArticleRevisionAttr::Id,
+                    ascending: false,
+                    ..Default::default()
+                },
+                PaginateParam {
+                    page_num: req_form.page_num,
+                    page_size: req_form.page_size,
+                },
+            )
+            .await?
+            .into_iter()
+            .map(|model| model.into())
+            .collect();
+        Page::new(
+            req_form.page_num,
+            req_form.page_size,
+            record_total,
+            model_infos,
+        )
+        .wrap(
+            "Invalid pagination parameters",
+            AppErrorKind::RequestParamInvalid,
+        )
+    }
+
+    async fn revision_diff(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleRevisionDiffReqForm,
+    ) -> AppResult<ArticleRevisionDiffResForm> {
+        self.verify_owner(id, token).await?;
+        let get_revision = |revision_id| {
+            self.article_revision_dao.get(ArticleRevisionFilterParam {
+                id: Some(revision_id),
+                article_id: Some(id),
+                ..Default::default()
+            })
+        };
+        let from_model = get_revision(req_form.from_revision_id).await?;
+        let to_model = get_revision(req_form.to_revision_id).await?;
+        Ok(ArticleRevisionDiffResForm {
+            from_revision_id: from_model.id,
+            to_revision_id: to_model.id,
+            lines: diff_lines(&from_model.content, &to_model.content),
+        })
+    }
+
+    async fn rollback(
+        &self,
+        id: i32,
+        revision_id: i32,
+        token: &str,
+    ) -> AppResult<ArticleRollbackResForm> {
+        let (claims, _) = self.verify_owner(id, token).await?;
+        let revision_model = self
+            .article_revision_dao
+            .get(ArticleRevisionFilterParam {
+                id: Some(revision_id),
+                article_id: Some(id),
+                ..Default::default()
+            })
+            .await?;
+        // the rollback is recorded as a new revision rather than dropping later ones
+        self.update_inner(
+            id,
+            ArticleUpdateParam {
+                title: Some(revision_model.title),
+                description: Some(revision_model.description),
+                content: Some(revision_model.content),
+                update_user_id: Some(claims.user_id),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    async fn admin_search(
+        &self,
+        token: &str,
+        req_form: ArticleAdminSearchReqForm,
+    ) -> AppResult<ArticleAdminSearchResForm> {
+        self.token_utils.verify_token(token, &[1]).await?;
+        let filter = ArticleFilterParam {
+            title_search: req_form.title_search,
+            status_type: req_form.status_type,
+            is_top: req_form.is_top,
+            create_user_id: req_form.create_user_id,
+            create_time_start: req_form.create_time_start,
+            create_time_end: req_form.create_time_end,
+            ..Default::default()
+        };
+        let tag_ids = self
+            .resolve_tag_ids(req_form.tag_id, req_form.include_descendants, vec![0, 1])
+            .await?;
+        let paginate = PaginateParam {
+            page_num: req_form.page_num,
+            page_size: req_form.page_size,
+        };
+        self.search_inner(filter, tag_ids, Self::latest_first(), paginate)
+            .await
+    }
+
+    async fn admin_find(&self, id: i32, token: &str) -> AppResult<ArticleAdminGetResForm> {
+        self.token_utils.verify_token(token, &[1]).await?;
+        let article_model = self
+            .article_dao
+            .get(ArticleFilterParam {
+                id: Some(id),
+                ..Default::default()
+            })
+            .await?;
+        self.find_res(article_model, &[0, 1]).await
+    }
+
+    async fn admin_edit(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleAdminEditReqForm,
+    ) -> AppResult<ArticleAdminEditResForm> {
+        let claims = self.token_utils.verify_token(token, &[1]).await?;
+        self.update_inner(
+            id,
+            ArticleUpdateParam {
+                is_top: req_form.is_top,
+                status_type: req_form.status_type,
+                update_user_id: Some(claims.user_id),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    async fn admin_delete(&self, id: i32, token: &str) -> AppResult<ArticleAdminDeleteResForm> {
+        let claims = self.token_utils.verify_token(token, &[1]).await?;
+        self.delete_inner(id, claims.user_id).await
+    }
+
+    async fn pins(
+        &self,
+        token: &str,
+        req_form: ArticlePinsReqForm,
+    ) -> AppResult<ArticlePinsResForm> {
+        self.token_utils.verify_token(token, &[1]).await?;
+        let article_models = match req_form.tag_id {
+            Some(tag_id) => self.article_tag_dao.list_pins(tag_id).await?,
+            None => {
+                self.article_dao
+                    .list_all(
+                        ArticleFilterParam {
+                            is_top: Some(true),
+                            ..Default::default()
+                        },
+                        OrderParam {
+                            by: ArticleAttr::PinPriority,
+                            ascending: false,
+                            ..Default::default()
+                        },
+                    )
+                    .await?
+            }
+        };
+        Ok(article_models
+            .into_iter()
+            .map(|model| model.into())
+            .collect())
+    }
+
+    async fn replace_pins(
+        &self,
+        token: &str,
+        req_form: ArticleReplacePinsReqForm,
+    ) -> AppResult<ArticlePinsResForm> {
+        self.token_utils.verify_token(token, &[1]).await?;
+        let mut seen = HashSet::new();
+        let mut article_ids = req_form.article_ids;
+        article_ids.retain(|id| seen.insert(*id));
+        match req_form.tag_id {
+            Some(tag_id) => {
+                self.article_tag_dao
+                    .replace_pins(tag_id, article_ids)
+                    .await?
+            }
+            None => self.article_dao.replace_pins(article_ids).await?,
+        }
+        self.pins(
+            token,
+            ArticlePinsReqForm {
+                tag_id: req_form.tag_id,
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag_model(id: i32, parent_tag_id: Option<i32>) -> TagDataModel {
+        TagDataModel {
+            id,
+            name: format!("tag{}", id),
+            slug: format!("tag{}", id),
+            parent_tag_id,
+            thumbnail_url: None,
+            status_type: 0,
+            create_time: DateTime::default(),
+            update_time: DateTime::default(),
+            create_user_id: 1,
+            update_user_id: 1,
+            delete_time: None,
+            delete_user_id: None,
+        }
+    }
+
+    #[test]
+    fn test_highlight() {
+        let terms = vec![lowercase_chars("rust"), lowercase_chars("数据")];
+        assert_eq!(
+            highlight("Rust <b> 数据库", &terms),
+            "<mark>Rust</mark> &lt;b&gt; <mark>数据</mark>库"
+        );
+
+        let content = format!("{}\n\nrust {}", "a".repeat(100), "b".repeat(200));
+        let snippet = snippet(&content, &terms);
+        assert!(snippet.starts_with("…a"));
+        assert!(snippet.contains("<mark>rust</mark>"));
+        assert!(snippet.ends_with("b…"));
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let lines = diff_lines("a\nb\nc\n", "a\nc\nd");
+        let summary: Vec<(&str, Option<usize>, Option<usize>, &str)> = lines
+            .iter()
+            .map(|line| {
+                (
+                    line.tag.as_str(),
+                    line.old_line_num,
+                    line.new_line_num,
+                    line.content.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("equal", Some(1), Some(1), "a"),
+                ("delete", Some(2), None, "b"),
+                ("equal", Some(3), Some(2), "c"),
+                ("insert", None, Some(3), "d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_descendant_tag_ids() {
+        let models = vec![
+            tag_model(1, None),
+            tag_model(2, Some(1)),
+            tag_model(3, Some(2)),
+            tag_model(4, Some(1)),
+            tag_model(5, None),
+        ];
+        let mut tag_ids = descendant_tag_ids(1, models.clone());
+        tag_ids.sort();
+        assert_eq!(tag_ids, vec![1, 2, 3, 4]);
+        assert_eq!(descendant_tag_ids(5, models), vec![5]);
+    }
+
+    #[test]
+    fn test_pinned_first() {
+        let order = OrderParam {
+            by: ArticleAttr::Title,
+            ascending: true,
+            ..Default::default()
+        };
+        let global = pinned_first(order.clone(), None);
+        assert_eq!(global.by, ArticleAttr::IsTop);
+        assert!(!global.ascending);
+        assert_eq!(
+            global.then,
+            vec![
+                (ArticleAttr::PinPriority, false),
+                (ArticleAttr::Title, true)
+            ]
+        );
+        let in_tag = pinned_first(order, Some(3));
+        assert_eq!(in_tag.by, ArticleAttr::TagPinPriority(3));
+        assert_eq!(
+            in_tag.then,
+            vec![
+                (ArticleAttr::IsTop, false),
+                (ArticleAttr::PinPriority, false),
+                (ArticleAttr::Title, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_month_range() {
+        let (start, end) = month_range(2023, 12).unwrap();
+        assert_eq!(start.to_string(), "2023-12-01 00:00:00");
+        assert_eq!(end.to_string(), "2024-01-01 00:00:00");
+        let (_, end) = month_range(2024, 2).unwrap();
+        assert_eq!(end.to_string(), "2024-03-01 00:00:00");
+        assert!(month_range(2024, 0).is_none());
+        assert!(month_range(2024, 13).is_none());
+    }
+}
@@ -0,0 +1,467 @@
+// ********************* import ********************* //
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{Duration, Local};
+
+use super::super::{traits::comment::CommentServiceTrait, types::comment::prelude::*};
+use crate::app::{
+    common::prelude::*,
+    dao::{
+        prelude::{
+            ArticleDataAccess, CommentDataAccess, OrderParam, PaginateParam, UserDataAccess,
+        },
+        types::{article::prelude::*, comment::prelude::*, user::prelude::*},
+    },
+    utils::prelude::{Page, TokenUtilsTrait},
+};
+
+// ********************* content ********************* //
+impl From<CommentDataModel> for CommentInfo {
+    fn from(model: CommentDataModel) -> Self {
+        Self {
+            id: model.id,
+            article_id: model.article_id,
+            parent_comment_id: model.parent_comment_id,
+            depth: model.depth,
+            content: model.content,
+            status_type: model.status_type,
+            create_time: model.create_time,
+            update_time: model.update_time,
+            create_user_id: model.create_user_id,
+        }
+    }
+}
+
+// t_space_comment.status_type
+const STATUS_PENDING: i32 = 0;
+const STATUS_APPROVED: i32 = 1;
+const STATUS_REJECTED: i32 = 2;
+const STATUS_DELETED: i32 = 3;
+
+// comments whose parent is not in `models` are unreachable and get dropped, so are
+// comments other than approved ones left without replies, the rest of those stay as
+// placeholders without content, an edit waiting for review does not hide its replies
+fn build_comment_thread(models: Vec<CommentDataModel>) -> Vec<CommentNode> {
+    fn build_nodes(
+        parent_comment_id: Option<i32>,
+        children_map: &mut HashMap<Option<i32>, Vec<CommentDataModel>>,
+    ) -> Vec<CommentNode> {
+        children_map
+            .remove(&parent_comment_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|model| {
+                let replies = build_nodes(Some(model.id), children_map);
+                let mut comment_info: CommentInfo = model.into();
+                if comment_info.status_type != STATUS_APPROVED {
+                    if replies.is_empty() {
+                        return None;
+                    }
+                    comment_info.content = String::new();
+                }
+                Some(CommentNode {
+                    comment_info,
+                    replies,
+                })
+            })
+            .collect()
+    }
+
+    let mut children_map: HashMap<Option<i32>, Vec<CommentDataModel>> = HashMap::new();
+    for model in models {
+        children_map
+            .entry(model.parent_comment_id)
+            .or_default()
+            .push(model);
+    }
+    build_nodes(None, &mut children_map)
+}
+
+pub struct CommentService<D, A, U, T>
+where
+    D: CommentDataAccess + Sync + Send,
+    A: ArticleDataAccess + Sync + Send,
+    U: UserDataAccess + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    pub comment_dao: Arc<D>,
+    pub article_dao: Arc<A>,
+    pub user_dao: Arc<U>,
+    pub token_utils: Arc<T>,
+    pub max_depth: i32,
+    pub edit_window_sec: i64,
+    pub new_user_days: i64,
+}
+
+impl<D, A, U, T> CommentService<D, A, U, T>
+where
+    D: CommentDataAccess + Sync + Send,
+    A: ArticleDataAccess + Sync + Send,
+    U: UserDataAccess + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    pub fn new(
+        comment_dao: Arc<D>,
+        article_dao: Arc<A>,
+        user_dao: Arc<U>,
+        token_utils: Arc<T>,
+        cfg: &CommentConfig,
+    ) -> Self {
+        Self {
+            comment_dao,
+            article_dao,
+            user_dao,
+            token_utils,
+            max_depth: cfg.max_depth,
+            edit_window_sec: cfg.edit_window_sec,
+            new_user_days: cfg.new_user_days,
+        }
+    }
+
+    // comments are only open on articles visible to the public
+    async fn verify_article(&self, article_id: i32) -> AppResult<()> {
+        self.article_dao
+            .get(ArticleFilterParam {
+                id: Some(article_id),
+                status_type: Some(1),
+                live_at: Some(Local::now().naive_local()),
+                ..Default::default()
+            })
+            .await
+            .map(|_| ())
+    }
+
+    async fn get_alive(&self, id: i32) -> AppResult<CommentDataModel> {
+        self.comment_dao
+            .get(CommentFilterParam {
+                id: Some(id),
+                status_types: Some(vec![STATUS_PENDING, STATUS_APPROVED, STATUS_REJECTED]),
+                ..Default::default()
+            })
+            .await
+    }
+
+    // comments of new users, other than admins, wait for moderation
+    async fn review_status(&self, user_id: i32) -> AppResult<i32> {
+        let user_model = self
+            .user_dao
+            .get(UserFilterParam {
+                id: Some(user_id),
+                ..Default::default()
+            })
+            .await?;
+        let is_new_user = user_model.create_time + Duration::days(self.new_user_days)
+            > Local::now().naive_local();
+        Ok(match user_model.group_type != 1 && is_new_user {
+            true => STATUS_PENDING,
+            false => STATUS_APPROVED,
+        })
+    }
+
+    async fn update_inner(
+        &self,
+        id: i32,
+        update_param: CommentUpdateParam,
+    ) -> AppResult<CommentFindResForm> {
+        let filter = CommentFilterParam {
+            id: Some(id),
+            ..Default::default()
+        };
+        self.comment_dao
+            .update(filter.clone(), update_param)
+            .await?;
+        let comment_model = self.comment_dao.get(filter).await?;
+        Ok(CommentFindResForm {
+            comment_info: comment_model.into(),
+        })
+    }
+
+    async fn moderate(
+        &self,
+        token: &str,
+        ids: Vec<i32>,
+        status_type: i32,
+    ) -> AppResult<CommentAdminModerateResForm> {
+        self.token_utils.verify_token(token, &[1]).await?;
+        let affected_num = self
+            .comment_dao
+            .update_all(
+                CommentFilterParam {
+                    ids: Some(ids),
+                    status_type: Some(STATUS_PENDING),
+                    ..Default::default()
+                },
+                CommentUpdateParam {
+                    status_type: Some(status_type),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(CommentAdminModerateResForm { affected_num })
+    }
+}
+
+#[async_trait]
+impl<D, A, U, T> CommentServiceTrait for CommentService<D, A, U, T>
+where
+    D: CommentDataAccess + Sync + Send,
+    A: ArticleDataAccess + Sync + Send,
+    U: UserDataAccess + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    async fn thread(&self, article_id: i32) -> AppResult<CommentThreadResForm> {
+        self.verify_article(article_id).await?;
+        let comment_models = self
+            .comment_dao
+            .list_all(
+                CommentFilterParam {
+                    article_id: Some(article_id),
+                    ..Default::default()
+                },
+                OrderParam {
+                    by: CommentAttr::Id,
+                    ascending: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        Ok(CommentThreadResForm {
+            thread: build_comment_thread(comment_models),
+        })
+    }
+
+    async fn create(
+        &self,
+        token: &str,
+        req_form: CommentCreateReqForm,
+    ) -> AppResult<CommentCreateResForm> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        self.verify_article(req_form.article_id).await?;
+        let depth = match req_form.parent_comment_id {
+            Some(parent_comment_id) => {
+                // replies go to approved comments of the same article only
+                let parent_model = self
+                    .comment_dao
+                    .get(CommentFilterParam {
+                        id: Some(parent_comment_id),
+                        article_id: Some(req_form.article_id),
+                        status_type: Some(STATUS_APPROVED),
+                        ..Default::default()
+                    })
+                    .await?;
+                parent_model.depth + 1
+            }
+            None => 1,
+        };
+        if depth > self.max_depth {
+            return Err(AppError::new(
+                format!(
+                    "Comment depth {} exceeds the limit {}",
+                    depth, self.max_depth
+                ),
+                AppErrorKind::RequestParamInvalid,
+            ));
+        }
+        let status_type = self.review_status(claims.user_id).await?;
+        let comment_model = self
+            .comment_dao
+            .create(CommentCreateParam {
+                article_id: req_form.article_id,
+                parent_comment_id: req_form.parent_comment_id,
+                depth,
+                content: req_form.content,
+                status_type,
+                create_user_id: claims.user_id,
+            })
+            .await?;
+        Ok(CommentCreateResForm {
+            comment_info: comment_model.into(),
+        })
+    }
+
+    async fn edit(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: CommentEditReqForm,
+    ) -> AppResult<CommentEditResForm> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        let comment_model = self.get_alive(id).await?;
+        if comment_model.create_user_id != Some(claims.user_id) {
+            return Err(AppError::new(
+                format!("Comment {} is not created by the user", id),
+                AppErrorKind::PermissionDenied,
+            ));
+        }
+        if comment_model.status_type == STATUS_REJECTED {
+            return Err(AppError::new(
+                format!("Comment {} has been rejected", id),
+                AppErrorKind::PermissionDenied,
+            ));
+        }
+        if comment_model.create_time + Duration::seconds(self.edit_window_sec)
+            < Local::now().naive_local()
+        {
+            return Err(AppError::new(
+                format!("Edit window of comment {} has passed", id),
+                AppErrorKind::PermissionDenied,
+            ));
+        }
+        // the approval was given to the old content, edits of new users are reviewed again
+        let status_type = match self.review_status(claims.user_id).await? {
+            STATUS_PENDING => Some(STATUS_PENDING),
+            _ => None,
+        };
+        self.update_inner(
+            id,
+            CommentUpdateParam {
+                content: Some(req_form.content),
+                status_type,
+            },
+        )
+        .await
+    }
+
+    async fn delete(&self, id: i32, token: &str) -> AppResult<CommentDeleteResForm> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        let comment_model = self.get_alive(id).await?;
+        if comment_model.create_user_id != Some(claims.user_id) {
+            let user_model = self
+                .user_dao
+                .get(UserFilterParam {
+                    id: Some(claims.user_id),
+                    ..Default::default()
+                })
+                .await?;
+            if user_model.group_type != 1 {
+                return Err(AppError::new(
+                    format!("Comment {} is not created by the user", id),
+                    AppErrorKind::PermissionDenied,
+                ));
+            }
+        }
+        // the row stays so that replies keep their place in the thread
+        self.update_inner(
+            id,
+            CommentUpdateParam {
+                content: Some(String::new()),
+                status_type: Some(STATUS_DELETED),
+            },
+        )
+        .await
+    }
+
+    async fn admin_search(
+        &self,
+        token: &str,
+        req_form: CommentAdminSearchReqForm,
+    ) -> AppResult<CommentAdminSearchResForm> {
+        self.token_utils.verify_token(token, &[1]).await?;
+        let filter = CommentFilterParam {
+            article_id: req_form.article_id,
+            create_user_id: req_form.create_user_id,
+            status_type: req_form.status_type,
+            content_search: req_form.content_search,
+            ..Default::default()
+        };
+        let paginate = PaginateParam {
+            page_num: req_form.page_num,
+            page_size: req_form.page_size,
+        };
+        let record_total = self.comment_dao.count(filter.clone()).await?;
+        let model_infos = self
+            .comment_dao
+            .list(
+                filter,
+                OrderParam::<CommentAttr>::default(),
+                paginate.clone(),
+            )
+            .await?
+            .into_iter()
+            .map(|model| model.into())
+            .collect();
+        Page::new(
+            paginate.page_num,
+            paginate.page_size,
+            record_total,
+            model_infos,
+        )
+        .wrap(
+            "Invalid pagination parameters",
+            AppErrorKind::RequestParamInvalid,
+        )
+    }
+
+    async fn admin_approve(
+        &self,
+        token: &str,
+        req_form: CommentAdminModerateReqForm,
+    ) -> AppResult<CommentAdminModerateResForm> {
+        self.moderate(token, req_form.ids, STATUS_APPROVED).await
+    }
+
+    async fn admin_reject(
+        &self,
+        token: &str,
+        req_form: CommentAdminModerateReqForm,
+    ) -> AppResult<CommentAdminModerateResForm> {
+        self.moderate(token, req_form.ids, STATUS_REJECTED).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::prelude::DateTime;
+
+    use super::*;
+
+    fn comment_model(
+        id: i32,
+        parent_comment_id: Option<i32>,
+        status_type: i32,
+    ) -> CommentDataModel {
+        CommentDataModel {
+            id,
+            article_id: 1,
+            parent_comment_id,
+            depth: 1,
+            content: format!("comment{}", id),
+            status_type,
+            create_time: DateTime::default(),
+            update_time: DateTime::default(),
+            create_user_id: Some(1),
+        }
+    }
+
+    #[test]
+    fn test_build_comment_thread() {
+        let models = vec![
+            comment_model(1, None, STATUS_APPROVED),
+            // deleted with a reply, kept as a placeholder
+            comment_model(2, Some(1), STATUS_DELETED),
+            comment_model(3, Some(2), STATUS_APPROVED),
+            // deleted without replies, dropped
+            comment_model(4, None, STATUS_DELETED),
+            // deleted whose only reply is deleted too, dropped
+            comment_model(5, None, STATUS_DELETED),
+            comment_model(6, Some(5), STATUS_DELETED),
+            // parent is filtered out, so the comment is unreachable
+            comment_model(7, Some(8), STATUS_APPROVED),
+            // edited and waiting for review, kept as a placeholder for its reply
+            comment_model(9, None, STATUS_PENDING),
+            comment_model(10, Some(9), STATUS_APPROVED),
+            // rejected without replies, dropped
+            comment_model(11, None, STATUS_REJECTED),
+        ];
+        let thread = build_comment_thread(models);
+        assert_eq!(thread.len(), 2);
+        assert_eq!(thread[0].comment_info.id, 1);
+        assert_eq!(thread[0].comment_info.content, "comment1");
+        assert_eq!(thread[0].replies[0].comment_info.id, 2);
+        assert_eq!(thread[0].replies[0].replies[0].comment_info.id, 3);
+        assert_eq!(thread[1].comment_info.id, 9);
+        assert_eq!(thread[1].comment_info.content, "");
+        assert_eq!(thread[1].replies[0].comment_info.id, 10);
+    }
+}
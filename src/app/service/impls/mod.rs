@@ -1,5 +1,21 @@
+pub mod article;
+pub mod comment;
+pub mod series;
+pub mod site;
+pub mod tag;
+pub mod transfer;
+pub mod trash;
+pub mod upload;
 pub mod user;
 
 pub mod prelude {
+    pub use super::article::ArticleService;
+    pub use super::comment::CommentService;
+    pub use super::series::SeriesService;
+    pub use super::site::SiteService;
+    pub use super::tag::TagService;
+    pub use super::transfer::TransferService;
+    pub use super::trash::TrashService;
+    pub use super::upload::UploadService;
     pub use super::user::UserService;
 }
@@ -0,0 +1,403 @@
+// ********************* import ********************* //
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::Local;
+
+use super::super::{traits::series::SeriesServiceTrait, types::series::prelude::*};
+use crate::app::{
+    common::prelude::*,
+    dao::{
+        prelude::{ArticleDataAccess, SeriesArticleDataAccess, SeriesDataAccess},
+        types::{article::prelude::*, series::prelude::*, series_article::prelude::*},
+    },
+    utils::prelude::TokenUtilsTrait,
+};
+
+// ********************* content ********************* //
+impl From<SeriesDataModel> for SeriesInfo {
+    fn from(model: SeriesDataModel) -> Self {
+        Self {
+            id: model.id,
+            title: model.title,
+            description: model.description,
+            create_time: model.create_time,
+            update_time: model.update_time,
+            create_user_id: model.create_user_id,
+        }
+    }
+}
+
+impl From<ArticleDataModel> for SeriesArticleInfo {
+    fn from(model: ArticleDataModel) -> Self {
+        Self {
+            id: model.id,
+            title: model.title,
+            slug: model.slug,
+            description: model.description,
+            status_type: model.status_type,
+            create_time: model.create_time,
+        }
+    }
+}
+
+// `part_ids` are all parts in order, unpublished ones are skipped over
+pub(super) fn series_nav(
+    series_model: SeriesDataModel,
+    article_id: i32,
+    part_ids: &[i32],
+    published_models: Vec<ArticleDataModel>,
+) -> SeriesNavInfo {
+    let mut published: HashMap<i32, ArticleDataModel> = published_models
+        .into_iter()
+        .map(|model| (model.id, model))
+        .collect();
+    let total = published.len();
+    let index = part_ids
+        .iter()
+        .position(|id| *id == article_id)
+        .unwrap_or(part_ids.len());
+    let (before, after) = part_ids.split_at(index);
+    let position = before
+        .iter()
+        .filter(|id| published.contains_key(id))
+        .count()
+        + 1;
+    let prev_id = before.iter().rev().find(|id| published.contains_key(id));
+    let next_id = after.iter().skip(1).find(|id| published.contains_key(id));
+    let prev = prev_id.and_then(|id| published.remove(id)).map(Into::into);
+    let next = next_id.and_then(|id| published.remove(id)).map(Into::into);
+    SeriesNavInfo {
+        series_id: series_model.id,
+        series_title: series_model.title,
+        position,
+        total,
+        prev,
+        next,
+    }
+}
+
+pub struct SeriesService<S, P, A, T>
+where
+    S: SeriesDataAccess + Sync + Send,
+    P: SeriesArticleDataAccess + Sync + Send,
+    A: ArticleDataAccess + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    pub series_dao: Arc<S>,
+    pub series_article_dao: Arc<P>,
+    pub article_dao: Arc<A>,
+    pub token_utils: Arc<T>,
+}
+
+impl<S, P, A, T> SeriesService<S, P, A, T>
+where
+    S: SeriesDataAccess + Sync + Send,
+    P: SeriesArticleDataAccess + Sync + Send,
+    A: ArticleDataAccess + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    pub fn new(
+        series_dao: Arc<S>,
+        series_article_dao: Arc<P>,
+        article_dao: Arc<A>,
+        token_utils: Arc<T>,
+    ) -> Self {
+        Self {
+            series_dao,
+            series_article_dao,
+            article_dao,
+            token_utils,
+        }
+    }
+
+    async fn get(&self, id: i32) -> AppResult<SeriesDataModel> {
+        self.series_dao
+            .get(SeriesFilterParam {
+                id: Some(id),
+                ..Default::default()
+            })
+            .await
+    }
+
+    async fn get_owned(&self, id: i32, token: &str) -> AppResult<SeriesDataModel> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        let series_model = self.get(id).await?;
+        if series_model.create_user_id != claims.user_id {
+            return Err(AppError::new(
+                format!("Series {} is not created by the user", id),
+                AppErrorKind::PermissionDenied,
+            ));
+        }
+        Ok(series_model)
+    }
+
+    async fn part_ids(&self, id: i32) -> AppResult<Vec<i32>> {
+        let mut models = self
+            .series_article_dao
+            .list_all(SeriesArticleFilterParam {
+                series_id: Some(id),
+                ..Default::default()
+            })
+            .await?;
+        models.sort_by_key(|model| model.position);
+        Ok(models.into_iter().map(|model| model.article_id).collect())
+    }
+
+    async fn find_res(
+        &self,
+        series_model: SeriesDataModel,
+        filter: ArticleFilterParam,
+    ) -> AppResult<SeriesFindResForm> {
+        let articles = self
+            .series_article_dao
+            .list_articles(series_model.id, filter)
+            .await?
+            .into_iter()
+            .map(|model| model.into())
+            .collect();
+        Ok(SeriesFindResForm {
+            series_info: series_model.into(),
+            articles,
+        })
+    }
+
+    // deleted articles are hidden from the owner as well
+    async fn owner_res(&self, series_model: SeriesDataModel) -> AppResult<SeriesFindResForm> {
+        let filter = ArticleFilterParam {
+            status_types: Some(vec![0, 1, 2]),
+            ..Default::default()
+        };
+        self.find_res(series_model, filter).await
+    }
+
+    async fn replace_parts(
+        &self,
+        series_model: SeriesDataModel,
+        part_ids: Vec<i32>,
+    ) -> AppResult<SeriesFindResForm> {
+        self.series_article_dao
+            .replace_articles(series_model.id, part_ids)
+            .await?;
+        self.owner_res(series_model).await
+    }
+}
+
+#[async_trait]
+impl<S, P, A, T> SeriesServiceTrait for SeriesService<S, P, A, T>
+where
+    S: SeriesDataAccess + Sync + Send,
+    P: SeriesArticleDataAccess + Sync + Send,
+    A: ArticleDataAccess + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    async fn find(&self, id: i32) -> AppResult<SeriesFindResForm> {
+        let series_model = self.get(id).await?;
+        let filter = ArticleFilterParam {
+            status_type: Some(1),
+            live_at: Some(Local::now().naive_local()),
+            ..Default::default()
+        };
+        self.find_res(series_model, filter).await
+    }
+
+    async fn create(
+        &self,
+        token: &str,
+        req_form: SeriesCreateReqForm,
+    ) -> AppResult<SeriesCreateResForm> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        let series_model = self
+            .series_dao
+            .create(SeriesCreateParam {
+                title: req_form.title,
+                description: req_form.description,
+                create_user_id: claims.user_id,
+            })
+            .await?;
+        Ok(SeriesFindResForm {
+            series_info: series_model.into(),
+            articles: Vec::new(),
+        })
+    }
+
+    async fn edit(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: SeriesEditReqForm,
+    ) -> AppResult<SeriesEditResForm> {
+        self.get_owned(id, token).await?;
+        let filter = SeriesFilterParam {
+            id: Some(id),
+            ..Default::default()
+        };
+        self.series_dao
+            .update(
+                filter,
+                SeriesUpdateParam {
+                    title: req_form.title,
+                    description: req_form.description,
+                },
+            )
+            .await?;
+        let series_model = self.get(id).await?;
+        self.owner_res(series_model).await
+    }
+
+    async fn delete(&self, id: i32, token: &str) -> AppResult<SeriesDeleteResForm> {
+        let series_model = self.get_owned(id, token).await?;
+        let res_form = self.owner_res(series_model).await?;
+        // memberships go along through the foreign key
+        self.series_dao
+            .delete(SeriesFilterParam {
+                id: Some(id),
+                ..Default::default()
+            })
+            .await?;
+        Ok(res_form)
+    }
+
+    async fn reorder(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: SeriesReorderReqForm,
+    ) -> AppResult<SeriesReorderResForm> {
+        let series_model = self.get_owned(id, token).await?;
+        let mut part_ids = self.part_ids(id).await?;
+        let mut new_part_ids = req_form.article_ids.clone();
+        part_ids.sort_unstable();
+        new_part_ids.sort_unstable();
+        if part_ids != new_part_ids {
+            return Err(AppError::new(
+                format!(
+                    "Every article of series {} must be listed exactly once, got {:?}",
+                    id, req_form.article_ids
+                ),
+                AppErrorKind::RequestParamInvalid,
+            ));
+        }
+        self.replace_parts(series_model, req_form.article_ids).await
+    }
+
+    async fn insert(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: SeriesInsertReqForm,
+    ) -> AppResult<SeriesInsertResForm> {
+        let series_model = self.get_owned(id, token).await?;
+        // only live articles of the owner can be added
+        self.article_dao
+            .get(ArticleFilterParam {
+                id: Some(req_form.article_id),
+                create_user_id: Some(series_model.create_user_id),
+                status_types: Some(vec![0, 1, 2]),
+                ..Default::default()
+            })
+            .await?;
+        if let Some(other) = self
+            .series_article_dao
+            .find_series(req_form.article_id)
+            .await?
+        {
+            return Err(AppError::new(
+                format!(
+                    "Article {} already belongs to series {}",
+                    req_form.article_id, other.id
+                ),
+                AppErrorKind::ResourceConflict,
+            ));
+        }
+        let mut part_ids = self.part_ids(id).await?;
+        let position = req_form
+            .position
+            .unwrap_or(part_ids.len())
+            .min(part_ids.len());
+        part_ids.insert(position, req_form.article_id);
+        self.replace_parts(series_model, part_ids).await
+    }
+
+    async fn remove(
+        &self,
+        id: i32,
+        article_id: i32,
+        token: &str,
+    ) -> AppResult<SeriesRemoveResForm> {
+        let series_model = self.get_owned(id, token).await?;
+        let mut part_ids = self.part_ids(id).await?;
+        let Some(index) = part_ids.iter().position(|part_id| *part_id == article_id) else {
+            return Err(AppError::new(
+                format!("Article {} is not in series {}", article_id, id),
+                AppErrorKind::ResourceNotFound,
+            ));
+        };
+        part_ids.remove(index);
+        self.replace_parts(series_model, part_ids).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::prelude::DateTime;
+
+    use super::*;
+
+    fn article_model(id: i32) -> ArticleDataModel {
+        ArticleDataModel {
+            id,
+            title: format!("part{}", id),
+            slug: format!("part{}", id),
+            description: "".to_string(),
+            content: "".to_string(),
+            is_top: false,
+            pin_priority: 0,
+            status_type: 1,
+            publish_at: None,
+            unpublish_at: None,
+            publish_time: None,
+            view_count: 0,
+            create_time: DateTime::default(),
+            update_time: DateTime::default(),
+            create_user_id: 1,
+            update_user_id: 1,
+            delete_time: None,
+            delete_user_id: None,
+        }
+    }
+
+    #[test]
+    fn test_series_nav() {
+        let series_model = SeriesDataModel {
+            id: 7,
+            title: "series".to_string(),
+            description: "".to_string(),
+            create_time: DateTime::default(),
+            update_time: DateTime::default(),
+            create_user_id: 1,
+        };
+        // 2 and 4 are unpublished
+        let part_ids = [1, 2, 3, 4, 5];
+        let published = || vec![article_model(1), article_model(3), article_model(5)];
+        let summary = |nav: SeriesNavInfo| {
+            (
+                nav.position,
+                nav.total,
+                nav.prev.map(|info| info.id),
+                nav.next.map(|info| info.id),
+            )
+        };
+
+        let nav = series_nav(series_model.clone(), 3, &part_ids, published());
+        assert_eq!(nav.series_id, 7);
+        assert_eq!(summary(nav), (2, 3, Some(1), Some(5)));
+        let nav = series_nav(series_model.clone(), 1, &part_ids, published());
+        assert_eq!(summary(nav), (1, 3, None, Some(3)));
+        let nav = series_nav(series_model.clone(), 5, &part_ids, published());
+        assert_eq!(summary(nav), (3, 3, Some(3), None));
+        // an unpublished part still links to its published neighbours
+        let nav = series_nav(series_model, 4, &part_ids, published());
+        assert_eq!(summary(nav), (3, 3, Some(3), Some(5)));
+    }
+}
@@ -0,0 +1,609 @@
+// ********************* import ********************* //
+use std::{fmt::Write, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use ring::digest::{digest, SHA256};
+
+use super::{
+    super::{traits::site::SiteServiceTrait, types::site::prelude::*},
+    article::ARTICLE_GENERATION_KEY,
+};
+use crate::app::{
+    common::prelude::*,
+    config::ServiceConfig,
+    dao::{
+        prelude::{
+            ArticleDataAccess, ArticleTagDataAccess, OrderParam, PaginateParam, TagDataAccess,
+            UserDataAccess,
+        },
+        types::{article::prelude::*, tag::prelude::*, user::prelude::*},
+    },
+    utils::prelude::{escape_xml, to_utc, CacheUtilsTrait, RenderUtilsTrait},
+};
+
+// ********************* content ********************* //
+#[derive(Clone, Copy, Debug)]
+enum FeedFormat {
+    Rss,
+    Atom,
+}
+
+impl FeedFormat {
+    fn file_name(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "feed.xml",
+            FeedFormat::Atom => "atom.xml",
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+        }
+    }
+}
+
+struct FeedChannel {
+    title: String,
+    description: String,
+    // the html page the feed belongs to
+    link: String,
+    self_link: String,
+    updated: DateTime<Utc>,
+}
+
+struct FeedItem {
+    title: String,
+    link: String,
+    author: String,
+    categories: Vec<String>,
+    description_html: String,
+    published: DateTime<Utc>,
+    updated: DateTime<Utc>,
+}
+
+fn rss_xml(channel: &FeedChannel, items: &[FeedItem]) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push_str(r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/">"#);
+    let _ = write!(
+        xml,
+        r#"<channel><title>{}</title><link>{}</link><description>{}</description><atom:link href="{}" rel="self" type="application/rss+xml"/><lastBuildDate>{}</lastBuildDate>"#,
+        escape_xml(&channel.title),
+        escape_xml(&channel.link),
+        escape_xml(&channel.description),
+        escape_xml(&channel.self_link),
+        channel.updated.to_rfc2822(),
+    );
+    for item in items {
+        let _ = write!(
+            xml,
+            r#"<item><title>{}</title><link>{}</link><guid isPermaLink="true">{}</guid><dc:creator>{}</dc:creator>"#,
+            escape_xml(&item.title),
+            escape_xml(&item.link),
+            escape_xml(&item.link),
+            escape_xml(&item.author),
+        );
+        for category in &item.categories {
+            let _ = write!(xml, "<category>{}</category>", escape_xml(category));
+        }
+        let _ = write!(
+            xml,
+            "<description>{}</description><pubDate>{}</pubDate></item>",
+            escape_xml(&item.description_html),
+            item.published.to_rfc2822(),
+        );
+    }
+    xml.push_str("</channel></rss>");
+    xml
+}
+
+fn atom_xml(channel: &FeedChannel, items: &[FeedItem]) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = write!(
+        xml,
+        r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>{}</title><subtitle>{}</subtitle><id>{}</id><link href="{}" rel="self" type="application/atom+xml"/><link href="{}" rel="alternate" type="text/html"/><updated>{}</updated>"#,
+        escape_xml(&channel.title),
+        escape_xml(&channel.description),
+        escape_xml(&channel.self_link),
+        escape_xml(&channel.self_link),
+        escape_xml(&channel.link),
+        channel.updated.to_rfc3339(),
+    );
+    for item in items {
+        let _ = write!(
+            xml,
+            r#"<entry><title>{}</title><id>{}</id><link href="{}" rel="alternate" type="text/html"/><published>{}</published><updated>{}</updated><author><name>{}</name></author>"#,
+            escape_xml(&item.title),
+            escape_xml(&item.link),
+            escape_xml(&item.link),
+            item.published.to_rfc3339(),
+            item.updated.to_rfc3339(),
+            escape_xml(&item.author),
+        );
+        for category in &item.categories {
+            let _ = write!(xml, r#"<category term="{}"/>"#, escape_xml(category));
+        }
+        let _ = write!(
+            xml,
+            r#"<summary type="html">{}</summary></entry>"#,
+            escape_xml(&item.description_html),
+        );
+    }
+    xml.push_str("</feed>");
+    xml
+}
+
+// a strong validator derived from the content, so it survives cache flushes
+fn etag(body: &str) -> String {
+    let hash = digest(&SHA256, body.as_bytes());
+    let hex: String = hash.as_ref()[..16]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("\"{}\"", hex)
+}
+
+fn document(content_type: &str, body: String, updated: Option<DateTime<Utc>>) -> SiteDocument {
+    SiteDocument {
+        content_type: content_type.to_string(),
+        etag: etag(&body),
+        body,
+        last_modified: updated.map(|time| time.timestamp()),
+    }
+}
+
+// the sitemap protocol allows at most 50,000 urls in a single file
+const SITEMAP_URL_LIMIT: usize = 50_000;
+
+struct SitemapEntry {
+    loc: String,
+    lastmod: Option<DateTime<Utc>>,
+}
+
+fn sitemap_entries_xml(root: &str, tag: &str, entries: &[SitemapEntry]) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = write!(
+        xml,
+        r#"<{} xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#,
+        root
+    );
+    for entry in entries {
+        let _ = write!(xml, "<{}><loc>{}</loc>", tag, escape_xml(&entry.loc));
+        if let Some(lastmod) = entry.lastmod {
+            let _ = write!(
+                xml,
+                "<lastmod>{}</lastmod>",
+                lastmod.to_rfc3339_opts(SecondsFormat::Secs, true)
+            );
+        }
+        let _ = write!(xml, "</{}>", tag);
+    }
+    let _ = write!(xml, "</{}>", root);
+    xml
+}
+
+// the whole url set when it fits in one file, otherwise an index of the parts for `None`
+// and the numbered part for `Some`, returns None for a part that does not exist
+fn sitemap_xml(
+    entries: &[SitemapEntry],
+    num: Option<usize>,
+    limit: usize,
+    part_loc: impl Fn(usize) -> String,
+) -> Option<(String, Option<DateTime<Utc>>)> {
+    let lastmod = |entries: &[SitemapEntry]| entries.iter().filter_map(|e| e.lastmod).max();
+    let parts: Vec<&[SitemapEntry]> = entries.chunks(limit).collect();
+    match num {
+        None if parts.len() <= 1 => Some((
+            sitemap_entries_xml("urlset", "url", entries),
+            lastmod(entries),
+        )),
+        None => {
+            let index: Vec<SitemapEntry> = parts
+                .iter()
+                .enumerate()
+                .map(|(i, part)| SitemapEntry {
+                    loc: part_loc(i + 1),
+                    lastmod: lastmod(part),
+                })
+                .collect();
+            Some((
+                sitemap_entries_xml("sitemapindex", "sitemap", &index),
+                lastmod(entries),
+            ))
+        }
+        Some(num) if parts.len() > 1 => {
+            let part = parts.get(num.checked_sub(1)?)?;
+            Some((sitemap_entries_xml("urlset", "url", part), lastmod(part)))
+        }
+        Some(_) => None,
+    }
+}
+
+pub struct SiteService<D, R, G, U, C, M>
+where
+    D: ArticleDataAccess + Sync + Send,
+    R: ArticleTagDataAccess + Sync + Send,
+    G: TagDataAccess + Sync + Send,
+    U: UserDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    M: RenderUtilsTrait + Sync + Send,
+{
+    pub article_dao: Arc<D>,
+    pub article_tag_dao: Arc<R>,
+    pub tag_dao: Arc<G>,
+    pub user_dao: Arc<U>,
+    pub cache_utils: Arc<C>,
+    pub render_utils: Arc<M>,
+    pub base_url: String,
+    pub title: String,
+    pub description: String,
+    pub feed_item_limit: u64,
+    pub feed_cache_expire_sec: u64,
+    pub sitemap_cache_expire_sec: u64,
+}
+
+impl<D, R, G, U, C, M> SiteService<D, R, G, U, C, M>
+where
+    D: ArticleDataAccess + Sync + Send,
+    R: ArticleTagDataAccess + Sync + Send,
+    G: TagDataAccess + Sync + Send,
+    U: UserDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    M: RenderUtilsTrait + Sync + Send,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        article_dao: Arc<D>,
+        article_tag_dao: Arc<R>,
+        tag_dao: Arc<G>,
+        user_dao: Arc<U>,
+        cache_utils: Arc<C>,
+        render_utils: Arc<M>,
+        service_cfg: &ServiceConfig,
+        site_cfg: &SiteConfig,
+    ) -> Self {
+        Self {
+            article_dao,
+            article_tag_dao,
+            tag_dao,
+            user_dao,
+            cache_utils,
+            render_utils,
+            base_url: service_cfg.base_url.trim_end_matches('/').to_string(),
+            title: site_cfg.title.clone(),
+            description: site_cfg.description.clone(),
+            feed_item_limit: site_cfg.feed_item_limit.max(1),
+            feed_cache_expire_sec: site_cfg.feed_cache_expire_sec,
+            sitemap_cache_expire_sec: site_cfg.sitemap_cache_expire_sec,
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path)
+    }
+
+    // a change of any article moves to a new generation and orphans older entries
+    async fn generation(&self) -> AppResult<i64> {
+        Ok(self
+            .cache_utils
+            .get(ARTICLE_GENERATION_KEY)
+            .await?
+            .unwrap_or(0))
+    }
+
+    fn public_article_filter() -> ArticleFilterParam {
+        ArticleFilterParam {
+            status_type: Some(1),
+            live_at: Some(Local::now().naive_local()),
+            ..Default::default()
+        }
+    }
+
+    async fn feed_item(&self, model: ArticleDataModel) -> AppResult<FeedItem> {
+        let author = self.article_dao.get_create_user(model.id).await?;
+        let categories = self
+            .article_tag_dao
+            .list_tags(model.id)
+            .await?
+            .into_iter()
+            .filter(|tag| tag.status_type == 0)
+            .map(|tag| tag.name)
+            .collect();
+        Ok(FeedItem {
+            title: model.title,
+            link: self.url(&format!("article/{}", model.slug)),
+            author: author.nickname,
+            categories,
+            description_html: self.render_utils.render(&model.description).html,
+            // drafts written long ago are dated by when they went public
+            published: to_utc(model.publish_time.unwrap_or(model.create_time)),
+            updated: to_utc(model.update_time),
+        })
+    }
+
+    async fn feed(&self, format: FeedFormat, tag_slug: Option<String>) -> AppResult<SiteDocument> {
+        let cache_key = format!(
+            "site:{}:{}:{}",
+            format.file_name(),
+            tag_slug.as_deref().unwrap_or(""),
+            self.generation().await?
+        );
+        if let Some(document) = self.cache_utils.get(&cache_key).await? {
+            return Ok(document);
+        }
+
+        let filter = Self::public_article_filter();
+        let order = OrderParam {
+            by: ArticleAttr::PublishTime,
+            ascending: false,
+            ..Default::default()
+        };
+        let paginate = PaginateParam {
+            page_num: 1,
+            page_size: self.feed_item_limit,
+        };
+        let (title, link, self_link, article_models) = match tag_slug {
+            Some(tag_slug) => {
+                let tag_model = self
+                    .tag_dao
+                    .get(TagFilterParam {
+                        slug: Some(tag_slug),
+                        status_type: Some(0),
+                        ..Default::default()
+                    })
+                    .await?;
+                let article_models = self
+                    .article_tag_dao
+                    .list_articles(vec![tag_model.id], filter, order, paginate)
+                    .await?;
+                (
+                    format!("{} - {}", self.title, tag_model.name),
+                    self.url(&format!("tag/{}", tag_model.slug)),
+                    self.url(&format!("tag/{}/{}", tag_model.slug, format.file_name())),
+                    article_models,
+                )
+            }
+            None => (
+                self.title.clone(),
+                self.url(""),
+                self.url(format.file_name()),
+                self.article_dao.list(filter, order, paginate).await?,
+            ),
+        };
+        let mut items = Vec::with_capacity(article_models.len());
+        for model in article_models {
+            items.push(self.feed_item(model).await?);
+        }
+        let updated = items.iter().map(|item| item.updated).max();
+        let channel = FeedChannel {
+            title,
+            description: self.description.clone(),
+            link,
+            self_link,
+            updated: updated.unwrap_or(DateTime::UNIX_EPOCH),
+        };
+        let body = match format {
+            FeedFormat::Rss => rss_xml(&channel, &items),
+            FeedFormat::Atom => atom_xml(&channel, &items),
+        };
+        let document = document(format.content_type(), body, updated);
+        self.cache_utils
+            .set(&cache_key, &document, Some(self.feed_cache_expire_sec))
+            .await?;
+        Ok(document)
+    }
+
+    // ordered by id within each kind, so the parts of a split sitemap stay stable
+    async fn sitemap_entries(&self) -> AppResult<Vec<SitemapEntry>> {
+        let mut entries = vec![SitemapEntry {
+            loc: self.url(""),
+            lastmod: None,
+        }];
+        let article_slugs = self
+            .article_dao
+            .list_slugs(Self::public_article_filter())
+            .await?;
+        entries.extend(
+            article_slugs
+                .into_iter()
+                .map(|(slug, update_time)| SitemapEntry {
+                    loc: self.url(&format!("article/{}", slug)),
+                    lastmod: Some(to_utc(update_time)),
+                }),
+        );
+        let tag_models = self
+            .tag_dao
+            .list_all(
+                TagFilterParam {
+                    status_type: Some(0),
+                    ..Default::default()
+                },
+                OrderParam {
+                    by: TagAttr::Id,
+                    ascending: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        entries.extend(tag_models.into_iter().map(|model| SitemapEntry {
+            loc: self.url(&format!("tag/{}", model.slug)),
+            lastmod: Some(to_utc(model.update_time)),
+        }));
+        let user_models = self
+            .user_dao
+            .list_all(
+                UserFilterParam {
+                    status_type: Some(1),
+                    ..Default::default()
+                },
+                OrderParam {
+                    by: UserAttr::Id,
+                    ascending: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        entries.extend(user_models.into_iter().map(|model| SitemapEntry {
+            loc: self.url(&format!("user/{}", model.id)),
+            lastmod: Some(to_utc(model.update_time)),
+        }));
+        Ok(entries)
+    }
+
+    async fn sitemap_document(&self, num: Option<usize>) -> AppResult<SiteDocument> {
+        let file = match num {
+            Some(num) => format!("sitemap/{}.xml", num),
+            None => "sitemap.xml".to_string(),
+        };
+        let cache_key = format!("site:{}:{}", file, self.generation().await?);
+        if let Some(document) = self.cache_utils.get(&cache_key).await? {
+            return Ok(document);
+        }
+
+        let entries = self.sitemap_entries().await?;
+        let part_loc = |num| self.url(&format!("sitemap/{}.xml", num));
+        let (body, updated) =
+            sitemap_xml(&entries, num, SITEMAP_URL_LIMIT, part_loc).ok_or_else(|| {
+                AppError::new(
+                    format!("Sitemap {} not found", file),
+                    AppErrorKind::ResourceNotFound,
+                )
+            })?;
+        let document = document("application/xml; charset=utf-8", body, updated);
+        self.cache_utils
+            .set(&cache_key, &document, Some(self.sitemap_cache_expire_sec))
+            .await?;
+        Ok(document)
+    }
+}
+
+#[async_trait]
+impl<D, R, G, U, C, M> SiteServiceTrait for SiteService<D, R, G, U, C, M>
+where
+    D: ArticleDataAccess + Sync + Send,
+    R: ArticleTagDataAccess + Sync + Send,
+    G: TagDataAccess + Sync + Send,
+    U: UserDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    M: RenderUtilsTrait + Sync + Send,
+{
+    async fn rss(&self, tag_slug: Option<String>) -> AppResult<SiteFeedResForm> {
+        self.feed(FeedFormat::Rss, tag_slug).await
+    }
+
+    async fn atom(&self, tag_slug: Option<String>) -> AppResult<SiteFeedResForm> {
+        self.feed(FeedFormat::Atom, tag_slug).await
+    }
+
+    async fn sitemap(&self) -> AppResult<SiteSitemapResForm> {
+        self.sitemap_document(None).await
+    }
+
+    async fn sitemap_part(&self, num: usize) -> AppResult<SiteSitemapResForm> {
+        self.sitemap_document(Some(num)).await
+    }
+
+    async fn robots(&self) -> AppResult<SiteRobotsResForm> {
+        let body = format!(
+            "User-agent: *\nDisallow: /api/\n\nSitemap: {}\n",
+            self.url("sitemap.xml")
+        );
+        Ok(document("text/plain; charset=utf-8", body, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn feed() -> (FeedChannel, Vec<FeedItem>) {
+        let time = Utc.with_ymd_and_hms(2024, 3, 5, 7, 8, 9).unwrap();
+        let channel = FeedChannel {
+            title: "Space".to_string(),
+            description: "a & b".to_string(),
+            link: "https://example.com/".to_string(),
+            self_link: "https://example.com/feed.xml".to_string(),
+            updated: time,
+        };
+        let items = vec![FeedItem {
+            title: "Rust <入门>".to_string(),
+            link: "https://example.com/article/rust".to_string(),
+            author: "stella".to_string(),
+            categories: vec!["编程".to_string()],
+            description_html: "<p>hello</p>".to_string(),
+            published: time,
+            updated: time,
+        }];
+        (channel, items)
+    }
+
+    #[test]
+    fn test_rss_xml() {
+        let (channel, items) = feed();
+        let xml = rss_xml(&channel, &items);
+        assert!(xml.contains("<description>a &amp; b</description>"));
+        assert!(xml.contains("<title>Rust &lt;入门&gt;</title>"));
+        assert!(xml.contains("<dc:creator>stella</dc:creator><category>编程</category>"));
+        assert!(xml.contains("<description>&lt;p&gt;hello&lt;/p&gt;</description>"));
+        assert!(xml.contains("<pubDate>Tue, 5 Mar 2024 07:08:09 +0000</pubDate>"));
+        assert!(xml.ends_with("</item></channel></rss>"));
+    }
+
+    #[test]
+    fn test_atom_xml() {
+        let (channel, items) = feed();
+        let xml = atom_xml(&channel, &items);
+        assert!(xml.contains("<id>https://example.com/feed.xml</id>"));
+        assert!(xml.contains("<updated>2024-03-05T07:08:09+00:00</updated>"));
+        assert!(xml.contains("<author><name>stella</name></author><category term=\"编程\"/>"));
+        assert!(xml.contains("<summary type=\"html\">&lt;p&gt;hello&lt;/p&gt;</summary>"));
+        assert!(xml.ends_with("</entry></feed>"));
+    }
+
+    #[test]
+    fn test_sitemap_xml() {
+        let time = Utc.with_ymd_and_hms(2024, 3, 5, 7, 8, 9).unwrap();
+        let entries: Vec<SitemapEntry> = (1..=5)
+            .map(|i| SitemapEntry {
+                loc: format!("https://example.com/article/{}?a&b", i),
+                lastmod: Some(time + chrono::Duration::days(i)),
+            })
+            .collect();
+        let part_loc = |num| format!("https://example.com/sitemap/{}.xml", num);
+
+        // fits in one file
+        let (xml, updated) = sitemap_xml(&entries, None, 5, part_loc).unwrap();
+        assert!(xml.contains("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">"));
+        assert!(xml.contains("<url><loc>https://example.com/article/1?a&amp;b</loc><lastmod>2024-03-06T07:08:09Z</lastmod></url>"));
+        assert_eq!(xml.matches("<url>").count(), 5);
+        assert_eq!(updated, entries[4].lastmod);
+        assert!(sitemap_xml(&entries, Some(1), 5, part_loc).is_none());
+
+        // split into an index
+        let (xml, _) = sitemap_xml(&entries, None, 2, part_loc).unwrap();
+        assert!(xml.contains("<sitemapindex "));
+        assert!(xml.contains("<sitemap><loc>https://example.com/sitemap/3.xml</loc><lastmod>2024-03-10T07:08:09Z</lastmod></sitemap>"));
+        assert_eq!(xml.matches("<sitemap>").count(), 3);
+        let (xml, updated) = sitemap_xml(&entries, Some(2), 2, part_loc).unwrap();
+        assert!(xml.contains("/article/3?"));
+        assert!(xml.contains("/article/4?"));
+        assert_eq!(xml.matches("<url>").count(), 2);
+        assert_eq!(updated, entries[3].lastmod);
+        assert!(sitemap_xml(&entries, Some(0), 2, part_loc).is_none());
+        assert!(sitemap_xml(&entries, Some(4), 2, part_loc).is_none());
+    }
+
+    #[test]
+    fn test_etag() {
+        assert_eq!(etag("a"), etag("a"));
+        assert_ne!(etag("a"), etag("b"));
+        assert_eq!(etag("a").len(), 34);
+    }
+}
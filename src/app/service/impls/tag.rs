@@ -0,0 +1,516 @@
+// ********************* import ********************* //
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+
+use super::{
+    super::{traits::tag::TagServiceTrait, types::tag::prelude::*},
+    article::ARTICLE_GENERATION_KEY,
+};
+use crate::app::{
+    common::prelude::*,
+    dao::{
+        prelude::{OrderParam, SlugHistoryDataAccess, TagDataAccess},
+        types::{slug_history::prelude::*, tag::prelude::*},
+    },
+    utils::prelude::{slugify, unique_slug, CacheUtilsTrait, TokenUtilsTrait},
+};
+
+// ********************* content ********************* //
+impl From<TagDataModel> for TagInfo {
+    fn from(model: TagDataModel) -> Self {
+        Self {
+            id: model.id,
+            name: model.name,
+            slug: model.slug,
+            parent_tag_id: model.parent_tag_id,
+            thumbnail_url: model.thumbnail_url,
+            status_type: model.status_type,
+            create_time: model.create_time,
+            update_time: model.update_time,
+            create_user_id: model.create_user_id,
+            update_user_id: model.update_user_id,
+        }
+    }
+}
+
+impl From<TagRestructureSummary> for TagRestructureInfo {
+    fn from(summary: TagRestructureSummary) -> Self {
+        Self {
+            moved_tags: summary.moved_tags,
+            moved_articles: summary.moved_articles,
+            dropped_articles: summary.dropped_articles,
+            deleted_tags: summary.deleted_tags,
+        }
+    }
+}
+
+// t_space_slug_history.resource_type
+const SLUG_RESOURCE_TYPE: i32 = SLUG_RESOURCE_TAG;
+
+// tags whose parent is not in `models` are unreachable and get dropped
+fn build_tag_tree(models: Vec<TagDataModel>) -> Vec<TagNode> {
+    fn build_nodes(
+        parent_tag_id: Option<i32>,
+        children_map: &mut HashMap<Option<i32>, Vec<TagDataModel>>,
+    ) -> Vec<TagNode> {
+        children_map
+            .remove(&parent_tag_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|model| {
+                let children = build_nodes(Some(model.id), children_map);
+                TagNode {
+                    tag_info: model.into(),
+                    children,
+                }
+            })
+            .collect()
+    }
+
+    let mut children_map: HashMap<Option<i32>, Vec<TagDataModel>> = HashMap::new();
+    for model in models {
+        children_map
+            .entry(model.parent_tag_id)
+            .or_default()
+            .push(model);
+    }
+    build_nodes(None, &mut children_map)
+}
+
+pub struct TagService<D, H, C, T>
+where
+    D: TagDataAccess + Sync + Send,
+    H: SlugHistoryDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    pub tag_dao: Arc<D>,
+    pub slug_history_dao: Arc<H>,
+    pub cache_utils: Arc<C>,
+    pub token_utils: Arc<T>,
+    pub max_depth: usize,
+}
+
+impl<D, H, C, T> TagService<D, H, C, T>
+where
+    D: TagDataAccess + Sync + Send,
+    H: SlugHistoryDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    pub fn new(
+        tag_dao: Arc<D>,
+        slug_history_dao: Arc<H>,
+        cache_utils: Arc<C>,
+        token_utils: Arc<T>,
+        cfg: &TagConfig,
+    ) -> Self {
+        Self {
+            tag_dao,
+            slug_history_dao,
+            cache_utils,
+            token_utils,
+            max_depth: cfg.max_depth,
+        }
+    }
+
+    async fn tree_inner(&self, filter: TagFilterParam) -> AppResult<TagTreeResForm> {
+        // siblings keep the order they were created in
+        let order = OrderParam {
+            by: TagAttr::Id,
+            ascending: true,
+            ..Default::default()
+        };
+        let tag_models = self.tag_dao.list_all(filter, order).await?;
+        Ok(TagTreeResForm {
+            tree: build_tag_tree(tag_models),
+        })
+    }
+
+    async fn get_alive(&self, id: i32) -> AppResult<TagDataModel> {
+        self.tag_dao
+            .get(TagFilterParam {
+                id: Some(id),
+                status_types: Some(vec![0, 1]),
+                ..Default::default()
+            })
+            .await
+    }
+
+    // return the tag itself followed by its ancestors, up to the top-level tag
+    async fn ancestors(&self, tag_model: TagDataModel) -> AppResult<Vec<TagDataModel>> {
+        let mut path = vec![tag_model];
+        while let Some(parent) = self.tag_dao.get_parent(path[path.len() - 1].id).await? {
+            if path.iter().any(|tag| tag.id == parent.id) {
+                return Err(AppError::new(
+                    format!("Cycle detected above tag {}", path[0].id),
+                    AppErrorKind::TagHierarchyConflict,
+                ));
+            }
+            path.push(parent);
+        }
+        Ok(path)
+    }
+
+    // slugs already used by other tags, now or in the past, are not available
+    async fn resolve_slug(&self, base: String, id: Option<i32>) -> AppResult<String> {
+        let mut taken: HashSet<String> = self
+            .tag_dao
+            .list_all(
+                TagFilterParam {
+                    slug_prefix: Some(base.clone()),
+                    ..Default::default()
+                },
+                OrderParam::<TagAttr>::default(),
+            )
+            .await?
+            .into_iter()
+            .filter(|model| Some(model.id) != id)
+            .map(|model| model.slug)
+            .collect();
+        taken.extend(
+            self.slug_history_dao
+                .list_all(
+                    SlugHistoryFilterParam {
+                        resource_type: Some(SLUG_RESOURCE_TYPE),
+                        slug_prefix: Some(base.clone()),
+                        ..Default::default()
+                    },
+                    OrderParam::<SlugHistoryAttr>::default(),
+                )
+                .await?
+                .into_iter()
+                .filter(|history| Some(history.resource_id) != id)
+                .map(|history| history.slug),
+        );
+        Ok(unique_slug(&base, &taken))
+    }
+
+    // articles carry tag names into the search index, feeds and sitemap
+    async fn touch_articles(&self) -> AppResult<()> {
+        self.cache_utils
+            .incr(ARTICLE_GENERATION_KEY, 1)
+            .await
+            .map(|_| ())
+    }
+
+    async fn restructure_res(
+        &self,
+        id: i32,
+        summary: TagRestructureSummary,
+    ) -> AppResult<TagRestructureResForm> {
+        self.touch_articles().await?;
+        let tag_model = self
+            .tag_dao
+            .get(TagFilterParam {
+                id: Some(id),
+                ..Default::default()
+            })
+            .await?;
+        Ok(TagRestructureResForm {
+            tag_info: tag_model.into(),
+            summary: summary.into(),
+        })
+    }
+
+    fn check_depth(&self, depth: usize) -> AppResult<()> {
+        if depth > self.max_depth {
+            return Err(AppError::new(
+                format!(
+                    "Tag depth {} exceeds the maximum depth {}",
+                    depth, self.max_depth
+                ),
+                AppErrorKind::TagHierarchyConflict,
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<D, H, C, T> TagServiceTrait for TagService<D, H, C, T>
+where
+    D: TagDataAccess + Sync + Send,
+    H: SlugHistoryDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    async fn tree(&self) -> AppResult<TagTreeResForm> {
+        self.tree_inner(TagFilterParam {
+            status_type: Some(0),
+            ..Default::default()
+        })
+        .await
+    }
+
+    async fn find(&self, id: i32) -> AppResult<TagFindResForm> {
+        let tag_model = self
+            .tag_dao
+            .get(TagFilterParam {
+                id: Some(id),
+                status_type: Some(0),
+                ..Default::default()
+            })
+            .await?;
+        Ok(TagFindResForm {
+            tag_info: tag_model.into(),
+        })
+    }
+
+    async fn find_by_slug(&self, slug: String) -> AppResult<TagSlugFindResForm> {
+        let tag_model = self
+            .tag_dao
+            .list_all(
+                TagFilterParam {
+                    slug: Some(slug.clone()),
+                    status_type: Some(0),
+                    ..Default::default()
+                },
+                OrderParam::<TagAttr>::default(),
+            )
+            .await?
+            .pop();
+        if let Some(tag_model) = tag_model {
+            return Ok(TagSlugFindResForm::Found(TagFindResForm {
+                tag_info: tag_model.into(),
+            }));
+        }
+        let history = self
+            .slug_history_dao
+            .get(SlugHistoryFilterParam {
+                resource_type: Some(SLUG_RESOURCE_TYPE),
+                slug: Some(slug),
+                ..Default::default()
+            })
+            .await?;
+        let tag_model = self
+            .tag_dao
+            .get(TagFilterParam {
+                id: Some(history.resource_id),
+                status_type: Some(0),
+                ..Default::default()
+            })
+            .await?;
+        Ok(TagSlugFindResForm::Moved(tag_model.slug))
+    }
+
+    async fn path(&self, id: i32) -> AppResult<TagPathResForm> {
+        let tag_model = self.tag_dao.get(TagFilterParam {
+            id: Some(id),
+            ..Default::default()
+        });
+        let mut path = self.ancestors(tag_model.await?).await?;
+        // a tag under a hidden ancestor is not publicly reachable
+        if path.iter().any(|tag| tag.status_type != 0) {
+            return Err(AppError::new(
+                format!("Tag {} is not publicly visible", id),
+                AppErrorKind::ResourceNotFound,
+            ));
+        }
+        path.reverse();
+        Ok(TagPathResForm {
+            path: path.into_iter().map(|model| model.into()).collect(),
+        })
+    }
+
+    async fn admin_tree(&self, token: &str) -> AppResult<TagAdminTreeResForm> {
+        self.token_utils.verify_token(token, &[1]).await?;
+        self.tree_inner(TagFilterParam {
+            status_types: Some(vec![0, 1]),
+            ..Default::default()
+        })
+        .await
+    }
+
+    async fn admin_create(
+        &self,
+        token: &str,
+        req_form: TagAdminCreateReqForm,
+    ) -> AppResult<TagAdminCreateResForm> {
+        let claims = self.token_utils.verify_token(token, &[1]).await?;
+        if let Some(parent_tag_id) = req_form.parent_tag_id {
+            let parent_model = self.get_alive(parent_tag_id).await?;
+            let parent_depth = self.ancestors(parent_model).await?.len();
+            self.check_depth(parent_depth + 1)?;
+        }
+        let slug = req_form
+            .slug
+            .unwrap_or_else(|| slugify(&req_form.name, "tag"));
+        let slug = self.resolve_slug(slug, None).await?;
+        let tag_model = self
+            .tag_dao
+            .create(TagCreateParam {
+                name: req_form.name,
+                slug,
+                parent_tag_id: req_form.parent_tag_id,
+                thumbnail_url: req_form.thumbnail_url,
+                create_user_id: claims.user_id,
+            })
+            .await?;
+        Ok(TagAdminCreateResForm {
+            tag_info: tag_model.into(),
+        })
+    }
+
+    async fn admin_edit(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: TagAdminEditReqForm,
+    ) -> AppResult<TagAdminEditResForm> {
+        let claims = self.token_utils.verify_token(token, &[1]).await?;
+        // tags in the trash only come back through restore
+        let filter = TagFilterParam {
+            id: Some(id),
+            status_types: Some(vec![0, 1]),
+            ..Default::default()
+        };
+        let old_slug = self.tag_dao.get(filter.clone()).await?.slug;
+        let slug = match req_form.slug {
+            Some(slug) if slug != old_slug => Some(self.resolve_slug(slug, Some(id)).await?),
+            _ => None,
+        };
+        let tag_model = self
+            .tag_dao
+            .edit(
+                filter,
+                TagUpdateParam {
+                    name: req_form.name,
+                    slug,
+                    thumbnail_url: req_form.thumbnail_url.map(Some),
+                    status_type: req_form.status_type,
+                    update_user_id: Some(claims.user_id),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        self.touch_articles().await?;
+        Ok(TagAdminEditResForm {
+            tag_info: tag_model.into(),
+        })
+    }
+
+    async fn admin_move(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: TagAdminMoveReqForm,
+    ) -> AppResult<TagAdminMoveResForm> {
+        let claims = self.token_utils.verify_token(token, &[1]).await?;
+        // the cycle and depth checks run in the same transaction as the move
+        let summary = self
+            .tag_dao
+            .move_subtree(id, req_form.parent_tag_id, self.max_depth, claims.user_id)
+            .await?;
+        self.restructure_res(id, summary).await
+    }
+
+    async fn admin_merge(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: TagAdminMergeReqForm,
+    ) -> AppResult<TagAdminMergeResForm> {
+        let claims = self.token_utils.verify_token(token, &[1]).await?;
+        let target_id = req_form.target_tag_id;
+        if target_id == id {
+            return Err(AppError::new(
+                format!("Tag {} cannot be merged into itself", id),
+                AppErrorKind::RequestParamInvalid,
+            ));
+        }
+        let summary = self
+            .tag_dao
+            .merge(id, target_id, self.max_depth, claims.user_id)
+            .await?;
+        self.restructure_res(target_id, summary).await
+    }
+
+    async fn admin_delete(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: TagAdminDeleteReqForm,
+    ) -> AppResult<TagAdminDeleteResForm> {
+        let claims = self.token_utils.verify_token(token, &[1]).await?;
+        // the children move one level up, which cannot exceed the maximum depth
+        if req_form.reparent_children {
+            let summary = self.tag_dao.dissolve(id, claims.user_id).await?;
+            return self.restructure_res(id, summary).await;
+        }
+        // children of a tag in the trash would drop out of the tree unnoticed
+        let child_num = self
+            .tag_dao
+            .count(TagFilterParam {
+                parent_tag_id: Some(Some(id)),
+                status_types: Some(vec![0, 1]),
+                ..Default::default()
+            })
+            .await?;
+        if child_num > 0 {
+            return Err(AppError::new(
+                format!("Tag {} still has {} children", id, child_num),
+                AppErrorKind::TagHierarchyConflict,
+            ));
+        }
+        let filter = TagFilterParam {
+            id: Some(id),
+            ..Default::default()
+        };
+        self.tag_dao.soft_delete(filter, claims.user_id).await?;
+        let summary = TagRestructureSummary {
+            deleted_tags: 1,
+            ..Default::default()
+        };
+        self.restructure_res(id, summary).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::prelude::DateTime;
+
+    use super::*;
+
+    fn tag_model(id: i32, parent_tag_id: Option<i32>) -> TagDataModel {
+        TagDataModel {
+            id,
+            name: format!("tag{}", id),
+            slug: format!("tag{}", id),
+            parent_tag_id,
+            thumbnail_url: None,
+            status_type: 0,
+            create_time: DateTime::default(),
+            update_time: DateTime::default(),
+            create_user_id: 1,
+            update_user_id: 1,
+            delete_time: None,
+            delete_user_id: None,
+        }
+    }
+
+    #[test]
+    fn test_build_tag_tree() {
+        let models = vec![
+            tag_model(1, None),
+            tag_model(2, Some(1)),
+            tag_model(3, Some(2)),
+            tag_model(4, Some(1)),
+            tag_model(5, None),
+            // parent is filtered out, so the tag is unreachable
+            tag_model(6, Some(7)),
+        ];
+        let tree = build_tag_tree(models);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].tag_info.id, 1);
+        assert_eq!(tree[0].children.len(), 2);
+        assert_eq!(tree[0].children[0].tag_info.id, 2);
+        assert_eq!(tree[0].children[0].children[0].tag_info.id, 3);
+        assert_eq!(tree[0].children[1].tag_info.id, 4);
+        assert!(tree[1].children.is_empty());
+    }
+}
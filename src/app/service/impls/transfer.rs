@@ -0,0 +1,550 @@
+// ********************* import ********************* //
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Cursor, Read, Write},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use chrono::Local;
+use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use super::{
+    super::{traits::transfer::TransferServiceTrait, types::transfer::prelude::*},
+    article::ARTICLE_GENERATION_KEY,
+};
+use crate::app::{
+    common::prelude::*,
+    dao::{
+        prelude::{
+            ArticleDataAccess, ArticleRevisionDataAccess, ArticleTagDataAccess, OrderParam,
+            TagDataAccess, UserDataAccess,
+        },
+        types::{article::prelude::*, article_tag::prelude::*, tag::prelude::*, user::prelude::*},
+    },
+    utils::prelude::{
+        format_markdown, parse_markdown, slugify, unique_slug, CacheUtilsTrait, FrontMatter,
+        MarkdownDoc, RenderUtilsTrait, TokenUtilsTrait,
+    },
+};
+
+// ********************* content ********************* //
+fn status_name(status_type: i32) -> &'static str {
+    match status_type {
+        0 => "draft",
+        1 => "published",
+        2 => "hidden",
+        _ => "deleted",
+    }
+}
+
+// "publish" and "private" come from Hexo and WordPress exports
+fn status_type(status_name: &str) -> Option<i32> {
+    match status_name.to_ascii_lowercase().as_str() {
+        "draft" => Some(0),
+        "published" | "publish" => Some(1),
+        "hidden" | "private" => Some(2),
+        "deleted" => Some(3),
+        _ => None,
+    }
+}
+
+fn write_zip(files: Vec<(String, String)>) -> AppResult<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, text) in files {
+        writer
+            .start_file(name, options)
+            .with_err_kind(AppErrorKind::default())?;
+        writer
+            .write_all(text.as_bytes())
+            .with_err_kind(AppErrorKind::default())?;
+    }
+    let cursor = writer.finish().with_err_kind(AppErrorKind::default())?;
+    Ok(cursor.into_inner())
+}
+
+// the longest article content, larger files are skipped instead of read into memory
+const FILE_MAX_SIZE: u64 = 16_777_215;
+
+// (file name, text) of every markdown file, the text is an error when it is not utf-8 or
+// larger than the given size
+fn read_zip(data: Vec<u8>, file_max_size: u64) -> AppResult<Vec<(String, Result<String, String>)>> {
+    let mut archive = ZipArchive::new(Cursor::new(data)).wrap(
+        "Request body is not a zip archive",
+        AppErrorKind::RequestParamInvalid,
+    )?;
+    let mut files = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).wrap(
+            "Failed to read zip archive",
+            AppErrorKind::RequestParamInvalid,
+        )?;
+        let name = file.name().to_string();
+        let lower_name = name.to_ascii_lowercase();
+        let is_markdown = lower_name.ends_with(".md") || lower_name.ends_with(".markdown");
+        if file.is_dir() || !is_markdown || name.starts_with("__MACOSX/") {
+            continue;
+        }
+        let too_large = format!("File is larger than {} bytes", file_max_size);
+        if file.size() > file_max_size {
+            files.push((name, Err(too_large)));
+            continue;
+        }
+        // the declared size may lie, never read more than one byte past the limit
+        let mut text = String::new();
+        let text = match (&mut file)
+            .take(file_max_size + 1)
+            .read_to_string(&mut text)
+        {
+            Ok(len) if len as u64 > file_max_size => Err(too_large),
+            Ok(_) => Ok(text),
+            Err(e) => Err(e.to_string()),
+        };
+        files.push((name, text));
+    }
+    Ok(files)
+}
+
+enum ImportOutcome {
+    Created,
+    Updated,
+    Skipped(String),
+}
+
+// lookups shared by all files of one import, tags created along the way are added, tags
+// and users in the trash are left out but their slugs stay taken
+struct ImportContext {
+    tag_ids: HashMap<String, i32>,
+    tag_slugs: HashMap<String, i32>,
+    taken_tag_slugs: HashSet<String>,
+    user_ids: HashMap<String, i32>,
+    created_tags: u64,
+}
+
+fn live_tag_filter() -> TagFilterParam {
+    TagFilterParam {
+        status_types: Some(vec![0, 1]),
+        ..Default::default()
+    }
+}
+
+fn live_user_filter() -> UserFilterParam {
+    UserFilterParam {
+        status_types: Some(vec![0, 1, 2]),
+        ..Default::default()
+    }
+}
+
+pub struct TransferService<D, V, R, G, U, C, M, T>
+where
+    D: ArticleDataAccess + Sync + Send,
+    V: ArticleRevisionDataAccess + Sync + Send,
+    R: ArticleTagDataAccess + Sync + Send,
+    G: TagDataAccess + Sync + Send,
+    U: UserDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    M: RenderUtilsTrait + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    pub article_dao: Arc<D>,
+    pub article_revision_dao: Arc<V>,
+    pub article_tag_dao: Arc<R>,
+    pub tag_dao: Arc<G>,
+    pub user_dao: Arc<U>,
+    pub cache_utils: Arc<C>,
+    pub render_utils: Arc<M>,
+    pub token_utils: Arc<T>,
+}
+
+impl<D, V, R, G, U, C, M, T> TransferService<D, V, R, G, U, C, M, T>
+where
+    D: ArticleDataAccess + Sync + Send,
+    V: ArticleRevisionDataAccess + Sync + Send,
+    R: ArticleTagDataAccess + Sync + Send,
+    G: TagDataAccess + Sync + Send,
+    U: UserDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    M: RenderUtilsTrait + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        article_dao: Arc<D>,
+        article_revision_dao: Arc<V>,
+        article_tag_dao: Arc<R>,
+        tag_dao: Arc<G>,
+        user_dao: Arc<U>,
+        cache_utils: Arc<C>,
+        render_utils: Arc<M>,
+        token_utils: Arc<T>,
+    ) -> Self {
+        Self {
+            article_dao,
+            article_revision_dao,
+            article_tag_dao,
+            tag_dao,
+            user_dao,
+            cache_utils,
+            render_utils,
+            token_utils,
+        }
+    }
+
+    // also used by the command line, which has no token
+    pub async fn export_zip(&self) -> AppResult<Vec<u8>> {
+        let tag_names: HashMap<i32, String> = self
+            .tag_dao
+            .list_all(live_tag_filter(), OrderParam::<TagAttr>::default())
+            .await?
+            .into_iter()
+            .map(|model| (model.id, model.name))
+            .collect();
+        let mut article_tags: HashMap<i32, Vec<String>> = HashMap::new();
+        for relation in self
+            .article_tag_dao
+            .list_all(ArticleTagFilterParam::default())
+            .await?
+        {
+            if let Some(name) = tag_names.get(&relation.tag_id) {
+                article_tags
+                    .entry(relation.article_id)
+                    .or_default()
+                    .push(name.clone());
+            }
+        }
+        let usernames: HashMap<i32, String> = self
+            .user_dao
+            .list_all(live_user_filter(), OrderParam::<UserAttr>::default())
+            .await?
+            .into_iter()
+            .map(|model| (model.id, model.username))
+            .collect();
+        let article_models = self
+            .article_dao
+            .list_all(
+                ArticleFilterParam::default(),
+                OrderParam {
+                    by: ArticleAttr::Id,
+                    ascending: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        let mut files = Vec::with_capacity(article_models.len());
+        for model in article_models {
+            let doc = MarkdownDoc {
+                front_matter: FrontMatter {
+                    title: Some(model.title),
+                    slug: Some(model.slug.clone()),
+                    description: Some(model.description).filter(|d| !d.is_empty()),
+                    tags: article_tags.remove(&model.id).unwrap_or_default(),
+                    date: Some(model.create_time),
+                    updated: Some(model.update_time),
+                    status: Some(status_name(model.status_type).to_string()),
+                    author: usernames.get(&model.create_user_id).cloned(),
+                },
+                content: model.content,
+            };
+            files.push((format!("{}.md", model.slug), format_markdown(&doc)?));
+        }
+        write_zip(files)
+    }
+
+    // files without an author of their own are attributed to `user_id`
+    pub async fn import_zip(
+        &self,
+        data: Vec<u8>,
+        upsert: bool,
+        user_id: i32,
+    ) -> AppResult<TransferImportResForm> {
+        let files = read_zip(data, FILE_MAX_SIZE)?;
+        let tag_models = self
+            .tag_dao
+            .list_all(TagFilterParam::default(), OrderParam::<TagAttr>::default())
+            .await?;
+        let live_tag_models = tag_models.iter().filter(|model| model.status_type != 2);
+        let mut ctx = ImportContext {
+            tag_ids: live_tag_models
+                .clone()
+                .map(|model| (model.name.clone(), model.id))
+                .collect(),
+            tag_slugs: live_tag_models
+                .map(|model| (model.slug.clone(), model.id))
+                .collect(),
+            taken_tag_slugs: tag_models.into_iter().map(|model| model.slug).collect(),
+            user_ids: self
+                .user_dao
+                .list_all(live_user_filter(), OrderParam::<UserAttr>::default())
+                .await?
+                .into_iter()
+                .map(|model| (model.username, model.id))
+                .collect(),
+            created_tags: 0,
+        };
+        let mut res_form = TransferImportResForm::default();
+        for (file, text) in files {
+            let outcome = match text {
+                Ok(text) => match parse_markdown(&file, &text) {
+                    Ok(doc) => self
+                        .import_doc(doc, upsert, user_id, &mut ctx)
+                        .await
+                        .unwrap_or_else(|e| {
+                            tracing::warn!("Failed to import {}: {:?}", file, e);
+                            ImportOutcome::Skipped(e.cause.to_string())
+                        }),
+                    Err(e) => ImportOutcome::Skipped(e.cause.to_string()),
+                },
+                Err(e) => ImportOutcome::Skipped(format!("Not a utf-8 text file, {}", e)),
+            };
+            match outcome {
+                ImportOutcome::Created => res_form.created += 1,
+                ImportOutcome::Updated => res_form.updated += 1,
+                ImportOutcome::Skipped(reason) => {
+                    res_form.skipped.push(TransferSkipInfo { file, reason })
+                }
+            }
+        }
+        res_form.created_tags = ctx.created_tags;
+        if res_form.created + res_form.updated > 0 {
+            self.cache_utils.incr(ARTICLE_GENERATION_KEY, 1).await?;
+        }
+        Ok(res_form)
+    }
+
+    async fn import_doc(
+        &self,
+        doc: MarkdownDoc,
+        upsert: bool,
+        user_id: i32,
+        ctx: &mut ImportContext,
+    ) -> AppResult<ImportOutcome> {
+        let front_matter = doc.front_matter;
+        let title = front_matter.title.unwrap_or_default();
+        let slug = slugify(front_matter.slug.as_deref().unwrap_or(&title), "article");
+        let status_type = match front_matter.status.as_deref() {
+            Some(status) => match status_type(status) {
+                Some(status_type) => status_type,
+                None => {
+                    return Ok(ImportOutcome::Skipped(format!(
+                        "Unknown status: {}",
+                        status
+                    )))
+                }
+            },
+            None => 1,
+        };
+        if status_type == 3 {
+            return Ok(ImportOutcome::Skipped(
+                "Deleted articles are not imported".to_string(),
+            ));
+        }
+        let existing = self
+            .article_dao
+            .list_all(
+                ArticleFilterParam {
+                    slug: Some(slug.clone()),
+                    ..Default::default()
+                },
+                OrderParam::<ArticleAttr>::default(),
+            )
+            .await?
+            .pop();
+        // overwriting would bring the article back without a restore
+        if existing
+            .as_ref()
+            .is_some_and(|model| model.status_type == 3)
+        {
+            return Ok(ImportOutcome::Skipped(format!(
+                "Slug belongs to an article in the trash: {}",
+                slug
+            )));
+        }
+        if existing.is_some() && !upsert {
+            return Ok(ImportOutcome::Skipped(format!(
+                "Slug already exists: {}",
+                slug
+            )));
+        }
+        // authors in the trash are not in the lookup, their files go to `user_id`
+        let create_user_id = front_matter
+            .author
+            .and_then(|author| ctx.user_ids.get(&author).copied())
+            .unwrap_or(user_id);
+        let description = match front_matter.description {
+            Some(description) => description,
+            None => self.render_utils.excerpt(&doc.content),
+        };
+        let mut tag_ids = Vec::with_capacity(front_matter.tags.len());
+        for name in &front_matter.tags {
+            tag_ids.push(self.resolve_tag(name, user_id, ctx).await?);
+        }
+
+        // published documents count as published on their date
+        let publish_time = (status_type == 1).then(|| {
+            front_matter
+                .date
+                .unwrap_or_else(|| Local::now().naive_local())
+        });
+        // the database fills in both times on create, so they are set by an update
+        let mut update_param = ArticleUpdateParam {
+            create_time: front_matter.date,
+            update_time: front_matter.updated.or(front_matter.date),
+            ..Default::default()
+        };
+        let (id, outcome) = match existing {
+            Some(model) => {
+                update_param = ArticleUpdateParam {
+                    title: Some(title.clone()),
+                    description: Some(description),
+                    content: Some(doc.content.clone()),
+                    status_type: Some(status_type),
+                    publish_time: publish_time.filter(|_| model.publish_time.is_none()),
+                    update_user_id: Some(user_id),
+                    ..update_param
+                };
+                (model.id, ImportOutcome::Updated)
+            }
+            None => {
+                let model = self
+                    .article_dao
+                    .create(ArticleCreateParam {
+                        title: title.clone(),
+                        slug,
+                        description,
+                        content: doc.content.clone(),
+                        is_top: false,
+                        status_type,
+                        publish_at: None,
+                        unpublish_at: None,
+                        publish_time,
+                        create_user_id,
+                    })
+                    .await?;
+                (model.id, ImportOutcome::Created)
+            }
+        };
+        match outcome {
+            // overwritten content is kept as a revision like any other edit
+            ImportOutcome::Updated => {
+                self.article_revision_dao
+                    .update_article(id, update_param)
+                    .await?;
+            }
+            _ if update_param.update_time.is_some() => {
+                self.article_dao
+                    .update(
+                        ArticleFilterParam {
+                            id: Some(id),
+                            ..Default::default()
+                        },
+                        update_param,
+                    )
+                    .await?;
+            }
+            _ => {}
+        }
+        self.article_tag_dao.replace_tags(id, tag_ids).await?;
+
+        Ok(outcome)
+    }
+
+    // match by name, then by slug, and create the tag when neither is taken
+    async fn resolve_tag(
+        &self,
+        name: &str,
+        user_id: i32,
+        ctx: &mut ImportContext,
+    ) -> AppResult<i32> {
+        if let Some(id) = ctx.tag_ids.get(name) {
+            return Ok(*id);
+        }
+        let slug = slugify(name, "tag");
+        if let Some(id) = ctx.tag_slugs.get(&slug) {
+            return Ok(*id);
+        }
+        let tag_model = self
+            .tag_dao
+            .create(TagCreateParam {
+                name: name.to_string(),
+                slug: unique_slug(&slug, &ctx.taken_tag_slugs),
+                parent_tag_id: None,
+                thumbnail_url: None,
+                create_user_id: user_id,
+            })
+            .await?;
+        ctx.tag_ids.insert(tag_model.name, tag_model.id);
+        ctx.taken_tag_slugs.insert(tag_model.slug.clone());
+        ctx.tag_slugs.insert(tag_model.slug, tag_model.id);
+        ctx.created_tags += 1;
+        Ok(tag_model.id)
+    }
+}
+
+#[async_trait]
+impl<D, V, R, G, U, C, M, T> TransferServiceTrait for TransferService<D, V, R, G, U, C, M, T>
+where
+    D: ArticleDataAccess + Sync + Send,
+    V: ArticleRevisionDataAccess + Sync + Send,
+    R: ArticleTagDataAccess + Sync + Send,
+    G: TagDataAccess + Sync + Send,
+    U: UserDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    M: RenderUtilsTrait + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    async fn export(&self, token: &str) -> AppResult<TransferExportResForm> {
+        self.token_utils.verify_token(token, &[1]).await?;
+        self.export_zip().await
+    }
+
+    async fn import(
+        &self,
+        token: &str,
+        req_form: TransferImportReqForm,
+        data: Vec<u8>,
+    ) -> AppResult<TransferImportResForm> {
+        let claims = self.token_utils.verify_token(token, &[1]).await?;
+        self.import_zip(data, req_form.upsert, claims.user_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_type() {
+        for status_type_value in 0..=3 {
+            assert_eq!(
+                status_type(status_name(status_type_value)),
+                Some(status_type_value)
+            );
+        }
+        assert_eq!(status_type("Publish"), Some(1));
+        assert_eq!(status_type("pending"), None);
+    }
+
+    #[test]
+    fn test_write_and_read_zip() {
+        let data = write_zip(vec![
+            ("a.md".to_string(), "# a".to_string()),
+            ("b.txt".to_string(), "b".to_string()),
+            ("__MACOSX/a.md".to_string(), "".to_string()),
+            ("posts/c.markdown".to_string(), "# c".to_string()),
+        ])
+        .unwrap();
+        let files = read_zip(data.clone(), FILE_MAX_SIZE).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                ("a.md".to_string(), Ok("# a".to_string())),
+                ("posts/c.markdown".to_string(), Ok("# c".to_string())),
+            ]
+        );
+        // files larger than the limit are not read
+        let files = read_zip(data, 2).unwrap();
+        assert!(files.iter().all(|(_, text)| text.is_err()));
+        assert!(read_zip(b"not a zip".to_vec(), FILE_MAX_SIZE).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+// ********************* import ********************* //
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Local;
+
+use super::{
+    super::{traits::trash::TrashServiceTrait, types::trash::prelude::*},
+    article::ARTICLE_GENERATION_KEY,
+};
+use crate::app::{
+    common::prelude::*,
+    dao::{
+        prelude::{
+            ArticleDataAccess, OrderParam, PaginateParam, SlugHistoryDataAccess, TagDataAccess,
+            UserDataAccess,
+        },
+        types::{article::prelude::*, tag::prelude::*, user::prelude::*},
+    },
+    utils::prelude::{CacheUtilsTrait, Page, TokenUtilsTrait},
+};
+
+// ********************* content ********************* //
+impl From<ArticleDataModel> for TrashItemInfo {
+    fn from(model: ArticleDataModel) -> Self {
+        Self {
+            kind: TrashKind::Article,
+            id: model.id,
+            name: model.title,
+            status_type: model.status_type,
+            delete_time: model.delete_time,
+            delete_user_id: model.delete_user_id,
+        }
+    }
+}
+
+impl From<TagDataModel> for TrashItemInfo {
+    fn from(model: TagDataModel) -> Self {
+        Self {
+            kind: TrashKind::Tag,
+            id: model.id,
+            name: model.name,
+            status_type: model.status_type,
+            delete_time: model.delete_time,
+            delete_user_id: model.delete_user_id,
+        }
+    }
+}
+
+impl From<UserDataModel> for TrashItemInfo {
+    fn from(model: UserDataModel) -> Self {
+        Self {
+            kind: TrashKind::User,
+            id: model.id,
+            name: model.username,
+            status_type: model.status_type,
+            delete_time: model.delete_time,
+            delete_user_id: model.delete_user_id,
+        }
+    }
+}
+
+pub struct TrashService<D, G, U, H, C, T>
+where
+    D: ArticleDataAccess + Sync + Send,
+    G: TagDataAccess + Sync + Send,
+    U: UserDataAccess + Sync + Send,
+    H: SlugHistoryDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    pub article_dao: Arc<D>,
+    pub tag_dao: Arc<G>,
+    pub user_dao: Arc<U>,
+    pub slug_history_dao: Arc<H>,
+    pub cache_utils: Arc<C>,
+    pub token_utils: Arc<T>,
+    pub retention_days: u64,
+    pub purge_interval_sec: u64,
+}
+
+impl<D, G, U, H, C, T> TrashService<D, G, U, H, C, T>
+where
+    D: ArticleDataAccess + Sync + Send,
+    G: TagDataAccess + Sync + Send,
+    U: UserDataAccess + Sync + Send,
+    H: SlugHistoryDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    pub fn new(
+        article_dao: Arc<D>,
+        tag_dao: Arc<G>,
+        user_dao: Arc<U>,
+        slug_history_dao: Arc<H>,
+        cache_utils: Arc<C>,
+        token_utils: Arc<T>,
+        cfg: &TrashConfig,
+    ) -> Self {
+        Self {
+            article_dao,
+            tag_dao,
+            user_dao,
+            slug_history_dao,
+            cache_utils,
+            token_utils,
+            retention_days: cfg.retention_days,
+            purge_interval_sec: cfg.purge_interval_sec.max(1),
+        }
+    }
+
+    // purging twice is harmless, so every instance runs it without a lock
+    pub async fn run_purger(&self) {
+        let mut interval = tokio::time::interval(Duration::from_secs(self.purge_interval_sec));
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.purge_expired().await {
+                tracing::error!("Failed to purge the trash: {:?}", e);
+            }
+        }
+    }
+
+    // users go last, purging a user takes along everything they created
+    pub async fn purge_expired(&self) -> AppResult<TrashPurgeResForm> {
+        let deleted_before =
+            Local::now().naive_local() - chrono::Duration::days(self.retention_days as i64);
+        let res_form = TrashPurgeResForm {
+            articles: self.article_dao.purge(deleted_before).await?,
+            tags: self.tag_dao.purge(deleted_before).await?,
+            users: self.user_dao.purge(deleted_before).await?,
+        };
+        // the slug history has no foreign key to follow the purged rows
+        self.slug_history_dao.delete_orphans().await?;
+        // purged tags drop out of articles, and articles of purged users are gone as well
+        if res_form.articles + res_form.tags + res_form.users > 0 {
+            self.cache_utils.incr(ARTICLE_GENERATION_KEY, 1).await?;
+        }
+        Ok(res_form)
+    }
+
+    async fn restore_tag(&self, id: i32) -> AppResult<TagDataModel> {
+        let filter = TagFilterParam {
+            id: Some(id),
+            ..Default::default()
+        };
+        // a tag under a parent in the trash would not show up in the tree
+        if let Some(parent) = self.tag_dao.get_parent(id).await? {
+            if parent.status_type == 2 {
+                return Err(AppError::new(
+                    format!("Parent tag {} of tag {} is in the trash", parent.id, id),
+                    AppErrorKind::TagHierarchyConflict,
+                ));
+            }
+        }
+        self.tag_dao.restore(filter.clone()).await?;
+        self.tag_dao.get(filter).await
+    }
+}
+
+#[async_trait]
+impl<D, G, U, H, C, T> TrashServiceTrait for TrashService<D, G, U, H, C, T>
+where
+    D: ArticleDataAccess + Sync + Send,
+    G: TagDataAccess + Sync + Send,
+    U: UserDataAccess + Sync + Send,
+    H: SlugHistoryDataAccess + Sync + Send,
+    C: CacheUtilsTrait + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    async fn search(
+        &self,
+        kind: TrashKind,
+        token: &str,
+        req_form: TrashSearchReqForm,
+    ) -> AppResult<TrashSearchResForm> {
+        self.token_utils.verify_token(token, &[1]).await?;
+        let paginate = PaginateParam {
+            page_num: req_form.page_num,
+            page_size: req_form.page_size,
+        };
+        let (record_total, item_infos): (u64, Vec<TrashItemInfo>) = match kind {
+            TrashKind::Article => {
+                let filter = ArticleFilterParam {
+                    status_type: Some(3),
+                    ..Default::default()
+                };
+                let order = OrderParam {
+                    by: ArticleAttr::DeleteTime,
+                    ascending: false,
+                    ..Default::default()
+                };
+                (
+                    self.article_dao.count(filter.clone()).await?,
+                    self.article_dao
+                        .list(filter, order, paginate.clone())
+                        .await?
+                        .into_iter()
+                        .map(|model| model.into())
+                        .collect(),
+                )
+            }
+            TrashKind::Tag => {
+                let filter = TagFilterParam {
+                    status_type: Some(2),
+                    ..Default::default()
+                };
+                let order = OrderParam {
+                    by: TagAttr::DeleteTime,
+                    ascending: false,
+                    ..Default::default()
+                };
+                (
+                    self.tag_dao.count(filter.clone()).await?,
+                    self.tag_dao
+                        .list(filter, order, paginate.clone())
+                        .await?
+                        .into_iter()
+                        .map(|model| model.into())
+                        .collect(),
+                )
+            }
+            TrashKind::User => {
+                let filter = UserFilterParam {
+                    status_type: Some(3),
+                    ..Default::default()
+                };
+                let order = OrderParam {
+                    by: UserAttr::DeleteTime,
+                    ascending: false,
+                    ..Default::default()
+                };
+                (
+                    self.user_dao.count(filter.clone()).await?,
+                    self.user_dao
+                        .list(filter, order, paginate.clone())
+                        .await?
+                        .into_iter()
+                        .map(|model| model.into())
+                        .collect(),
+                )
+            }
+        };
+        Page::new(
+            paginate.page_num,
+            paginate.page_size,
+            record_total,
+            item_infos,
+        )
+        .wrap(
+            "Invalid pagination parameters",
+            AppErrorKind::RequestParamInvalid,
+        )
+    }
+
+    async fn restore(
+        &self,
+        kind: TrashKind,
+        id: i32,
+        token: &str,
+    ) -> AppResult<TrashRestoreResForm> {
+        self.token_utils.verify_token(token, &[1]).await?;
+        let item_info = match kind {
+            TrashKind::Article => {
+                let filter = ArticleFilterParam {
+                    id: Some(id),
+                    ..Default::default()
+                };
+                self.article_dao.restore(filter.clone()).await?;
+                self.article_dao.get(filter).await?.into()
+            }
+            TrashKind::Tag => self.restore_tag(id).await?.into(),
+            TrashKind::User => {
+                let filter = UserFilterParam {
+                    id: Some(id),
+                    ..Default::default()
+                };
+                self.user_dao.restore(filter.clone()).await?;
+                self.user_dao.get(filter).await?.into()
+            }
+        };
+        // a restored article, tag or author may show up in public lists again
+        self.cache_utils.incr(ARTICLE_GENERATION_KEY, 1).await?;
+        Ok(item_info)
+    }
+
+    async fn purge(&self, token: &str) -> AppResult<TrashPurgeResForm> {
+        self.token_utils.verify_token(token, &[1]).await?;
+        self.purge_expired().await
+    }
+}
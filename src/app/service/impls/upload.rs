@@ -0,0 +1,476 @@
+// ********************* import ********************* //
+use std::{collections::BTreeMap, sync::Arc};
+
+use async_trait::async_trait;
+
+use super::super::{traits::upload::UploadServiceTrait, types::upload::prelude::*};
+use crate::app::{
+    common::prelude::*,
+    config::ServiceConfig,
+    dao::{
+        prelude::{OrderParam, PaginateParam, UploadDataAccess},
+        types::upload::prelude::*,
+    },
+    utils::prelude::{
+        is_valid_storage_key, mime_extension, sniff_mime, storage_key, ImageConfig,
+        ImageUtilsTrait, Page, StorageBackend, StorageConfig, TokenUtilsTrait,
+    },
+};
+
+// ********************* content ********************* //
+pub struct UploadService<D, S, I, T>
+where
+    D: UploadDataAccess + Sync + Send,
+    S: StorageBackend + Sync + Send,
+    I: ImageUtilsTrait + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    pub upload_dao: Arc<D>,
+    pub storage_backend: Arc<S>,
+    pub image_utils: Arc<I>,
+    pub token_utils: Arc<T>,
+    // file urls are this prefix followed by the storage key
+    pub public_url: String,
+    pub max_size: usize,
+    pub allowed_mime_types: Vec<String>,
+    pub cache_max_age_sec: u64,
+    pub presign_expire_sec: u64,
+}
+
+impl<D, S, I, T> UploadService<D, S, I, T>
+where
+    D: UploadDataAccess + Sync + Send,
+    S: StorageBackend + Sync + Send,
+    I: ImageUtilsTrait + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    pub fn new(
+        upload_dao: Arc<D>,
+        storage_backend: Arc<S>,
+        image_utils: Arc<I>,
+        token_utils: Arc<T>,
+        service_cfg: &ServiceConfig,
+        storage_cfg: &StorageConfig,
+        upload_cfg: &UploadConfig,
+    ) -> Self {
+        let public_url = match storage_cfg.public_url.is_empty() {
+            true => app_file_url(service_cfg),
+            false => storage_cfg.public_url.trim_end_matches('/').to_string(),
+        };
+        Self {
+            upload_dao,
+            storage_backend,
+            image_utils,
+            token_utils,
+            public_url,
+            max_size: upload_cfg.max_size,
+            allowed_mime_types: upload_cfg.allowed_mime_types.clone(),
+            cache_max_age_sec: upload_cfg.cache_max_age_sec,
+            presign_expire_sec: storage_cfg.presign_expire_sec.max(1),
+        }
+    }
+
+    fn info(&self, model: UploadDataModel) -> UploadInfo {
+        UploadInfo {
+            id: model.id,
+            url: format!("{}/{}", self.public_url, model.storage_key),
+            file_name: model.file_name,
+            mime_type: model.mime_type,
+            size: model.size,
+            create_time: model.create_time,
+            create_user_id: model.create_user_id,
+        }
+    }
+
+    fn check_size(&self, size: usize) -> AppResult<()> {
+        match size > self.max_size {
+            true => Err(AppError::new(
+                format!(
+                    "Uploaded file has {} bytes, at most {} allowed",
+                    size, self.max_size
+                ),
+                AppErrorKind::PayloadTooLarge,
+            )),
+            false => Ok(()),
+        }
+    }
+
+    fn check_mime_type(&self, file_name: &str, mime_type: Option<&str>) -> AppResult<()> {
+        match mime_type {
+            Some(mime_type) if self.allowed_mime_types.iter().any(|t| t == mime_type) => Ok(()),
+            mime_type => Err(AppError::new(
+                format!(
+                    "Uploaded file {} is of type {}",
+                    file_name,
+                    mime_type.unwrap_or("unknown")
+                ),
+                AppErrorKind::UnsupportedMediaType,
+            )),
+        }
+    }
+
+    async fn store(&self, user_id: i32, file: UploadFile) -> AppResult<UploadInfo> {
+        if file.data.is_empty() {
+            return Err(AppError::new(
+                "Uploaded file is empty",
+                AppErrorKind::RequestParamInvalid,
+            ));
+        }
+        self.check_size(file.data.len())?;
+        let mime_type = sniff_mime(&file.data);
+        self.check_mime_type(&file.file_name, mime_type)?;
+        let mime_type = mime_type.unwrap_or_default();
+        // the key is taken after stripping, so the same photo with other exif is stored once
+        let data = self
+            .image_utils
+            .strip_metadata(file.data, mime_type)
+            .await?;
+        let key = storage_key(&data, mime_type);
+        let size = data.len() as i64;
+        self.storage_backend.put(&key, data, mime_type).await?;
+        // every upload gets its own record even if the content is already stored
+        let model = self
+            .upload_dao
+            .create(UploadCreateParam {
+                storage_key: key,
+                file_name: file.file_name,
+                mime_type: mime_type.to_string(),
+                size,
+                create_user_id: user_id,
+            })
+            .await?;
+        Ok(self.info(model))
+    }
+
+    // Some when the storage serves the file itself
+    fn redirect(&self, key: &str) -> AppResult<Option<UploadFileResForm>> {
+        let url = self
+            .storage_backend
+            .presign_get(key, self.presign_expire_sec)?;
+        Ok(url.map(|url| UploadFileResForm::Redirect {
+            url,
+            cache_max_age_sec: self.presign_expire_sec / 2,
+        }))
+    }
+
+    async fn generate_variant(
+        &self,
+        key: &str,
+        size: u32,
+        variant_key: &str,
+    ) -> AppResult<Vec<u8>> {
+        let data = self.storage_backend.get(key).await?.ok_or_else(|| {
+            AppError::new(
+                format!("File {} not found", key),
+                AppErrorKind::ResourceNotFound,
+            )
+        })?;
+        let data = self.image_utils.variant(data, size).await?;
+        self.storage_backend
+            .put(
+                variant_key,
+                data.clone(),
+                self.image_utils.variant_mime_type(),
+            )
+            .await?;
+        Ok(data)
+    }
+}
+
+#[async_trait]
+impl<D, S, I, T> UploadServiceTrait for UploadService<D, S, I, T>
+where
+    D: UploadDataAccess + Sync + Send,
+    S: StorageBackend + Sync + Send,
+    I: ImageUtilsTrait + Sync + Send,
+    T: TokenUtilsTrait + Sync + Send,
+{
+    async fn create(&self, token: &str, file: UploadFile) -> AppResult<UploadCreateResForm> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        self.store(claims.user_id, file).await
+    }
+
+    async fn presign(
+        &self,
+        token: &str,
+        req_form: UploadPresignReqForm,
+    ) -> AppResult<UploadPresignResForm> {
+        self.token_utils.verify_token(token, &[0, 1]).await?;
+        // only what the client declares, the content is checked on confirm
+        self.check_size(req_form.size)?;
+        self.check_mime_type(&req_form.file_name, Some(&req_form.mime_type))?;
+        let upload_key = format!(
+            "{}{:032x}",
+            UPLOAD_STAGING_KEY_PREFIX,
+            rand::random::<u128>()
+        );
+        let upload_url = self
+            .storage_backend
+            .presign_put(
+                &upload_key,
+                &req_form.mime_type,
+                req_form.size as u64,
+                self.presign_expire_sec,
+            )?
+            .ok_or_else(|| {
+                AppError::new(
+                    "Storage backend does not support direct upload",
+                    AppErrorKind::NotImplemented,
+                )
+            })?;
+        Ok(UploadPresignResForm {
+            upload_key,
+            upload_url,
+            content_type: req_form.mime_type,
+            expire_sec: self.presign_expire_sec,
+        })
+    }
+
+    // the client may have sent anything under the staging key, so it is read back and goes
+    // through the same checks as a regular upload, a staging key is only confirmed once and
+    // an oversized one is dropped before it is read into memory
+    async fn confirm(
+        &self,
+        token: &str,
+        req_form: UploadConfirmReqForm,
+    ) -> AppResult<UploadConfirmResForm> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        let not_found = || {
+            AppError::new(
+                format!("Upload {} not found", req_form.upload_key),
+                AppErrorKind::ResourceNotFound,
+            )
+        };
+        let size = self
+            .storage_backend
+            .size(&req_form.upload_key)
+            .await?
+            .ok_or_else(not_found)?;
+        if let Err(e) = self.check_size(usize::try_from(size).unwrap_or(usize::MAX)) {
+            self.storage_backend.delete(&req_form.upload_key).await?;
+            return Err(e);
+        }
+        let data = self
+            .storage_backend
+            .get(&req_form.upload_key)
+            .await?
+            .ok_or_else(not_found)?;
+        self.storage_backend.delete(&req_form.upload_key).await?;
+        let file = UploadFile {
+            file_name: req_form.file_name,
+            data,
+        };
+        self.store(claims.user_id, file).await
+    }
+
+    async fn mine_search(
+        &self,
+        token: &str,
+        req_form: UploadMineSearchReqForm,
+    ) -> AppResult<UploadMineSearchResForm> {
+        let claims = self.token_utils.verify_token(token, &[0, 1]).await?;
+        let filter = UploadFilterParam {
+            create_user_id: Some(claims.user_id),
+            ..Default::default()
+        };
+        let order = OrderParam {
+            by: UploadAttr::CreateTime,
+            ascending: false,
+            ..Default::default()
+        };
+        let paginate = PaginateParam {
+            page_num: req_form.page_num,
+            page_size: req_form.page_size,
+        };
+        let record_total = self.upload_dao.count(filter.clone()).await?;
+        let upload_infos = self
+            .upload_dao
+            .list(filter, order, paginate.clone())
+            .await?
+            .into_iter()
+            .map(|model| self.info(model))
+            .collect();
+        Page::new(
+            paginate.page_num,
+            paginate.page_size,
+            record_total,
+            upload_infos,
+        )
+        .wrap(
+            "Invalid pagination parameters",
+            AppErrorKind::RequestParamInvalid,
+        )
+    }
+
+    async fn file(&self, key: &str) -> AppResult<UploadFileResForm> {
+        let not_found = || {
+            AppError::new(
+                format!("File {} not found", key),
+                AppErrorKind::ResourceNotFound,
+            )
+        };
+        if !is_valid_storage_key(key) {
+            return Err(not_found());
+        }
+        let model = self
+            .upload_dao
+            .get(UploadFilterParam {
+                storage_key: Some(key.to_string()),
+                ..Default::default()
+            })
+            .await?;
+        if let Some(res_form) = self.redirect(key)? {
+            return Ok(res_form);
+        }
+        let data = self.storage_backend.get(key).await?.ok_or_else(not_found)?;
+        Ok(UploadFileResForm::Content(UploadFileContent {
+            mime_type: model.mime_type,
+            data,
+            etag: format!("\"{}\"", key),
+            cache_max_age_sec: self.cache_max_age_sec,
+        }))
+    }
+
+    async fn file_variant(&self, key: &str, size: u32) -> AppResult<UploadFileResForm> {
+        if !is_valid_storage_key(key) || !self.image_utils.variant_sizes().contains(&size) {
+            return Err(AppError::new(
+                format!("File {} in size {} not found", key, size),
+                AppErrorKind::ResourceNotFound,
+            ));
+        }
+        let model = self
+            .upload_dao
+            .get(UploadFilterParam {
+                storage_key: Some(key.to_string()),
+                ..Default::default()
+            })
+            .await?;
+        if !model.mime_type.starts_with("image/") {
+            return Err(AppError::new(
+                format!("File {} is not an image", key),
+                AppErrorKind::UnsupportedMediaType,
+            ));
+        }
+        let variant_mime_type = self.image_utils.variant_mime_type();
+        let variant_key = variant_key(key, size, variant_mime_type);
+        if let Some(res_form) = self.redirect(&variant_key)? {
+            if !self.storage_backend.exists(&variant_key).await? {
+                self.generate_variant(key, size, &variant_key).await?;
+            }
+            return Ok(res_form);
+        }
+        let data = match self.storage_backend.get(&variant_key).await? {
+            Some(data) => data,
+            None => self.generate_variant(key, size, &variant_key).await?,
+        };
+        Ok(UploadFileResForm::Content(UploadFileContent {
+            mime_type: variant_mime_type.to_string(),
+            data,
+            etag: format!("\"{}\"", variant_key),
+            cache_max_age_sec: self.cache_max_age_sec,
+        }))
+    }
+}
+
+fn app_file_url(service_cfg: &ServiceConfig) -> String {
+    format!(
+        "{}/api/v1/public/upload/file",
+        service_cfg.base_url.trim_end_matches('/')
+    )
+}
+
+// variants sit next to the original in the storage, e.g. `<hash>-128.webp`
+fn variant_key(key: &str, size: u32, mime_type: &str) -> String {
+    let stem = key.split('.').next().unwrap_or(key);
+    format!("{}-{}.{}", stem, size, mime_extension(mime_type))
+}
+
+// sized urls of an uploaded image, used where a single url was exposed so far
+#[derive(Clone, Debug)]
+pub(super) struct VariantUrls {
+    // an url starting with one of these points to an upload
+    file_url_prefixes: Vec<String>,
+    variant_url_prefix: String,
+    sizes: Vec<u32>,
+}
+
+impl VariantUrls {
+    pub(super) fn new(
+        service_cfg: &ServiceConfig,
+        storage_cfg: &StorageConfig,
+        image_cfg: &ImageConfig,
+    ) -> Self {
+        let variant_url_prefix = app_file_url(service_cfg);
+        let mut file_url_prefixes = vec![format!("{}/", variant_url_prefix)];
+        if !storage_cfg.public_url.is_empty() {
+            file_url_prefixes.push(format!("{}/", storage_cfg.public_url.trim_end_matches('/')));
+        }
+        Self {
+            file_url_prefixes,
+            variant_url_prefix,
+            sizes: image_cfg.variant_sizes.clone(),
+        }
+    }
+
+    // None for urls that do not point to an upload, e.g. external avatars
+    pub(super) fn of(&self, url: Option<&str>) -> Option<BTreeMap<u32, String>> {
+        let url = url?;
+        let key = self
+            .file_url_prefixes
+            .iter()
+            .find_map(|prefix| url.strip_prefix(prefix.as_str()))?;
+        if !is_valid_storage_key(key) {
+            return None;
+        }
+        let variant_urls = self
+            .sizes
+            .iter()
+            .map(|size| {
+                let url = format!("{}/{}/{}", self.variant_url_prefix, key, size);
+                (*size, url)
+            })
+            .collect();
+        Some(variant_urls)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_urls() {
+        let service_cfg = ServiceConfig {
+            base_url: "https://example.com/".into(),
+            ..Default::default()
+        };
+        let storage_cfg = StorageConfig {
+            public_url: "https://cdn.example.com".into(),
+            ..Default::default()
+        };
+        let variant_urls = VariantUrls::new(&service_cfg, &storage_cfg, &ImageConfig::default());
+
+        let key = "ab".repeat(32) + ".png";
+        let expected: BTreeMap<u32, String> = [64, 128, 512]
+            .into_iter()
+            .map(|size| {
+                let url = format!(
+                    "https://example.com/api/v1/public/upload/file/{}/{}",
+                    key, size
+                );
+                (size, url)
+            })
+            .collect();
+        let url = format!("https://example.com/api/v1/public/upload/file/{}", key);
+        assert_eq!(variant_urls.of(Some(&url)), Some(expected.clone()));
+        let url = format!("https://cdn.example.com/{}", key);
+        assert_eq!(variant_urls.of(Some(&url)), Some(expected));
+        assert_eq!(variant_urls.of(Some("https://gravatar.com/avatar/1")), None);
+        assert_eq!(variant_urls.of(None), None);
+
+        assert_eq!(
+            variant_key(&key, 64, "image/webp"),
+            format!("{}-64.webp", "ab".repeat(32))
+        );
+    }
+}
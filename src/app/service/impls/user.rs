@@ -3,54 +3,104 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
-use super::super::{traits::user::UserServiceTrait, types::user::prelude::*};
+use super::{
+    super::{traits::user::UserServiceTrait, types::user::prelude::*},
+    article::ARTICLE_GENERATION_KEY,
+    upload::VariantUrls,
+};
 use crate::app::{
     common::prelude::*,
+    config::ServiceConfig,
     dao::{
         prelude::{OrderParam, PaginateParam, UserDataAccess},
         types::user::prelude::*,
     },
-    utils::prelude::{CryptoUtilsTrait, Page, TokenUtilsTrait},
+    utils::prelude::{
+        CacheUtilsTrait, CryptoUtilsTrait, Identicon, ImageConfig, Page, StorageConfig,
+        TokenUtilsTrait,
+    },
 };
 
 // ********************* content ********************* //
-impl From<UserDataModel> for UserInfo {
-    fn from(model: UserDataModel) -> Self {
-        Self {
-            id: model.id,
-            username: model.username,
-            nickname: model.nickname,
-            email: model.email,
-            avatar_url: model.avatar_url,
-            signature: model.signature,
-            group_type: model.group_type,
-            status_type: model.status_type,
-        }
-    }
-}
+// disabled users and users in the trash can not log in
+const LOGIN_STATUS_TYPES: [i32; 2] = [0, 1];
 
-pub struct UserService<D, C, T>
+pub struct UserService<D, C, K, T>
 where
     D: UserDataAccess + Sync + Send,
     C: CryptoUtilsTrait + Sync + Send,
+    K: CacheUtilsTrait + Sync + Send,
     T: TokenUtilsTrait + Sync + Send,
 {
     pub user_dao: Arc<D>,
     pub crypto_utils: Arc<C>,
+    pub cache_utils: Arc<K>,
     pub token_utils: Arc<T>,
+    avatar_variant_urls: VariantUrls,
+    // identicon urls are this prefix followed by `/{id}/identicon`
+    user_url: String,
+    identicon_sizes: Vec<u32>,
 }
 
-impl<D, C, T> UserService<D, C, T>
+impl<D, C, K, T> UserService<D, C, K, T>
 where
     D: UserDataAccess + Sync + Send,
     C: CryptoUtilsTrait + Sync + Send,
+    K: CacheUtilsTrait + Sync + Send,
     T: TokenUtilsTrait + Sync + Send,
 {
-    pub fn new(user_dao: Arc<D>, crypto_utils: Arc<C>, token_utils: Arc<T>) -> Self {
+    pub fn new(
+        user_dao: Arc<D>,
+        crypto_utils: Arc<C>,
+        cache_utils: Arc<K>,
+        token_utils: Arc<T>,
+        service_cfg: &ServiceConfig,
+        storage_cfg: &StorageConfig,
+        image_cfg: &ImageConfig,
+    ) -> Self {
         Self {
             user_dao,
             crypto_utils,
+            cache_utils,
             token_utils,
+            avatar_variant_urls: VariantUrls::new(service_cfg, storage_cfg, image_cfg),
+            user_url: format!(
+                "{}/api/v1/public/user",
+                service_cfg.base_url.trim_end_matches('/')
+            ),
+            identicon_sizes: image_cfg.variant_sizes.clone(),
+        }
+    }
+
+    fn user_info(&self, model: UserDataModel) -> UserInfo {
+        let (avatar_url, avatar_variants) = match model.avatar_url {
+            Some(avatar_url) => {
+                let avatar_variants = self.avatar_variant_urls.of(Some(&avatar_url));
+                (avatar_url, avatar_variants)
+            }
+            None => {
+                let identicon_url = format!("{}/{}/identicon", self.user_url, model.id);
+                let avatar_variants = self
+                    .identicon_sizes
+                    .iter()
+                    .map(|size| {
+                        let url = format!("{}?format=png&size={}", identicon_url, size);
+                        (*size, url)
+                    })
+                    .collect();
+                (identicon_url, Some(avatar_variants))
+            }
+        };
+        UserInfo {
+            id: model.id,
+            username: model.username,
+            nickname: model.nickname,
+            email: model.email,
+            avatar_variants,
+            avatar_url,
+            signature: model.signature,
+            group_type: model.group_type,
+            status_type: model.status_type,
         }
     }
 
@@ -68,7 +118,7 @@ where
             .list(filter, OrderParam::<UserAttr>::default(), paginate.clone())
             .await?
             .into_iter()
-            .map(|model| model.into())
+            .map(|model| self.user_info(model))
             .collect();
         let page = Page::new(
             paginate.page_num,
@@ -92,16 +142,17 @@ where
         self.token_utils.verify_token(token, allow_group).await?;
         let user_model = self.user_dao.get(filter_param).await?;
         Ok(UserFindResForm {
-            user_info: user_model.into(),
+            user_info: self.user_info(user_model),
         })
     }
 }
 
 #[async_trait]
-impl<D, C, T> UserServiceTrait for UserService<D, C, T>
+impl<D, C, K, T> UserServiceTrait for UserService<D, C, K, T>
 where
     D: UserDataAccess + Sync + Send,
     C: CryptoUtilsTrait + Sync + Send,
+    K: CacheUtilsTrait + Sync + Send,
     T: TokenUtilsTrait + Sync + Send,
 {
     async fn register(&self, req_form: UserRegisterReqForm) -> AppResult<UserRegisterResForm> {
@@ -132,7 +183,7 @@ where
             .generate_token(user_model.id, user_model.group_type, 3600 * 24 * 7)
             .await?;
         Ok(UserRegisterResForm {
-            user_info: user_model.into(),
+            user_info: self.user_info(user_model),
             token,
         })
     }
@@ -142,6 +193,7 @@ where
             .user_dao
             .get(UserFilterParam {
                 username: Some(req_form.username),
+                status_types: Some(LOGIN_STATUS_TYPES.to_vec()),
                 ..Default::default()
             })
             .await?;
@@ -152,7 +204,7 @@ where
             .generate_token(user_model.id, user_model.group_type, 3600 * 24 * 7)
             .await?;
         Ok(UserLoginResForm {
-            user_info: user_model.into(),
+            user_info: self.user_info(user_model),
             token,
         })
     }
@@ -199,6 +251,24 @@ where
         self.find_inner(token, filter, &[0, 1]).await
     }
 
+    async fn identicon(
+        &self,
+        id: i32,
+        req_form: UserIdenticonReqForm,
+    ) -> AppResult<UserIdenticonResForm> {
+        let identicon = Identicon::new(id.to_string().as_bytes());
+        Ok(match req_form.format {
+            UserIdenticonFormat::Svg => UserIdenticonResForm {
+                mime_type: "image/svg+xml",
+                data: identicon.svg().into_bytes(),
+            },
+            UserIdenticonFormat::Png => UserIdenticonResForm {
+                mime_type: "image/png",
+                data: identicon.png(req_form.size)?,
+            },
+        })
+    }
+
     async fn change_password(
         &self,
         id: i32,
@@ -273,7 +343,7 @@ where
             .await?;
         let user_model = self.user_dao.get(filter_param).await?;
         Ok(UserEditResForm {
-            user_info: user_model.into(),
+            user_info: self.user_info(user_model),
         })
     }
 
@@ -310,8 +380,10 @@ where
         req_form: UserAdminEditReqForm,
     ) -> AppResult<UserAdminEditResForm> {
         self.token_utils.verify_token(token, &[1]).await?;
+        // users in the trash only come back through restore
         let filter_param = UserFilterParam {
             id: Some(id),
+            status_types: Some(vec![0, 1, 2]),
             ..Default::default()
         };
         self.user_dao
@@ -326,7 +398,31 @@ where
             .await?;
         let user_model = self.user_dao.get(filter_param).await?;
         Ok(UserAdminEditResForm {
-            user_info: user_model.into(),
+            user_info: self.user_info(user_model),
+        })
+    }
+
+    async fn admin_delete(&self, id: i32, token: &str) -> AppResult<UserAdminDeleteResForm> {
+        let claims = self.token_utils.verify_token(token, &[1]).await?;
+        if claims.user_id == id {
+            return Err(AppError::new(
+                "You cannot delete yourself",
+                AppErrorKind::PermissionDenied,
+            ));
+        }
+        let filter_param = UserFilterParam {
+            id: Some(id),
+            ..Default::default()
+        };
+        self.user_dao
+            .soft_delete(filter_param.clone(), claims.user_id)
+            .await?;
+        self.token_utils.invalidate_token(id, 3600 * 24 * 7).await?;
+        // articles of users in the trash leave public lists, feeds and the sitemap
+        self.cache_utils.incr(ARTICLE_GENERATION_KEY, 1).await?;
+        let user_model = self.user_dao.get(filter_param).await?;
+        Ok(UserAdminDeleteResForm {
+            user_info: self.user_info(user_model),
         })
     }
 }
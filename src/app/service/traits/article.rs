@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+
+use super::super::types::article::prelude::*;
+use crate::app::common::prelude::AppResult;
+
+#[async_trait]
+pub trait ArticleServiceTrait {
+    async fn search(&self, req_form: ArticleSearchReqForm) -> AppResult<ArticleSearchResForm>;
+    async fn fulltext_search(
+        &self,
+        req_form: ArticleFulltextSearchReqForm,
+    ) -> AppResult<ArticleFulltextSearchResForm>;
+    async fn suggest(&self, req_form: ArticleSuggestReqForm) -> AppResult<ArticleSuggestResForm>;
+    // published article counts per month of creation, the latest month first
+    async fn archive(&self) -> AppResult<ArticleArchiveResForm>;
+    async fn archive_search(
+        &self,
+        year: i32,
+        month: u32,
+        req_form: ArticleArchiveSearchReqForm,
+    ) -> AppResult<ArticleArchiveSearchResForm>;
+    // a view is counted for the visitor
+    async fn find(&self, id: i32, visitor: &str) -> AppResult<ArticleFindResForm>;
+    async fn find_by_slug(&self, slug: String, visitor: &str) -> AppResult<ArticleSlugFindResForm>;
+    async fn render(&self, id: i32) -> AppResult<ArticleRenderResForm>;
+    async fn mine_search(
+        &self,
+        token: &str,
+        req_form: ArticleMineSearchReqForm,
+    ) -> AppResult<ArticleMineSearchResForm>;
+    async fn create(
+        &self,
+        token: &str,
+        req_form: ArticleCreateReqForm,
+    ) -> AppResult<ArticleCreateResForm>;
+    async fn edit(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleEditReqForm,
+    ) -> AppResult<ArticleEditResForm>;
+    async fn change_status(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleChangeStatusReqForm,
+    ) -> AppResult<ArticleChangeStatusResForm>;
+    async fn delete(&self, id: i32, token: &str) -> AppResult<ArticleDeleteResForm>;
+    async fn schedule(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleScheduleReqForm,
+    ) -> AppResult<ArticleScheduleResForm>;
+    async fn replace_tags(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleTagsReqForm,
+    ) -> AppResult<ArticleTagsResForm>;
+    async fn add_tags(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleTagsReqForm,
+    ) -> AppResult<ArticleTagsResForm>;
+    async fn remove_tags(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleTagsReqForm,
+    ) -> AppResult<ArticleTagsResForm>;
+    async fn react(&self, id: i32, kind: String, token: &str)
+        -> AppResult<ArticleReactionsResForm>;
+    async fn unreact(
+        &self,
+        id: i32,
+        kind: String,
+        token: &str,
+    ) -> AppResult<ArticleReactionsResForm>;
+    // "mine" is only filled in when a token is given
+    async fn reactions(&self, id: i32, token: Option<&str>) -> AppResult<ArticleReactionsResForm>;
+    // revisions are snapshots of the title, description and content, status, slug and
+    // schedule changes do not add one
+    async fn revision_search(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleRevisionSearchReqForm,
+    ) -> AppResult<ArticleRevisionSearchResForm>;
+    async fn revision_diff(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleRevisionDiffReqForm,
+    ) -> AppResult<ArticleRevisionDiffResForm>;
+    async fn rollback(
+        &self,
+        id: i32,
+        revision_id: i32,
+        token: &str,
+    ) -> AppResult<ArticleRollbackResForm>;
+    async fn admin_search(
+        &self,
+        token: &str,
+        req_form: ArticleAdminSearchReqForm,
+    ) -> AppResult<ArticleAdminSearchResForm>;
+    async fn admin_find(&self, id: i32, token: &str) -> AppResult<ArticleAdminGetResForm>;
+    async fn admin_edit(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: ArticleAdminEditReqForm,
+    ) -> AppResult<ArticleAdminEditResForm>;
+    async fn admin_delete(&self, id: i32, token: &str) -> AppResult<ArticleAdminDeleteResForm>;
+    async fn pins(
+        &self,
+        token: &str,
+        req_form: ArticlePinsReqForm,
+    ) -> AppResult<ArticlePinsResForm>;
+    // replace all pins of the tag, or the global ones when no tag is given
+    async fn replace_pins(
+        &self,
+        token: &str,
+        req_form: ArticleReplacePinsReqForm,
+    ) -> AppResult<ArticlePinsResForm>;
+}
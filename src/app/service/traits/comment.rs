@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+
+use super::super::types::comment::prelude::*;
+use crate::app::common::prelude::AppResult;
+
+#[async_trait]
+pub trait CommentServiceTrait {
+    async fn thread(&self, article_id: i32) -> AppResult<CommentThreadResForm>;
+    async fn create(
+        &self,
+        token: &str,
+        req_form: CommentCreateReqForm,
+    ) -> AppResult<CommentCreateResForm>;
+    async fn edit(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: CommentEditReqForm,
+    ) -> AppResult<CommentEditResForm>;
+    // the author or an admin may delete
+    async fn delete(&self, id: i32, token: &str) -> AppResult<CommentDeleteResForm>;
+    async fn admin_search(
+        &self,
+        token: &str,
+        req_form: CommentAdminSearchReqForm,
+    ) -> AppResult<CommentAdminSearchResForm>;
+    async fn admin_approve(
+        &self,
+        token: &str,
+        req_form: CommentAdminModerateReqForm,
+    ) -> AppResult<CommentAdminModerateResForm>;
+    async fn admin_reject(
+        &self,
+        token: &str,
+        req_form: CommentAdminModerateReqForm,
+    ) -> AppResult<CommentAdminModerateResForm>;
+}
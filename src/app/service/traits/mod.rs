@@ -1,5 +1,21 @@
+pub mod article;
+pub mod comment;
+pub mod series;
+pub mod site;
+pub mod tag;
+pub mod transfer;
+pub mod trash;
+pub mod upload;
 pub mod user;
 
 pub mod prelude {
+    pub use super::article::ArticleServiceTrait;
+    pub use super::comment::CommentServiceTrait;
+    pub use super::series::SeriesServiceTrait;
+    pub use super::site::SiteServiceTrait;
+    pub use super::tag::TagServiceTrait;
+    pub use super::transfer::TransferServiceTrait;
+    pub use super::trash::TrashServiceTrait;
+    pub use super::upload::UploadServiceTrait;
     pub use super::user::UserServiceTrait;
 }
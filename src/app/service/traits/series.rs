@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+
+use super::super::types::series::prelude::*;
+use crate::app::common::prelude::AppResult;
+
+#[async_trait]
+pub trait SeriesServiceTrait {
+    async fn find(&self, id: i32) -> AppResult<SeriesFindResForm>;
+    async fn create(
+        &self,
+        token: &str,
+        req_form: SeriesCreateReqForm,
+    ) -> AppResult<SeriesCreateResForm>;
+    // the following are for the owner only
+    async fn edit(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: SeriesEditReqForm,
+    ) -> AppResult<SeriesEditResForm>;
+    async fn delete(&self, id: i32, token: &str) -> AppResult<SeriesDeleteResForm>;
+    async fn reorder(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: SeriesReorderReqForm,
+    ) -> AppResult<SeriesReorderResForm>;
+    async fn insert(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: SeriesInsertReqForm,
+    ) -> AppResult<SeriesInsertResForm>;
+    async fn remove(&self, id: i32, article_id: i32, token: &str)
+        -> AppResult<SeriesRemoveResForm>;
+}
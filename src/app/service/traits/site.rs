@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use super::super::types::site::prelude::*;
+use crate::app::common::prelude::AppResult;
+
+#[async_trait]
+pub trait SiteServiceTrait {
+    // the most recent published articles, of the tag when a slug is given
+    async fn rss(&self, tag_slug: Option<String>) -> AppResult<SiteFeedResForm>;
+    async fn atom(&self, tag_slug: Option<String>) -> AppResult<SiteFeedResForm>;
+    async fn sitemap(&self) -> AppResult<SiteSitemapResForm>;
+    // the 1-based part of a sitemap split into an index
+    async fn sitemap_part(&self, num: usize) -> AppResult<SiteSitemapResForm>;
+    async fn robots(&self) -> AppResult<SiteRobotsResForm>;
+}
@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+
+use super::super::types::tag::prelude::*;
+use crate::app::common::prelude::AppResult;
+
+#[async_trait]
+pub trait TagServiceTrait {
+    async fn tree(&self) -> AppResult<TagTreeResForm>;
+    async fn find(&self, id: i32) -> AppResult<TagFindResForm>;
+    async fn find_by_slug(&self, slug: String) -> AppResult<TagSlugFindResForm>;
+    async fn path(&self, id: i32) -> AppResult<TagPathResForm>;
+    async fn admin_tree(&self, token: &str) -> AppResult<TagAdminTreeResForm>;
+    async fn admin_create(
+        &self,
+        token: &str,
+        req_form: TagAdminCreateReqForm,
+    ) -> AppResult<TagAdminCreateResForm>;
+    async fn admin_edit(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: TagAdminEditReqForm,
+    ) -> AppResult<TagAdminEditResForm>;
+    async fn admin_move(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: TagAdminMoveReqForm,
+    ) -> AppResult<TagAdminMoveResForm>;
+    // the tag is moved into the trash, the target tag is returned
+    async fn admin_merge(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: TagAdminMergeReqForm,
+    ) -> AppResult<TagAdminMergeResForm>;
+    // a tag with children that are not deleted can only be deleted by handing them
+    // over to its parent
+    async fn admin_delete(
+        &self,
+        id: i32,
+        token: &str,
+        req_form: TagAdminDeleteReqForm,
+    ) -> AppResult<TagAdminDeleteResForm>;
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use super::super::types::transfer::prelude::*;
+use crate::app::common::prelude::AppResult;
+
+#[async_trait]
+pub trait TransferServiceTrait {
+    // all articles as markdown files with front matter, zipped
+    async fn export(&self, token: &str) -> AppResult<TransferExportResForm>;
+    // files that cannot be imported are skipped and reported, the rest still go through
+    async fn import(
+        &self,
+        token: &str,
+        req_form: TransferImportReqForm,
+        data: Vec<u8>,
+    ) -> AppResult<TransferImportResForm>;
+}
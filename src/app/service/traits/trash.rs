@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+
+use super::super::types::trash::prelude::*;
+use crate::app::common::prelude::AppResult;
+
+#[async_trait]
+pub trait TrashServiceTrait {
+    async fn search(
+        &self,
+        kind: TrashKind,
+        token: &str,
+        req_form: TrashSearchReqForm,
+    ) -> AppResult<TrashSearchResForm>;
+    // restored data is not public until it is published or activated again
+    async fn restore(
+        &self,
+        kind: TrashKind,
+        id: i32,
+        token: &str,
+    ) -> AppResult<TrashRestoreResForm>;
+    // hard-delete data that has been in the trash longer than the retention period
+    async fn purge(&self, token: &str) -> AppResult<TrashPurgeResForm>;
+}
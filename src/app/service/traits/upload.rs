@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+
+use super::super::types::upload::prelude::*;
+use crate::app::common::prelude::AppResult;
+
+#[async_trait]
+pub trait UploadServiceTrait {
+    // the content decides the type, whatever the client declared
+    async fn create(&self, token: &str, file: UploadFile) -> AppResult<UploadCreateResForm>;
+    async fn mine_search(
+        &self,
+        token: &str,
+        req_form: UploadMineSearchReqForm,
+    ) -> AppResult<UploadMineSearchResForm>;
+    // a url to PUT the file to the storage directly, NotImplemented if the backend can not
+    // hand one out
+    async fn presign(
+        &self,
+        token: &str,
+        req_form: UploadPresignReqForm,
+    ) -> AppResult<UploadPresignResForm>;
+    async fn confirm(
+        &self,
+        token: &str,
+        req_form: UploadConfirmReqForm,
+    ) -> AppResult<UploadConfirmResForm>;
+    // only files with an upload record are served
+    async fn file(&self, key: &str) -> AppResult<UploadFileResForm>;
+    // an image scaled to one of the configured sizes, generated on the first request
+    async fn file_variant(&self, key: &str, size: u32) -> AppResult<UploadFileResForm>;
+}
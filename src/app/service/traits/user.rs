@@ -17,6 +17,12 @@ pub trait UserServiceTrait {
         req_form: UserSearchReqForm,
     ) -> AppResult<UserSearchResForm>;
     async fn find(&self, id: i32, token: &str) -> AppResult<UserFindResForm>;
+    // derived from the id alone, so no lookup and no token
+    async fn identicon(
+        &self,
+        id: i32,
+        req_form: UserIdenticonReqForm,
+    ) -> AppResult<UserIdenticonResForm>;
     async fn change_password(
         &self,
         id: i32,
@@ -41,4 +47,5 @@ pub trait UserServiceTrait {
         token: &str,
         req_form: UserAdminEditReqForm,
     ) -> AppResult<UserAdminEditResForm>;
+    async fn admin_delete(&self, id: i32, token: &str) -> AppResult<UserAdminDeleteResForm>;
 }
@@ -0,0 +1,535 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        ArticleAdminDeleteResForm, ArticleAdminEditReqForm, ArticleAdminEditResForm,
+        ArticleAdminGetResForm, ArticleAdminSearchReqForm, ArticleAdminSearchResForm,
+        ArticleArchiveMonth, ArticleArchiveResForm, ArticleArchiveSearchReqForm,
+        ArticleArchiveSearchResForm, ArticleChangeStatusReqForm, ArticleChangeStatusResForm,
+        ArticleConfig, ArticleCreateReqForm, ArticleCreateResForm, ArticleDeleteResForm,
+        ArticleEditReqForm, ArticleEditResForm, ArticleFindResForm, ArticleFulltextSearchReqForm,
+        ArticleFulltextSearchResForm, ArticleHitInfo, ArticleInfo, ArticleMineSearchReqForm,
+        ArticleMineSearchResForm, ArticleOrderBy, ArticlePinsReqForm, ArticlePinsResForm,
+        ArticleReactionCount, ArticleReactionsResForm, ArticleRenderResForm,
+        ArticleReplacePinsReqForm, ArticleRevisionDiffReqForm, ArticleRevisionDiffResForm,
+        ArticleRevisionInfo, ArticleRevisionSearchReqForm, ArticleRevisionSearchResForm,
+        ArticleRollbackResForm, ArticleScheduleReqForm, ArticleScheduleResForm,
+        ArticleSearchReqForm, ArticleSearchResForm, ArticleSlugFindResForm, ArticleSuggestReqForm,
+        ArticleSuggestResForm, ArticleTagsReqForm, ArticleTagsResForm, DiffLine,
+    };
+}
+
+// ********************* import ********************* //
+use garde::Validate;
+use sea_orm::prelude::DateTime;
+use serde::{Deserialize, Serialize};
+
+use super::{default_page_num, default_page_size, series::SeriesNavInfo, tag::TagInfo, SLUG_RE};
+use crate::app::utils::prelude::{Page, RenderedContent, SLUG_MAX_LEN};
+
+// ********************* content ********************* //
+const TITLE_MIN_LEN: usize = 1;
+const TITLE_MAX_LEN: usize = 255;
+const DESCRIPTION_MAX_LEN: usize = 65_535;
+const CONTENT_MAX_LEN: usize = 16_777_215;
+const STATUS_TYPE_MIN: i32 = 0;
+const STATUS_TYPE_MAX: i32 = 3;
+// articles are moved into the trash by deleting them, not by setting the status
+const ALIVE_STATUS_TYPE_MAX: i32 = 2;
+const TITLE_SEARCH_MIN_LEN: usize = 1;
+const TITLE_SEARCH_MAX_LEN: usize = 64;
+const TAG_IDS_MAX_LEN: usize = 32;
+const QUERY_MIN_LEN: usize = 1;
+const QUERY_MAX_LEN: usize = 64;
+const PIN_ARTICLE_IDS_MAX_LEN: usize = 32;
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ArticleConfig {
+    // how often due publish_at / unpublish_at are applied
+    pub schedule_interval_sec: u64,
+    // repeated views from the same visitor within the window count once
+    pub view_dedup_sec: u64,
+    // how often buffered view counts are written to the database
+    pub view_flush_interval_sec: u64,
+    // "like" and the emoji users may react with, in display order
+    pub reaction_kinds: Vec<String>,
+    // cached counts also catch up with reactions removed along with their users
+    pub reaction_cache_expire_sec: u64,
+}
+
+impl Default for ArticleConfig {
+    fn default() -> Self {
+        Self {
+            schedule_interval_sec: 60,
+            view_dedup_sec: 1800,
+            view_flush_interval_sec: 60,
+            reaction_kinds: ["like", "👍", "❤️", "🎉", "😄", "🤔"]
+                .map(String::from)
+                .to_vec(),
+            reaction_cache_expire_sec: 600,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArticleInfo {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub description: String,
+    pub content: String,
+    #[serde(rename = "isTop")]
+    pub is_top: bool,
+    #[serde(rename = "pinPriority")]
+    pub pin_priority: i32,
+    #[serde(rename = "statusType")]
+    pub status_type: i32,
+    #[serde(rename = "publishAt")]
+    pub publish_at: Option<DateTime>,
+    #[serde(rename = "unpublishAt")]
+    pub unpublish_at: Option<DateTime>,
+    #[serde(rename = "publishTime")]
+    pub publish_time: Option<DateTime>,
+    #[serde(rename = "viewCount")]
+    pub view_count: i64,
+    #[serde(rename = "createTime")]
+    pub create_time: DateTime,
+    #[serde(rename = "updateTime")]
+    pub update_time: DateTime,
+    #[serde(rename = "createUserId")]
+    pub create_user_id: i32,
+    #[serde(rename = "updateUserId")]
+    pub update_user_id: i32,
+}
+
+// search
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleSearchReqForm {
+    #[serde(rename = "titleSearch")]
+    #[garde(length(min = TITLE_SEARCH_MIN_LEN, max = TITLE_SEARCH_MAX_LEN))]
+    pub title_search: Option<String>,
+    #[serde(rename = "createUserId")]
+    #[garde(skip)]
+    pub create_user_id: Option<i32>,
+    #[serde(rename = "tagId")]
+    #[garde(skip)]
+    pub tag_id: Option<i32>,
+    #[serde(rename = "includeDescendants", default)]
+    #[garde(skip)]
+    pub include_descendants: bool,
+    #[serde(rename = "orderBy", default)]
+    #[garde(skip)]
+    pub order_by: ArticleOrderBy,
+    #[serde(default)]
+    #[garde(skip)]
+    pub ascending: bool,
+    #[serde(rename = "pageNum", default = "default_page_num")]
+    #[garde(range(min = 1))]
+    pub page_num: u64,
+    #[serde(rename = "pageSize", default = "default_page_size")]
+    #[garde(range(min = 1))]
+    pub page_size: u64,
+}
+pub type ArticleSearchResForm = Page<ArticleInfo>;
+
+// applied after pinned articles, which always come first
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArticleOrderBy {
+    #[default]
+    CreateTime,
+    UpdateTime,
+    Title,
+}
+
+// fulltext search
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleFulltextSearchReqForm {
+    #[garde(length(min = QUERY_MIN_LEN, max = QUERY_MAX_LEN))]
+    pub query: String,
+    #[serde(rename = "pageNum", default = "default_page_num")]
+    #[garde(range(min = 1))]
+    pub page_num: u64,
+    #[serde(rename = "pageSize", default = "default_page_size")]
+    #[garde(range(min = 1))]
+    pub page_size: u64,
+}
+
+// highlights are html escaped with matches wrapped in <mark>
+#[derive(Debug, Serialize)]
+pub struct ArticleHitInfo {
+    pub id: i32,
+    pub title: String,
+    pub description: String,
+    #[serde(rename = "titleHighlight")]
+    pub title_highlight: String,
+    #[serde(rename = "contentSnippet")]
+    pub content_snippet: String,
+    #[serde(rename = "createTime")]
+    pub create_time: DateTime,
+    #[serde(rename = "updateTime")]
+    pub update_time: DateTime,
+    #[serde(rename = "createUserId")]
+    pub create_user_id: i32,
+}
+pub type ArticleFulltextSearchResForm = Page<ArticleHitInfo>;
+
+// suggest
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleSuggestReqForm {
+    #[garde(length(min = QUERY_MIN_LEN, max = QUERY_MAX_LEN))]
+    pub prefix: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArticleSuggestResForm {
+    pub suggestions: Vec<String>,
+}
+
+// archive
+#[derive(Debug, Serialize)]
+pub struct ArticleArchiveMonth {
+    pub year: i32,
+    pub month: i32,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArticleArchiveResForm {
+    pub months: Vec<ArticleArchiveMonth>,
+}
+
+// archive search, the year and month come from the path
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleArchiveSearchReqForm {
+    #[serde(rename = "pageNum", default = "default_page_num")]
+    #[garde(range(min = 1))]
+    pub page_num: u64,
+    #[serde(rename = "pageSize", default = "default_page_size")]
+    #[garde(range(min = 1))]
+    pub page_size: u64,
+}
+pub type ArticleArchiveSearchResForm = Page<ArticleInfo>;
+
+// get
+#[derive(Debug, Serialize)]
+pub struct ArticleFindResForm {
+    #[serde(rename = "articleInfo")]
+    pub article_info: ArticleInfo,
+    pub tags: Vec<TagInfo>,
+    // None when the article is not part of a series
+    pub series: Option<SeriesNavInfo>,
+}
+
+// find by slug, an old slug resolves to the current one
+#[derive(Debug)]
+pub enum ArticleSlugFindResForm {
+    Found(Box<ArticleFindResForm>),
+    Moved(String),
+}
+
+// render
+pub type ArticleRenderResForm = RenderedContent;
+
+// mine search
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleMineSearchReqForm {
+    #[serde(rename = "titleSearch")]
+    #[garde(length(min = TITLE_SEARCH_MIN_LEN, max = TITLE_SEARCH_MAX_LEN))]
+    pub title_search: Option<String>,
+    #[serde(rename = "statusType")]
+    #[garde(range(min = STATUS_TYPE_MIN, max = ALIVE_STATUS_TYPE_MAX))]
+    pub status_type: Option<i32>,
+    #[serde(rename = "pageNum", default = "default_page_num")]
+    #[garde(range(min = 1))]
+    pub page_num: u64,
+    #[serde(rename = "pageSize", default = "default_page_size")]
+    #[garde(range(min = 1))]
+    pub page_size: u64,
+}
+pub type ArticleMineSearchResForm = Page<ArticleInfo>;
+
+// create
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleCreateReqForm {
+    #[garde(length(min = TITLE_MIN_LEN, max = TITLE_MAX_LEN))]
+    pub title: String,
+    // generated from title when missing
+    #[garde(pattern(SLUG_RE), length(max = SLUG_MAX_LEN))]
+    pub slug: Option<String>,
+    // generated from content when empty
+    #[serde(default)]
+    #[garde(length(max = DESCRIPTION_MAX_LEN))]
+    pub description: String,
+    #[garde(length(max = CONTENT_MAX_LEN))]
+    pub content: String,
+}
+pub type ArticleCreateResForm = ArticleFindResForm;
+
+// edit
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleEditReqForm {
+    #[garde(length(min = TITLE_MIN_LEN, max = TITLE_MAX_LEN))]
+    pub title: Option<String>,
+    #[garde(pattern(SLUG_RE), length(max = SLUG_MAX_LEN))]
+    pub slug: Option<String>,
+    #[garde(length(max = DESCRIPTION_MAX_LEN))]
+    pub description: Option<String>,
+    #[garde(length(max = CONTENT_MAX_LEN))]
+    pub content: Option<String>,
+}
+pub type ArticleEditResForm = ArticleFindResForm;
+
+// change status
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleChangeStatusReqForm {
+    #[serde(rename = "statusType")]
+    #[garde(range(min = STATUS_TYPE_MIN, max = ALIVE_STATUS_TYPE_MAX))]
+    pub status_type: i32,
+}
+pub type ArticleChangeStatusResForm = ArticleFindResForm;
+
+// delete, the article is moved into the trash
+pub type ArticleDeleteResForm = ArticleFindResForm;
+
+// schedule, a missing time clears it
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleScheduleReqForm {
+    #[serde(rename = "publishAt")]
+    #[garde(skip)]
+    pub publish_at: Option<DateTime>,
+    #[serde(rename = "unpublishAt")]
+    #[garde(skip)]
+    pub unpublish_at: Option<DateTime>,
+}
+pub type ArticleScheduleResForm = ArticleFindResForm;
+
+// replace / add / remove tags
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleTagsReqForm {
+    #[serde(rename = "tagIds")]
+    #[garde(length(max = TAG_IDS_MAX_LEN))]
+    pub tag_ids: Vec<i32>,
+}
+pub type ArticleTagsResForm = ArticleFindResForm;
+
+// react / unreact / reactions
+#[derive(Debug, Serialize)]
+pub struct ArticleReactionCount {
+    pub kind: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArticleReactionsResForm {
+    pub counts: Vec<ArticleReactionCount>,
+    // empty for anonymous visitors
+    pub mine: Vec<String>,
+}
+
+// revision search
+#[derive(Debug, Serialize)]
+pub struct ArticleRevisionInfo {
+    pub id: i32,
+    #[serde(rename = "articleId")]
+    pub article_id: i32,
+    pub title: String,
+    pub description: String,
+    #[serde(rename = "createTime")]
+    pub create_time: DateTime,
+    // None once the editor is purged
+    #[serde(rename = "updateUserId")]
+    pub update_user_id: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleRevisionSearchReqForm {
+    #[serde(rename = "pageNum", default = "default_page_num")]
+    #[garde(range(min = 1))]
+    pub page_num: u64,
+    #[serde(rename = "pageSize", default = "default_page_size")]
+    #[garde(range(min = 1))]
+    pub page_size: u64,
+}
+pub type ArticleRevisionSearchResForm = Page<ArticleRevisionInfo>;
+
+// revision diff
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleRevisionDiffReqForm {
+    #[serde(rename = "fromRevisionId")]
+    #[garde(skip)]
+    pub from_revision_id: i32,
+    #[serde(rename = "toRevisionId")]
+    #[garde(skip)]
+    pub to_revision_id: i32,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct DiffLine {
+    // equal, delete or insert
+    pub tag: String,
+    #[serde(rename = "oldLineNum")]
+    pub old_line_num: Option<usize>,
+    #[serde(rename = "newLineNum")]
+    pub new_line_num: Option<usize>,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArticleRevisionDiffResForm {
+    #[serde(rename = "fromRevisionId")]
+    pub from_revision_id: i32,
+    #[serde(rename = "toRevisionId")]
+    pub to_revision_id: i32,
+    pub lines: Vec<DiffLine>,
+}
+
+// rollback
+pub type ArticleRollbackResForm = ArticleFindResForm;
+
+// admin search
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleAdminSearchReqForm {
+    #[serde(rename = "titleSearch")]
+    #[garde(length(min = TITLE_SEARCH_MIN_LEN, max = TITLE_SEARCH_MAX_LEN))]
+    pub title_search: Option<String>,
+    #[serde(rename = "statusType")]
+    #[garde(range(min = STATUS_TYPE_MIN, max = STATUS_TYPE_MAX))]
+    pub status_type: Option<i32>,
+    #[serde(rename = "isTop")]
+    #[garde(skip)]
+    pub is_top: Option<bool>,
+    #[serde(rename = "createUserId")]
+    #[garde(skip)]
+    pub create_user_id: Option<i32>,
+    #[serde(rename = "createTimeStart")]
+    #[garde(skip)]
+    pub create_time_start: Option<DateTime>,
+    #[serde(rename = "createTimeEnd")]
+    #[garde(skip)]
+    pub create_time_end: Option<DateTime>,
+    #[serde(rename = "tagId")]
+    #[garde(skip)]
+    pub tag_id: Option<i32>,
+    #[serde(rename = "includeDescendants", default)]
+    #[garde(skip)]
+    pub include_descendants: bool,
+    #[serde(rename = "pageNum", default = "default_page_num")]
+    #[garde(range(min = 1))]
+    pub page_num: u64,
+    #[serde(rename = "pageSize", default = "default_page_size")]
+    #[garde(range(min = 1))]
+    pub page_size: u64,
+}
+pub type ArticleAdminSearchResForm = Page<ArticleInfo>;
+
+// admin get
+pub type ArticleAdminGetResForm = ArticleFindResForm;
+
+// admin edit
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleAdminEditReqForm {
+    #[serde(rename = "isTop")]
+    #[garde(skip)]
+    pub is_top: Option<bool>,
+    #[serde(rename = "statusType")]
+    #[garde(range(min = STATUS_TYPE_MIN, max = ALIVE_STATUS_TYPE_MAX))]
+    pub status_type: Option<i32>,
+}
+pub type ArticleAdminEditResForm = ArticleFindResForm;
+
+// admin delete
+pub type ArticleAdminDeleteResForm = ArticleFindResForm;
+
+// pins, global ones when no tag is given
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticlePinsReqForm {
+    #[serde(rename = "tagId")]
+    #[garde(skip)]
+    pub tag_id: Option<i32>,
+}
+// the highest priority first
+pub type ArticlePinsResForm = Vec<ArticleInfo>;
+
+// replace pins, the first article gets the highest priority
+#[derive(Debug, Deserialize, Validate)]
+pub struct ArticleReplacePinsReqForm {
+    #[serde(rename = "tagId")]
+    #[garde(skip)]
+    pub tag_id: Option<i32>,
+    #[serde(rename = "articleIds")]
+    #[garde(length(max = PIN_ARTICLE_IDS_MAX_LEN))]
+    pub article_ids: Vec<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_inputs() {
+        let forms = vec![
+            ArticleCreateReqForm {
+                title: "valid title".to_string(),
+                slug: Some("valid-title".to_string()),
+                description: "".to_string(),
+                content: "# valid content".to_string(),
+            },
+            ArticleCreateReqForm {
+                title: "合法标题".to_string(),
+                slug: None,
+                description: "合法描述".to_string(),
+                content: "".to_string(),
+            },
+        ];
+
+        for form in forms {
+            assert!(form.validate(&()).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let forms = vec![
+            ArticleCreateReqForm {
+                title: "".to_string(), // 太短
+                slug: None,
+                description: "".to_string(),
+                content: "".to_string(),
+            },
+            ArticleCreateReqForm {
+                title: "t".repeat(TITLE_MAX_LEN + 1), // 太长
+                slug: None,
+                description: "".to_string(),
+                content: "".to_string(),
+            },
+            ArticleCreateReqForm {
+                title: "valid title".to_string(),
+                slug: Some("Invalid Slug".to_string()), // 别名只能包含小写字母、数字和连字符
+                description: "".to_string(),
+                content: "".to_string(),
+            },
+        ];
+
+        for form in forms {
+            assert!(form.validate(&()).is_err());
+        }
+
+        // 普通用户不能将文章置为删除状态
+        let status_form = ArticleChangeStatusReqForm { status_type: 3 };
+        assert!(status_form.validate(&()).is_err());
+
+        // 单篇文章的标签数量有上限
+        let tags_form = ArticleTagsReqForm {
+            tag_ids: (0..=TAG_IDS_MAX_LEN as i32).collect(),
+        };
+        assert!(tags_form.validate(&()).is_err());
+
+        // 置顶文章数量有上限
+        let pins_form = ArticleReplacePinsReqForm {
+            tag_id: None,
+            article_ids: (0..=PIN_ARTICLE_IDS_MAX_LEN as i32).collect(),
+        };
+        assert!(pins_form.validate(&()).is_err());
+    }
+}
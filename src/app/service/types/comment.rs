@@ -0,0 +1,184 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        CommentAdminModerateReqForm, CommentAdminModerateResForm, CommentAdminSearchReqForm,
+        CommentAdminSearchResForm, CommentConfig, CommentCreateReqForm, CommentCreateResForm,
+        CommentDeleteResForm, CommentEditReqForm, CommentEditResForm, CommentFindResForm,
+        CommentInfo, CommentNode, CommentThreadResForm,
+    };
+}
+
+// ********************* import ********************* //
+use garde::Validate;
+use sea_orm::prelude::DateTime;
+use serde::{Deserialize, Serialize};
+
+use super::{default_page_num, default_page_size};
+use crate::app::utils::prelude::Page;
+
+// ********************* content ********************* //
+const CONTENT_MIN_LEN: usize = 1;
+const CONTENT_MAX_LEN: usize = 4096;
+const STATUS_TYPE_MIN: i32 = 0;
+const STATUS_TYPE_MAX: i32 = 3;
+const CONTENT_SEARCH_MIN_LEN: usize = 1;
+const CONTENT_SEARCH_MAX_LEN: usize = 64;
+const MODERATE_IDS_MIN_LEN: usize = 1;
+const MODERATE_IDS_MAX_LEN: usize = 100;
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct CommentConfig {
+    // top-level comments have depth 1
+    pub max_depth: i32,
+    // authors may edit their comments within the window after posting
+    pub edit_window_sec: i64,
+    // comments from users registered within the period wait for approval
+    pub new_user_days: i64,
+}
+
+impl Default for CommentConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 5,
+            edit_window_sec: 900,
+            new_user_days: 7,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentInfo {
+    pub id: i32,
+    #[serde(rename = "articleId")]
+    pub article_id: i32,
+    #[serde(rename = "parentCommentId")]
+    pub parent_comment_id: Option<i32>,
+    pub depth: i32,
+    // empty for deleted comments
+    pub content: String,
+    #[serde(rename = "statusType")]
+    pub status_type: i32,
+    #[serde(rename = "createTime")]
+    pub create_time: DateTime,
+    #[serde(rename = "updateTime")]
+    pub update_time: DateTime,
+    // None once the author is purged
+    #[serde(rename = "createUserId")]
+    pub create_user_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentNode {
+    #[serde(flatten)]
+    pub comment_info: CommentInfo,
+    pub replies: Vec<CommentNode>,
+}
+
+// thread, deleted comments stay as placeholders while they have visible replies
+#[derive(Debug, Serialize)]
+pub struct CommentThreadResForm {
+    pub thread: Vec<CommentNode>,
+}
+
+// get
+#[derive(Debug, Serialize)]
+pub struct CommentFindResForm {
+    #[serde(rename = "commentInfo")]
+    pub comment_info: CommentInfo,
+}
+
+// create
+#[derive(Debug, Deserialize, Validate)]
+pub struct CommentCreateReqForm {
+    #[serde(rename = "articleId")]
+    #[garde(skip)]
+    pub article_id: i32,
+    // None posts a top-level comment
+    #[serde(rename = "parentCommentId")]
+    #[garde(skip)]
+    pub parent_comment_id: Option<i32>,
+    #[garde(length(min = CONTENT_MIN_LEN, max = CONTENT_MAX_LEN))]
+    pub content: String,
+}
+pub type CommentCreateResForm = CommentFindResForm;
+
+// edit
+#[derive(Debug, Deserialize, Validate)]
+pub struct CommentEditReqForm {
+    #[garde(length(min = CONTENT_MIN_LEN, max = CONTENT_MAX_LEN))]
+    pub content: String,
+}
+pub type CommentEditResForm = CommentFindResForm;
+
+// delete
+pub type CommentDeleteResForm = CommentFindResForm;
+
+// admin search
+#[derive(Debug, Deserialize, Validate)]
+pub struct CommentAdminSearchReqForm {
+    #[serde(rename = "articleId")]
+    #[garde(skip)]
+    pub article_id: Option<i32>,
+    #[serde(rename = "createUserId")]
+    #[garde(skip)]
+    pub create_user_id: Option<i32>,
+    #[serde(rename = "statusType")]
+    #[garde(range(min = STATUS_TYPE_MIN, max = STATUS_TYPE_MAX))]
+    pub status_type: Option<i32>,
+    #[serde(rename = "contentSearch")]
+    #[garde(length(min = CONTENT_SEARCH_MIN_LEN, max = CONTENT_SEARCH_MAX_LEN))]
+    pub content_search: Option<String>,
+    #[serde(rename = "pageNum", default = "default_page_num")]
+    #[garde(range(min = 1))]
+    pub page_num: u64,
+    #[serde(rename = "pageSize", default = "default_page_size")]
+    #[garde(range(min = 1))]
+    pub page_size: u64,
+}
+pub type CommentAdminSearchResForm = Page<CommentInfo>;
+
+// admin approve / reject, only pending comments are affected
+#[derive(Debug, Deserialize, Validate)]
+pub struct CommentAdminModerateReqForm {
+    #[garde(length(min = MODERATE_IDS_MIN_LEN, max = MODERATE_IDS_MAX_LEN))]
+    pub ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentAdminModerateResForm {
+    #[serde(rename = "affectedNum")]
+    pub affected_num: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_inputs() {
+        let forms = vec![
+            CommentCreateReqForm {
+                article_id: 1,
+                parent_comment_id: None,
+                content: "".to_string(), // 太短
+            },
+            CommentCreateReqForm {
+                article_id: 1,
+                parent_comment_id: Some(1),
+                content: "c".repeat(CONTENT_MAX_LEN + 1), // 太长
+            },
+        ];
+        for form in forms {
+            assert!(form.validate(&()).is_err());
+        }
+
+        // 批量审核的数量有上下限
+        let moderate_form = CommentAdminModerateReqForm { ids: Vec::new() };
+        assert!(moderate_form.validate(&()).is_err());
+        let moderate_form = CommentAdminModerateReqForm {
+            ids: (0..=MODERATE_IDS_MAX_LEN as i32).collect(),
+        };
+        assert!(moderate_form.validate(&()).is_err());
+    }
+}
@@ -1,7 +1,23 @@
 // ********************* mod ********************* //
+pub mod article;
+pub mod comment;
+pub mod series;
+pub mod site;
+pub mod tag;
+pub mod transfer;
+pub mod trash;
+pub mod upload;
 pub mod user;
 
 pub mod prelude {
+    pub use super::article::prelude::*;
+    pub use super::comment::prelude::*;
+    pub use super::series::prelude::*;
+    pub use super::site::prelude::*;
+    pub use super::tag::prelude::*;
+    pub use super::transfer::prelude::*;
+    pub use super::trash::prelude::*;
+    pub use super::upload::prelude::*;
     pub use super::user::prelude::*;
 }
 
@@ -12,3 +28,12 @@ use regex::Regex;
 // ********************* content ********************* //
 static BASIC_ASCII_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?-u:\w)+$").unwrap());
 static BASIC_UNICODE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\w+$").unwrap());
+static SLUG_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-z0-9]+(?:-[a-z0-9]+)*$").unwrap());
+
+fn default_page_size() -> u64 {
+    10
+}
+
+fn default_page_num() -> u64 {
+    1
+}
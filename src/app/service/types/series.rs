@@ -0,0 +1,149 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        SeriesArticleInfo, SeriesCreateReqForm, SeriesCreateResForm, SeriesDeleteResForm,
+        SeriesEditReqForm, SeriesEditResForm, SeriesFindResForm, SeriesInfo, SeriesInsertReqForm,
+        SeriesInsertResForm, SeriesNavInfo, SeriesRemoveResForm, SeriesReorderReqForm,
+        SeriesReorderResForm,
+    };
+}
+
+// ********************* import ********************* //
+use garde::Validate;
+use sea_orm::prelude::DateTime;
+use serde::{Deserialize, Serialize};
+
+// ********************* content ********************* //
+const TITLE_MIN_LEN: usize = 1;
+const TITLE_MAX_LEN: usize = 255;
+const DESCRIPTION_MAX_LEN: usize = 65_535;
+const ARTICLE_IDS_MAX_LEN: usize = 200;
+
+#[derive(Debug, Serialize)]
+pub struct SeriesInfo {
+    pub id: i32,
+    pub title: String,
+    pub description: String,
+    #[serde(rename = "createTime")]
+    pub create_time: DateTime,
+    #[serde(rename = "updateTime")]
+    pub update_time: DateTime,
+    #[serde(rename = "createUserId")]
+    pub create_user_id: i32,
+}
+
+// a part of the series, without the content
+#[derive(Debug, Serialize)]
+pub struct SeriesArticleInfo {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub description: String,
+    #[serde(rename = "statusType")]
+    pub status_type: i32,
+    #[serde(rename = "createTime")]
+    pub create_time: DateTime,
+}
+
+// navigation shown with an article, counting only the published parts
+#[derive(Debug, Serialize)]
+pub struct SeriesNavInfo {
+    #[serde(rename = "seriesId")]
+    pub series_id: i32,
+    #[serde(rename = "seriesTitle")]
+    pub series_title: String,
+    // 1-based
+    pub position: usize,
+    pub total: usize,
+    pub prev: Option<SeriesArticleInfo>,
+    pub next: Option<SeriesArticleInfo>,
+}
+
+// get, the public only sees published parts while the owner sees all but deleted ones
+#[derive(Debug, Serialize)]
+pub struct SeriesFindResForm {
+    #[serde(rename = "seriesInfo")]
+    pub series_info: SeriesInfo,
+    pub articles: Vec<SeriesArticleInfo>,
+}
+
+// create
+#[derive(Debug, Deserialize, Validate)]
+pub struct SeriesCreateReqForm {
+    #[garde(length(min = TITLE_MIN_LEN, max = TITLE_MAX_LEN))]
+    pub title: String,
+    #[serde(default)]
+    #[garde(length(max = DESCRIPTION_MAX_LEN))]
+    pub description: String,
+}
+pub type SeriesCreateResForm = SeriesFindResForm;
+
+// edit
+#[derive(Debug, Deserialize, Validate)]
+pub struct SeriesEditReqForm {
+    #[garde(length(min = TITLE_MIN_LEN, max = TITLE_MAX_LEN))]
+    pub title: Option<String>,
+    #[garde(length(max = DESCRIPTION_MAX_LEN))]
+    pub description: Option<String>,
+}
+pub type SeriesEditResForm = SeriesFindResForm;
+
+// delete, the articles themselves are kept
+pub type SeriesDeleteResForm = SeriesFindResForm;
+
+// reorder, every current part exactly once in the new order
+#[derive(Debug, Deserialize, Validate)]
+pub struct SeriesReorderReqForm {
+    #[serde(rename = "articleIds")]
+    #[garde(length(max = ARTICLE_IDS_MAX_LEN))]
+    pub article_ids: Vec<i32>,
+}
+pub type SeriesReorderResForm = SeriesFindResForm;
+
+// insert
+#[derive(Debug, Deserialize, Validate)]
+pub struct SeriesInsertReqForm {
+    #[serde(rename = "articleId")]
+    #[garde(skip)]
+    pub article_id: i32,
+    // 0-based, None or past the end appends
+    #[garde(skip)]
+    pub position: Option<usize>,
+}
+pub type SeriesInsertResForm = SeriesFindResForm;
+
+// remove
+pub type SeriesRemoveResForm = SeriesFindResForm;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_inputs() {
+        let forms = vec![
+            SeriesCreateReqForm {
+                title: "".to_string(), // 太短
+                description: "".to_string(),
+            },
+            SeriesCreateReqForm {
+                title: "t".repeat(TITLE_MAX_LEN + 1), // 太长
+                description: "".to_string(),
+            },
+        ];
+        for form in forms {
+            assert!(form.validate(&()).is_err());
+        }
+
+        let edit_form = SeriesEditReqForm {
+            title: Some("".to_string()), // 太短
+            description: None,
+        };
+        assert!(edit_form.validate(&()).is_err());
+
+        let reorder_form = SeriesReorderReqForm {
+            article_ids: (0..=ARTICLE_IDS_MAX_LEN as i32).collect(), // 太多
+        };
+        assert!(reorder_form.validate(&()).is_err());
+    }
+}
@@ -0,0 +1,95 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        SiteConfig, SiteDocument, SiteFeedResForm, SiteRobotsResForm, SiteSitemapResForm,
+    };
+}
+
+// ********************* import ********************* //
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+// ********************* content ********************* //
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct SiteConfig {
+    pub title: String,
+    pub description: String,
+    // number of the most recent articles in a feed
+    pub feed_item_limit: u64,
+    // a cached feed is dropped as soon as an article changes, this only bounds stale tags
+    pub feed_cache_expire_sec: u64,
+    // tags and users do not move the article generation, so they show up within this delay
+    pub sitemap_cache_expire_sec: u64,
+}
+
+impl Default for SiteConfig {
+    fn default() -> Self {
+        Self {
+            title: "NzmiStella's Space".into(),
+            description: "".into(),
+            feed_item_limit: 20,
+            feed_cache_expire_sec: 3600,
+            sitemap_cache_expire_sec: 3600,
+        }
+    }
+}
+
+// a generated document along with its validators for conditional requests
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SiteDocument {
+    pub content_type: String,
+    pub body: String,
+    pub etag: String,
+    // unix timestamp in seconds
+    pub last_modified: Option<i64>,
+}
+
+impl SiteDocument {
+    // If-None-Match wins over If-Modified-Since when both are sent
+    pub fn is_fresh(&self, if_none_match: Option<&str>, if_modified_since: Option<&str>) -> bool {
+        if let Some(if_none_match) = if_none_match {
+            return if_none_match
+                .split(',')
+                .map(|etag| etag.trim().trim_start_matches("W/"))
+                .any(|etag| etag == "*" || etag == self.etag);
+        }
+        match (if_modified_since, self.last_modified) {
+            (Some(since), Some(last_modified)) => DateTime::parse_from_rfc2822(since)
+                .is_ok_and(|since| last_modified <= since.timestamp()),
+            _ => false,
+        }
+    }
+}
+
+// rss / atom
+pub type SiteFeedResForm = SiteDocument;
+
+// sitemap.xml, either a url set or an index of the split parts
+pub type SiteSitemapResForm = SiteDocument;
+
+// robots.txt
+pub type SiteRobotsResForm = SiteDocument;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_fresh() {
+        let document = SiteDocument {
+            content_type: "application/rss+xml".to_string(),
+            body: "".to_string(),
+            etag: "\"abc\"".to_string(),
+            // Tue, 05 Mar 2024 07:08:09 GMT
+            last_modified: Some(1_709_622_489),
+        };
+        assert!(document.is_fresh(Some("\"xyz\", W/\"abc\""), None));
+        assert!(document.is_fresh(Some("*"), None));
+        assert!(!document.is_fresh(Some("\"xyz\""), Some("Tue, 05 Mar 2024 07:08:09 GMT")));
+        assert!(document.is_fresh(None, Some("Tue, 05 Mar 2024 07:08:09 GMT")));
+        assert!(!document.is_fresh(None, Some("Tue, 05 Mar 2024 07:08:08 GMT")));
+        assert!(!document.is_fresh(None, Some("not a date")));
+        assert!(!document.is_fresh(None, None));
+    }
+}
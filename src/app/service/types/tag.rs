@@ -0,0 +1,181 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        TagAdminCreateReqForm, TagAdminCreateResForm, TagAdminDeleteReqForm, TagAdminDeleteResForm,
+        TagAdminEditReqForm, TagAdminEditResForm, TagAdminMergeReqForm, TagAdminMergeResForm,
+        TagAdminMoveReqForm, TagAdminMoveResForm, TagAdminTreeResForm, TagConfig, TagFindResForm,
+        TagInfo, TagNode, TagPathResForm, TagRestructureInfo, TagRestructureResForm,
+        TagSlugFindResForm, TagTreeResForm,
+    };
+}
+
+// ********************* import ********************* //
+use garde::Validate;
+use sea_orm::prelude::DateTime;
+use serde::{Deserialize, Serialize};
+
+use super::SLUG_RE;
+use crate::app::utils::prelude::SLUG_MAX_LEN;
+
+// ********************* content ********************* //
+const NAME_MIN_LEN: usize = 1;
+const NAME_MAX_LEN: usize = 32;
+const STATUS_TYPE_MIN: i32 = 0;
+// tags are moved into the trash by deleting them, not by setting the status
+const ALIVE_STATUS_TYPE_MAX: i32 = 1;
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct TagConfig {
+    // top-level tags have depth 1
+    pub max_depth: usize,
+}
+
+impl Default for TagConfig {
+    fn default() -> Self {
+        Self { max_depth: 5 }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagInfo {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    #[serde(rename = "parentTagId")]
+    pub parent_tag_id: Option<i32>,
+    #[serde(rename = "thumbnailUrl")]
+    pub thumbnail_url: Option<String>,
+    #[serde(rename = "statusType")]
+    pub status_type: i32,
+    #[serde(rename = "createTime")]
+    pub create_time: DateTime,
+    #[serde(rename = "updateTime")]
+    pub update_time: DateTime,
+    #[serde(rename = "createUserId")]
+    pub create_user_id: i32,
+    #[serde(rename = "updateUserId")]
+    pub update_user_id: i32,
+}
+
+// rows affected by a change to the shape of the tag tree
+#[derive(Debug, Serialize)]
+pub struct TagRestructureInfo {
+    // descendants included
+    #[serde(rename = "movedTags")]
+    pub moved_tags: u64,
+    #[serde(rename = "movedArticles")]
+    pub moved_articles: u64,
+    // links of articles that already had the target tag
+    #[serde(rename = "droppedArticles")]
+    pub dropped_articles: u64,
+    #[serde(rename = "deletedTags")]
+    pub deleted_tags: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagNode {
+    #[serde(flatten)]
+    pub tag_info: TagInfo,
+    pub children: Vec<TagNode>,
+}
+
+// tree
+#[derive(Debug, Serialize)]
+pub struct TagTreeResForm {
+    pub tree: Vec<TagNode>,
+}
+
+// get
+#[derive(Debug, Serialize)]
+pub struct TagFindResForm {
+    #[serde(rename = "tagInfo")]
+    pub tag_info: TagInfo,
+}
+
+// the tag left in place after the change, along with what the change touched
+#[derive(Debug, Serialize)]
+pub struct TagRestructureResForm {
+    #[serde(rename = "tagInfo")]
+    pub tag_info: TagInfo,
+    pub summary: TagRestructureInfo,
+}
+
+// find by slug, an old slug resolves to the current one
+#[derive(Debug)]
+pub enum TagSlugFindResForm {
+    Found(TagFindResForm),
+    Moved(String),
+}
+
+// path
+#[derive(Debug, Serialize)]
+pub struct TagPathResForm {
+    // from the top-level tag down to the requested tag
+    pub path: Vec<TagInfo>,
+}
+
+// admin tree
+pub type TagAdminTreeResForm = TagTreeResForm;
+
+// admin create
+#[derive(Debug, Deserialize, Validate)]
+pub struct TagAdminCreateReqForm {
+    #[garde(length(min = NAME_MIN_LEN, max = NAME_MAX_LEN))]
+    pub name: String,
+    // generated from name when missing
+    #[garde(pattern(SLUG_RE), length(max = SLUG_MAX_LEN))]
+    pub slug: Option<String>,
+    #[serde(rename = "parentTagId")]
+    #[garde(skip)]
+    pub parent_tag_id: Option<i32>,
+    #[serde(rename = "thumbnailUrl")]
+    #[garde(url)]
+    pub thumbnail_url: Option<String>,
+}
+pub type TagAdminCreateResForm = TagFindResForm;
+
+// admin edit
+#[derive(Debug, Deserialize, Validate)]
+pub struct TagAdminEditReqForm {
+    #[garde(length(min = NAME_MIN_LEN, max = NAME_MAX_LEN))]
+    pub name: Option<String>,
+    #[garde(pattern(SLUG_RE), length(max = SLUG_MAX_LEN))]
+    pub slug: Option<String>,
+    #[serde(rename = "thumbnailUrl")]
+    #[garde(url)]
+    pub thumbnail_url: Option<String>,
+    #[serde(rename = "statusType")]
+    #[garde(range(min = STATUS_TYPE_MIN, max = ALIVE_STATUS_TYPE_MAX))]
+    pub status_type: Option<i32>,
+}
+pub type TagAdminEditResForm = TagFindResForm;
+
+// admin move, the tag takes its descendants along
+#[derive(Debug, Deserialize, Validate)]
+pub struct TagAdminMoveReqForm {
+    // None moves the tag to the top level
+    #[serde(rename = "parentTagId")]
+    #[garde(skip)]
+    pub parent_tag_id: Option<i32>,
+}
+pub type TagAdminMoveResForm = TagRestructureResForm;
+
+// admin merge, the articles and children of the tag go over to the target tag
+#[derive(Debug, Deserialize, Validate)]
+pub struct TagAdminMergeReqForm {
+    #[serde(rename = "targetTagId")]
+    #[garde(skip)]
+    pub target_tag_id: i32,
+}
+pub type TagAdminMergeResForm = TagRestructureResForm;
+
+// admin delete, the tag is moved into the trash
+#[derive(Debug, Deserialize, Validate)]
+pub struct TagAdminDeleteReqForm {
+    // hand the children over to the parent of the tag instead of refusing to delete
+    #[serde(rename = "reparentChildren", default)]
+    #[garde(skip)]
+    pub reparent_children: bool,
+}
+pub type TagAdminDeleteResForm = TagRestructureResForm;
@@ -0,0 +1,38 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        TransferExportResForm, TransferImportReqForm, TransferImportResForm, TransferSkipInfo,
+    };
+}
+
+// ********************* import ********************* //
+use garde::Validate;
+use serde::{Deserialize, Serialize};
+
+// ********************* content ********************* //
+// export, a zip of "{slug}.md" files with front matter
+pub type TransferExportResForm = Vec<u8>;
+
+// import, the request body is a zip of markdown files
+#[derive(Debug, Deserialize, Validate)]
+pub struct TransferImportReqForm {
+    // overwrite the article with the same slug instead of skipping the file
+    #[serde(default)]
+    #[garde(skip)]
+    pub upsert: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransferSkipInfo {
+    pub file: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct TransferImportResForm {
+    pub created: u64,
+    pub updated: u64,
+    #[serde(rename = "createdTags")]
+    pub created_tags: u64,
+    pub skipped: Vec<TransferSkipInfo>,
+}
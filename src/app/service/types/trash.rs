@@ -0,0 +1,98 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        TrashConfig, TrashItemInfo, TrashKind, TrashPurgeResForm, TrashRestoreResForm,
+        TrashSearchReqForm, TrashSearchResForm,
+    };
+}
+
+// ********************* import ********************* //
+use garde::Validate;
+use sea_orm::prelude::DateTime;
+use serde::{Deserialize, Serialize};
+
+use super::{default_page_num, default_page_size};
+use crate::app::utils::prelude::Page;
+
+// ********************* content ********************* //
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct TrashConfig {
+    // data stays in the trash this long before it is purged
+    pub retention_days: u64,
+    pub purge_interval_sec: u64,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: 30,
+            purge_interval_sec: 3600,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrashKind {
+    Article,
+    Tag,
+    User,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TrashItemInfo {
+    pub kind: TrashKind,
+    pub id: i32,
+    // article title, tag name or username
+    pub name: String,
+    #[serde(rename = "statusType")]
+    pub status_type: i32,
+    // None for data deleted before the delete time was recorded
+    #[serde(rename = "deleteTime")]
+    pub delete_time: Option<DateTime>,
+    #[serde(rename = "deleteUserId")]
+    pub delete_user_id: Option<i32>,
+}
+
+// search, the latest deleted first
+#[derive(Debug, Deserialize, Validate)]
+pub struct TrashSearchReqForm {
+    #[serde(rename = "pageNum", default = "default_page_num")]
+    #[garde(range(min = 1))]
+    pub page_num: u64,
+    #[serde(rename = "pageSize", default = "default_page_size")]
+    #[garde(range(min = 1))]
+    pub page_size: u64,
+}
+pub type TrashSearchResForm = Page<TrashItemInfo>;
+
+// restore
+pub type TrashRestoreResForm = TrashItemInfo;
+
+// purge, the number of data deleted for good
+#[derive(Debug, Default, Serialize)]
+pub struct TrashPurgeResForm {
+    pub articles: u64,
+    pub tags: u64,
+    pub users: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trash_kind() {
+        let kind: TrashKind = serde_json::from_str("\"article\"").unwrap();
+        assert_eq!(kind, TrashKind::Article);
+        // 评论没有回收站
+        assert!(serde_json::from_str::<TrashKind>("\"comment\"").is_err());
+
+        let search_form = TrashSearchReqForm {
+            page_num: 0,
+            page_size: 10,
+        };
+        assert!(search_form.validate(&()).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+// ********************* mod ********************* //
+pub mod prelude {
+    pub use super::{
+        UploadConfig, UploadConfirmReqForm, UploadConfirmResForm, UploadCreateResForm, UploadFile,
+        UploadFileContent, UploadFileResForm, UploadInfo, UploadMineSearchReqForm,
+        UploadMineSearchResForm, UploadPresignReqForm, UploadPresignResForm,
+        UPLOAD_STAGING_KEY_PREFIX,
+    };
+}
+
+// ********************* import ********************* //
+use garde::Validate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use sea_orm::prelude::DateTime;
+use serde::{Deserialize, Serialize};
+
+use super::{default_page_num, default_page_size};
+use crate::app::utils::prelude::Page;
+
+// ********************* content ********************* //
+const FILE_NAME_MIN_LEN: usize = 1;
+const FILE_NAME_MAX_LEN: usize = 255;
+
+// direct uploads land under this prefix until they are confirmed, a bucket lifecycle rule
+// may clean up the ones never confirmed
+pub const UPLOAD_STAGING_KEY_PREFIX: &str = "staging-";
+static STAGING_KEY_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^staging-[0-9a-f]{32}$").unwrap());
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct UploadConfig {
+    // in bytes
+    pub max_size: usize,
+    pub allowed_mime_types: Vec<String>,
+    // files never change under their key, so clients may keep them for long
+    pub cache_max_age_sec: u64,
+}
+
+impl Default for UploadConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 5 * 1024 * 1024,
+            allowed_mime_types: vec![
+                "image/png".into(),
+                "image/jpeg".into(),
+                "image/gif".into(),
+                "image/webp".into(),
+            ],
+            cache_max_age_sec: 31_536_000,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct UploadInfo {
+    pub id: i32,
+    // ready to be used as an avatar or thumbnail url
+    pub url: String,
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    pub size: i64,
+    #[serde(rename = "createTime")]
+    pub create_time: DateTime,
+    #[serde(rename = "createUserId")]
+    pub create_user_id: i32,
+}
+
+// a file as sent in the multipart body, the declared type is not trusted
+#[derive(Debug)]
+pub struct UploadFile {
+    pub file_name: String,
+    pub data: Vec<u8>,
+}
+
+// create
+pub type UploadCreateResForm = UploadInfo;
+
+// mine search, the latest uploaded first
+#[derive(Debug, Deserialize, Validate)]
+pub struct UploadMineSearchReqForm {
+    #[serde(rename = "pageNum", default = "default_page_num")]
+    #[garde(range(min = 1))]
+    pub page_num: u64,
+    #[serde(rename = "pageSize", default = "default_page_size")]
+    #[garde(range(min = 1))]
+    pub page_size: u64,
+}
+pub type UploadMineSearchResForm = Page<UploadInfo>;
+
+// presign, for a PUT straight to the storage with the given content type
+#[derive(Debug, Deserialize, Validate)]
+pub struct UploadPresignReqForm {
+    #[serde(rename = "fileName")]
+    #[garde(length(min = FILE_NAME_MIN_LEN, max = FILE_NAME_MAX_LEN))]
+    pub file_name: String,
+    #[serde(rename = "mimeType")]
+    #[garde(skip)]
+    pub mime_type: String,
+    #[garde(range(min = 1))]
+    pub size: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UploadPresignResForm {
+    #[serde(rename = "uploadKey")]
+    pub upload_key: String,
+    #[serde(rename = "uploadUrl")]
+    pub upload_url: String,
+    #[serde(rename = "contentType")]
+    pub content_type: String,
+    #[serde(rename = "expireSec")]
+    pub expire_sec: u64,
+}
+
+// confirm, the file is checked like a regular upload and moved to its content key
+#[derive(Debug, Deserialize, Validate)]
+pub struct UploadConfirmReqForm {
+    #[serde(rename = "uploadKey")]
+    #[garde(pattern(STAGING_KEY_RE))]
+    pub upload_key: String,
+    #[serde(rename = "fileName")]
+    #[garde(length(min = FILE_NAME_MIN_LEN, max = FILE_NAME_MAX_LEN))]
+    pub file_name: String,
+}
+pub type UploadConfirmResForm = UploadInfo;
+
+// file
+#[derive(Debug)]
+pub enum UploadFileResForm {
+    Content(UploadFileContent),
+    // the storage serves the file itself, the url is only valid for a while
+    Redirect { url: String, cache_max_age_sec: u64 },
+}
+
+#[derive(Debug)]
+pub struct UploadFileContent {
+    pub mime_type: String,
+    pub data: Vec<u8>,
+    pub etag: String,
+    pub cache_max_age_sec: u64,
+}
+
+impl UploadFileContent {
+    pub fn is_fresh(&self, if_none_match: Option<&str>) -> bool {
+        if_none_match.is_some_and(|if_none_match| {
+            if_none_match
+                .split(',')
+                .map(|etag| etag.trim().trim_start_matches("W/"))
+                .any(|etag| etag == "*" || etag == self.etag)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::utils::prelude::storage_key;
+
+    #[test]
+    fn test_is_fresh() {
+        let res_form = UploadFileContent {
+            mime_type: "image/png".to_string(),
+            data: vec![],
+            etag: "\"abc.png\"".to_string(),
+            cache_max_age_sec: 60,
+        };
+        assert!(res_form.is_fresh(Some("\"xyz.png\", W/\"abc.png\"")));
+        assert!(res_form.is_fresh(Some("*")));
+        assert!(!res_form.is_fresh(Some("\"xyz.png\"")));
+        assert!(!res_form.is_fresh(None));
+    }
+
+    #[test]
+    fn test_confirm_form() {
+        let confirm_form = UploadConfirmReqForm {
+            upload_key: format!("{}{:032x}", UPLOAD_STAGING_KEY_PREFIX, 42),
+            file_name: "a.png".to_string(),
+        };
+        assert!(confirm_form.validate(&()).is_ok());
+        // only staging keys can be confirmed
+        let confirm_form = UploadConfirmReqForm {
+            upload_key: storage_key(b"hello", "image/png"),
+            ..confirm_form
+        };
+        assert!(confirm_form.validate(&()).is_err());
+    }
+}
@@ -1,19 +1,22 @@
 // ********************* mod ********************* //
 pub mod prelude {
     pub use super::{
-        UserAdminEditReqForm, UserAdminEditResForm, UserAdminGetResForm, UserAdminSearchReqForm,
-        UserAdminSearchResForm, UserAvailabilityReqForm, UserAvailabilityResForm,
-        UserChangePasswordReqForm, UserChangePasswordResForm, UserEditReqForm, UserEditResForm,
-        UserFindResForm, UserInfo, UserLoginReqForm, UserLoginResForm, UserRegisterReqForm,
-        UserRegisterResForm, UserSearchReqForm, UserSearchResForm,
+        UserAdminDeleteResForm, UserAdminEditReqForm, UserAdminEditResForm, UserAdminGetResForm,
+        UserAdminSearchReqForm, UserAdminSearchResForm, UserAvailabilityReqForm,
+        UserAvailabilityResForm, UserChangePasswordReqForm, UserChangePasswordResForm,
+        UserEditReqForm, UserEditResForm, UserFindResForm, UserIdenticonFormat,
+        UserIdenticonReqForm, UserIdenticonResForm, UserInfo, UserLoginReqForm, UserLoginResForm,
+        UserRegisterReqForm, UserRegisterResForm, UserSearchReqForm, UserSearchResForm,
     };
 }
 
 // ********************* import ********************* //
+use std::collections::BTreeMap;
+
 use garde::Validate;
 use serde::{Deserialize, Serialize};
 
-use super::{BASIC_ASCII_RE, BASIC_UNICODE_RE};
+use super::{default_page_num, default_page_size, BASIC_ASCII_RE, BASIC_UNICODE_RE};
 use crate::app::utils::prelude::Page;
 
 // ********************* content ********************* //
@@ -29,16 +32,12 @@ const GROUP_TYPE_MIN: i32 = 0;
 const GROUP_TYPE_MAX: i32 = 1;
 const STATUS_TYPE_MIN: i32 = 0;
 const STATUS_TYPE_MAX: i32 = 3;
+// users are moved into the trash by deleting them, not by setting the status
+const ALIVE_STATUS_TYPE_MAX: i32 = 2;
 const NAME_SEARCH_MIN_LEN: usize = 1;
 const NAME_SEARCH_MAX_LEN: usize = 16;
-
-fn default_page_size() -> u64 {
-    10
-}
-
-fn default_page_num() -> u64 {
-    1
-}
+const IDENTICON_SIZE_MIN: u32 = 16;
+const IDENTICON_SIZE_MAX: u32 = 1024;
 
 #[derive(Debug, Serialize)]
 pub struct UserInfo {
@@ -46,8 +45,12 @@ pub struct UserInfo {
     pub username: String,
     pub nickname: String,
     pub email: String,
+    // the generated identicon when no avatar is set, so every client shows the same default
     #[serde(rename = "avatarUrl")]
-    pub avatar_url: Option<String>,
+    pub avatar_url: String,
+    // edge length to url, None for avatars hosted elsewhere
+    #[serde(rename = "avatarVariants")]
+    pub avatar_variants: Option<BTreeMap<u32, String>>,
     pub signature: String,
     #[serde(rename = "groupType")]
     pub group_type: i32,
@@ -144,6 +147,35 @@ pub struct UserEditReqForm {
 }
 pub type UserEditResForm = UserFindResForm;
 
+// identicon
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UserIdenticonFormat {
+    #[default]
+    Svg,
+    Png,
+}
+
+fn default_identicon_size() -> u32 {
+    128
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UserIdenticonReqForm {
+    #[serde(default)]
+    #[garde(skip)]
+    pub format: UserIdenticonFormat,
+    // only used for png, svg scales by itself
+    #[serde(default = "default_identicon_size")]
+    #[garde(range(min = IDENTICON_SIZE_MIN, max = IDENTICON_SIZE_MAX))]
+    pub size: u32,
+}
+#[derive(Debug)]
+pub struct UserIdenticonResForm {
+    pub mime_type: &'static str,
+    pub data: Vec<u8>,
+}
+
 // admin search
 #[derive(Debug, Deserialize, Validate)]
 pub struct UserAdminSearchReqForm {
@@ -175,11 +207,14 @@ pub struct UserAdminEditReqForm {
     #[garde(range(min = GROUP_TYPE_MIN, max = GROUP_TYPE_MAX))]
     pub group_type: Option<i32>,
     #[serde(rename = "statusType")]
-    #[garde(range(min = STATUS_TYPE_MIN, max = STATUS_TYPE_MAX))]
+    #[garde(range(min = STATUS_TYPE_MIN, max = ALIVE_STATUS_TYPE_MAX))]
     pub status_type: Option<i32>,
 }
 pub type UserAdminEditResForm = UserEditResForm;
 
+// admin delete, the user is moved into the trash and logged out
+pub type UserAdminDeleteResForm = UserFindResForm;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -222,5 +257,12 @@ mod tests {
         for form in forms {
             assert!(form.validate(&()).is_err());
         }
+
+        // 管理员不能直接将用户置为删除状态
+        let edit_form = UserAdminEditReqForm {
+            group_type: None,
+            status_type: Some(3),
+        };
+        assert!(edit_form.validate(&()).is_err());
     }
 }
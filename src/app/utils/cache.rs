@@ -12,6 +12,8 @@ pub trait CacheUtilsTrait {
     async fn del(&self, key: &str) -> AppResult<()>;
     async fn exists(&self, key: &str) -> AppResult<bool>;
     async fn expire(&self, key: &str, expire_sec: u64) -> AppResult<()>;
+    // integer
+    async fn incr(&self, key: &str, delta: i64) -> AppResult<i64>;
     // string
     async fn get<T: for<'a> Deserialize<'a>>(&self, key: &str) -> AppResult<Option<T>>;
     async fn set<T: Serialize + Send>(
@@ -20,6 +22,13 @@ pub trait CacheUtilsTrait {
         value: T,
         expire_sec: Option<u64>,
     ) -> AppResult<()>;
+    // only set when the key does not exist, return whether it was set
+    async fn set_nx<T: Serialize + Send>(
+        &self,
+        key: &str,
+        value: T,
+        expire_sec: Option<u64>,
+    ) -> AppResult<bool>;
 }
 
 pub trait CacheUtilsProvider {
@@ -120,6 +129,13 @@ impl CacheUtilsTrait for RedisCacheUtils {
             )
     }
 
+    async fn incr(&self, key: &str, delta: i64) -> AppResult<i64> {
+        self.get_conn().await?.incr(key, delta).await.wrap_with(
+            || format!("Failed to increment key: {} by {}", key, delta),
+            AppErrorKind::CacheOperationError,
+        )
+    }
+
     async fn get<T: for<'a> Deserialize<'a>>(&self, key: &str) -> AppResult<Option<T>> {
         let value_opt: Option<String> = self.get_conn().await?.get(key).await.wrap_with(
             || format!("Failed to get value by key: {}", key),
@@ -159,6 +175,31 @@ impl CacheUtilsTrait for RedisCacheUtils {
             AppErrorKind::CacheOperationError,
         )
     }
+
+    async fn set_nx<T: Serialize + Send>(
+        &self,
+        key: &str,
+        value: T,
+        expire_sec: Option<u64>,
+    ) -> AppResult<bool> {
+        let value_str = serde_json::to_string(&value).wrap(
+            "Failed to serialize value",
+            AppErrorKind::CacheOperationError,
+        )?;
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(key).arg(&value_str).arg("NX");
+        if let Some(expire_sec) = expire_sec {
+            cmd.arg("EX").arg(expire_sec);
+        }
+        let reply: Option<String> = cmd
+            .query_async(&mut self.get_conn().await?)
+            .await
+            .wrap_with(
+                || format!("Failed to set key: {} value: {}", key, value_str),
+                AppErrorKind::CacheOperationError,
+            )?;
+        Ok(reply.is_some())
+    }
 }
 
 #[cfg(test)]
@@ -256,8 +297,49 @@ mod tests {
         let invalid_struct: Result<Option<TestStruct>, _> = cache.get("invalid_struct_key").await;
         assert!(invalid_struct.is_err());
 
+        // 测试 set_nx 仅在键不存在时成功
+        cache
+            .del("test_nx_key")
+            .await
+            .expect("Failed to delete key");
+        let is_set = cache
+            .set_nx("test_nx_key", "first", Some(10))
+            .await
+            .expect("Failed to set key");
+        assert!(is_set);
+        let is_set = cache
+            .set_nx("test_nx_key", "second", Some(10))
+            .await
+            .expect("Failed to set key");
+        assert!(!is_set);
+        let value: Option<String> = cache.get("test_nx_key").await.expect("Failed to get key");
+        assert_eq!(value, Some("first".into()));
+
+        // 测试 incr 在键不存在时从0开始，且值可被 get 读取
+        cache
+            .del("test_incr_key")
+            .await
+            .expect("Failed to delete key");
+        let value = cache
+            .incr("test_incr_key", 2)
+            .await
+            .expect("Failed to increment key");
+        assert_eq!(value, 2);
+        let value = cache
+            .incr("test_incr_key", -1)
+            .await
+            .expect("Failed to increment key");
+        assert_eq!(value, 1);
+        let value: Option<i64> = cache.get("test_incr_key").await.expect("Failed to get key");
+        assert_eq!(value, Some(1));
+
         // 清理
         let _: () = cache.del("test_key").await.expect("Failed to clean up");
+        let _: () = cache
+            .del("test_incr_key")
+            .await
+            .expect("Failed to clean up");
+        let _: () = cache.del("test_nx_key").await.expect("Failed to clean up");
         let _: () = cache
             .del("test_struct_key")
             .await
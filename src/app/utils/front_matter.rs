@@ -0,0 +1,271 @@
+// ********************* import ********************* //
+use std::collections::HashSet;
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
+use serde::{Serialize, Serializer};
+use serde_yaml::{Mapping, Value};
+
+use crate::app::common::prelude::*;
+
+// ********************* content ********************* //
+const DELIMITER: &str = "---";
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// the yaml block at the head of a markdown file, keys of Hexo and Jekyll are accepted too
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct FrontMatter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    #[serde(serialize_with = "serialize_time")]
+    pub date: Option<NaiveDateTime>,
+    #[serde(serialize_with = "serialize_time")]
+    pub updated: Option<NaiveDateTime>,
+    // draft, published, hidden or deleted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    // username of the author
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownDoc {
+    pub front_matter: FrontMatter,
+    pub content: String,
+}
+
+fn serialize_time<S: Serializer>(
+    time: &Option<NaiveDateTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match time {
+        Some(time) => serializer.serialize_str(&time.format(TIME_FORMAT).to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn format_markdown(doc: &MarkdownDoc) -> AppResult<String> {
+    let yaml = serde_yaml::to_string(&doc.front_matter)
+        .wrap("Failed to serialize front matter", AppErrorKind::default())?;
+    Ok(format!(
+        "{}\n{}{}\n\n{}",
+        DELIMITER, yaml, DELIMITER, doc.content
+    ))
+}
+
+// the file name fills in what the front matter leaves out, Jekyll posts are named
+// "YYYY-MM-DD-slug.md"
+pub fn parse_markdown(file_name: &str, text: &str) -> AppResult<MarkdownDoc> {
+    let text = text.trim_start_matches('\u{feff}');
+    let (yaml, content) = split_front_matter(text);
+    let mapping = match yaml {
+        Some(yaml) => match serde_yaml::from_str::<Value>(yaml).wrap_with(
+            || format!("Invalid front matter, file: {}", file_name),
+            AppErrorKind::RequestParamInvalid,
+        )? {
+            Value::Mapping(mapping) => mapping,
+            Value::Null => Mapping::new(),
+            _ => {
+                return Err(AppError::new(
+                    format!("Front matter is not a mapping, file: {}", file_name),
+                    AppErrorKind::RequestParamInvalid,
+                ))
+            }
+        },
+        None => Mapping::new(),
+    };
+
+    let stem = file_name
+        .rsplit('/')
+        .next()
+        .unwrap_or(file_name)
+        .trim_end_matches(".markdown")
+        .trim_end_matches(".md");
+    let (stem_date, stem_slug) = match stem.get(..11).and_then(|prefix| {
+        let date = prefix.strip_suffix('-')?;
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+    }) {
+        Some(date) => (date.and_hms_opt(0, 0, 0), &stem[11..]),
+        None => (None, stem),
+    };
+
+    let permalink_slug = get_str(&mapping, &["permalink"]).and_then(|permalink| {
+        permalink
+            .split('/')
+            .rfind(|segment| !segment.is_empty())
+            .map(|segment| segment.trim_end_matches(".html").to_string())
+    });
+    let mut tags = get_list(&mapping, "tags");
+    for category in get_list(&mapping, "categories") {
+        if !tags.contains(&category) {
+            tags.push(category);
+        }
+    }
+    let status = get_str(&mapping, &["status"]).or_else(|| {
+        let is_draft = mapping.get("draft").and_then(Value::as_bool) == Some(true)
+            || mapping.get("published").and_then(Value::as_bool) == Some(false);
+        is_draft.then(|| "draft".to_string())
+    });
+    let front_matter = FrontMatter {
+        title: get_str(&mapping, &["title"]).or_else(|| Some(stem_slug.to_string())),
+        slug: get_str(&mapping, &["slug"])
+            .or(permalink_slug)
+            .or_else(|| Some(stem_slug.to_string()))
+            .filter(|slug| !slug.is_empty()),
+        description: get_str(&mapping, &["description", "excerpt"]),
+        tags,
+        date: get_str(&mapping, &["date"])
+            .and_then(|date| parse_time(&date))
+            .or(stem_date),
+        updated: get_str(&mapping, &["updated", "lastmod", "last_modified_at"])
+            .and_then(|updated| parse_time(&updated)),
+        status,
+        author: get_str(&mapping, &["author"]),
+    };
+    Ok(MarkdownDoc {
+        front_matter,
+        content: content.trim_start_matches(['\r', '\n']).to_string(),
+    })
+}
+
+// (yaml, content), the yaml block is delimited by "---" lines
+fn split_front_matter(text: &str) -> (Option<&str>, &str) {
+    let Some(rest) = text
+        .strip_prefix(DELIMITER)
+        .and_then(|rest| rest.strip_prefix('\n').or(rest.strip_prefix("\r\n")))
+    else {
+        return (None, text);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed == DELIMITER || trimmed == "..." {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, text)
+}
+
+// the first of the keys with a scalar value
+fn get_str(mapping: &Mapping, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| match mapping.get(*key)? {
+            Value::String(value) => Some(value.trim().to_string()),
+            Value::Number(value) => Some(value.to_string()),
+            Value::Bool(value) => Some(value.to_string()),
+            _ => None,
+        })
+        .find(|value| !value.is_empty())
+}
+
+// a comma separated string or a list, nested lists (Hexo category paths) are flattened
+fn get_list(mapping: &Mapping, key: &str) -> Vec<String> {
+    fn collect(value: &Value, out: &mut Vec<String>) {
+        match value {
+            Value::String(value) => out.extend(
+                value
+                    .split(',')
+                    .map(|item| item.trim().to_string())
+                    .filter(|item| !item.is_empty()),
+            ),
+            Value::Number(value) => out.push(value.to_string()),
+            Value::Sequence(values) => values.iter().for_each(|value| collect(value, out)),
+            _ => {}
+        }
+    }
+    let mut items = Vec::new();
+    if let Some(value) = mapping.get(key) {
+        collect(value, &mut items);
+    }
+    let mut seen = HashSet::new();
+    items.retain(|item| seen.insert(item.clone()));
+    items
+}
+
+// times with an offset are converted to local time, the way the database stores them
+fn parse_time(text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    for format in ["%Y-%m-%d %H:%M:%S %z", "%Y-%m-%d %H:%M %z"] {
+        if let Ok(time) = DateTime::parse_from_str(text, format) {
+            return Some(time.with_timezone(&Local).naive_local());
+        }
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Some(time.with_timezone(&Local).naive_local());
+    }
+    for format in [TIME_FORMAT, "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(text, format) {
+            return Some(time);
+        }
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_and_parse_markdown() {
+        let doc = MarkdownDoc {
+            front_matter: FrontMatter {
+                title: Some("Hello: World".to_string()),
+                slug: Some("hello-world".to_string()),
+                description: None,
+                tags: vec!["rust".to_string(), "数据库".to_string()],
+                date: parse_time("2024-01-02 03:04:05"),
+                updated: parse_time("2024-02-03 04:05:06"),
+                status: Some("published".to_string()),
+                author: Some("admin".to_string()),
+            },
+            content: "# Hello\n\n---\n\ntext".to_string(),
+        };
+        let text = format_markdown(&doc).unwrap();
+        assert!(text.starts_with("---\ntitle: 'Hello: World'\n"));
+        assert_eq!(parse_markdown("hello-world.md", &text).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_parse_hexo_and_jekyll() {
+        let hexo = "---\ntitle: 你好\ndate: 2020-05-06 07:08:09\ntags: [a, b]\n\
+                    categories:\n- [c, d]\npermalink: /posts/hi/\n---\nbody\n";
+        let doc = parse_markdown("source/_posts/hi.md", hexo).unwrap();
+        assert_eq!(doc.front_matter.slug.as_deref(), Some("hi"));
+        assert_eq!(doc.front_matter.tags, vec!["a", "b", "c", "d"]);
+        assert_eq!(doc.front_matter.date, parse_time("2020-05-06 07:08:09"));
+        assert_eq!(doc.content, "body\n");
+
+        let jekyll = "---\nlayout: post\ntitle: Hi\ntags: x, y\npublished: false\n---\nbody";
+        let doc = parse_markdown("_posts/2019-03-04-my-post.md", jekyll).unwrap();
+        assert_eq!(doc.front_matter.slug.as_deref(), Some("my-post"));
+        assert_eq!(doc.front_matter.tags, vec!["x", "y"]);
+        assert_eq!(doc.front_matter.date, parse_time("2019-03-04"));
+        assert_eq!(doc.front_matter.status.as_deref(), Some("draft"));
+
+        let plain = parse_markdown("note.md", "no front matter").unwrap();
+        assert_eq!(plain.front_matter.title.as_deref(), Some("note"));
+        assert_eq!(plain.content, "no front matter");
+        assert!(parse_markdown("bad.md", "---\n- a\n---\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_time() {
+        let time = parse_time("2024-01-02 03:04:05").unwrap();
+        assert_eq!(time.to_string(), "2024-01-02 03:04:05");
+        assert_eq!(parse_time("2024-01-02T03:04:05"), Some(time));
+        assert_eq!(
+            parse_time("2024-01-02").unwrap().to_string(),
+            "2024-01-02 00:00:00"
+        );
+        assert!(parse_time("2024-01-02 03:04:05 +0800").is_some());
+        assert!(parse_time("yesterday").is_none());
+    }
+}
@@ -0,0 +1,131 @@
+// ********************* import ********************* //
+use image::{codecs::png::PngEncoder, Rgb, RgbImage};
+use ring::digest::{digest, SHA256};
+
+use crate::app::common::prelude::*;
+
+// ********************* content ********************* //
+const GRID: usize = 5;
+const BACKGROUND: [u8; 3] = [0xf0, 0xf0, 0xf0];
+
+// a 5x5 pattern mirrored around the middle column, the same seed always gives the same one
+#[derive(Debug, PartialEq)]
+pub struct Identicon {
+    color: [u8; 3],
+    cells: [[bool; GRID]; GRID],
+}
+
+impl Identicon {
+    pub fn new(seed: &[u8]) -> Self {
+        let hash = digest(&SHA256, seed);
+        let hash = hash.as_ref();
+        let hue = u16::from_be_bytes([hash[0], hash[1]]) as f64 % 360.0;
+        let mut cells = [[false; GRID]; GRID];
+        // the left three columns come from the hash, the right two mirror them
+        for (i, byte) in hash[2..2 + GRID * 3].iter().enumerate() {
+            let (row, col) = (i / 3, i % 3);
+            cells[row][col] = byte % 2 == 0;
+            cells[row][GRID - 1 - col] = cells[row][col];
+        }
+        Self {
+            color: hsl_to_rgb(hue, 0.55, 0.5),
+            cells,
+        }
+    }
+
+    pub fn svg(&self) -> String {
+        let mut rects = String::new();
+        for (y, row) in self.cells.iter().enumerate() {
+            for (x, on) in row.iter().enumerate() {
+                if *on {
+                    rects.push_str(&format!(
+                        r#"<rect x="{}" y="{}" width="1" height="1"/>"#,
+                        x, y
+                    ));
+                }
+            }
+        }
+        // half a cell of margin around the pattern
+        format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-0.5 -0.5 6 6" "#,
+                r#"shape-rendering="crispEdges">"#,
+                r#"<rect x="-0.5" y="-0.5" width="6" height="6" fill="{}"/>"#,
+                r#"<g fill="{}">{}</g></svg>"#
+            ),
+            hex_color(BACKGROUND),
+            hex_color(self.color),
+            rects
+        )
+    }
+
+    pub fn png(&self, size: u32) -> AppResult<Vec<u8>> {
+        let cell_of = |px: u32| {
+            let pos = (px as f64 + 0.5) * (GRID + 1) as f64 / size as f64 - 0.5;
+            (0.0..GRID as f64).contains(&pos).then_some(pos as usize)
+        };
+        let image = RgbImage::from_fn(size, size, |x, y| match (cell_of(x), cell_of(y)) {
+            (Some(x), Some(y)) if self.cells[y][x] => Rgb(self.color),
+            _ => Rgb(BACKGROUND),
+        });
+        let mut data = Vec::new();
+        image
+            .write_with_encoder(PngEncoder::new(&mut data))
+            .wrap("Failed to encode identicon", AppErrorKind::InternalError)?;
+        Ok(data)
+    }
+}
+
+fn hex_color(color: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> [u8; 3] {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 / 60 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    [r, g, b].map(|c| ((c + m) * 255.0).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identicon() {
+        let identicon = Identicon::new(b"1");
+        assert_eq!(identicon, Identicon::new(b"1"));
+        assert_ne!(identicon, Identicon::new(b"2"));
+        for row in identicon.cells {
+            assert_eq!(row[0], row[4]);
+            assert_eq!(row[1], row[3]);
+        }
+
+        let svg = identicon.svg();
+        assert!(svg.starts_with("<svg "));
+        let cell_count = identicon.cells.iter().flatten().filter(|on| **on).count();
+        assert_eq!(svg.matches(r#"width="1""#).count(), cell_count);
+
+        let png = identicon.png(64).unwrap();
+        let image = image::load_from_memory(&png).unwrap().to_rgb8();
+        assert_eq!(image.dimensions(), (64, 64));
+        assert_eq!(image.get_pixel(0, 0), &Rgb(BACKGROUND));
+        // the centre of the top left cell
+        let expected = match identicon.cells[0][0] {
+            true => identicon.color,
+            false => BACKGROUND,
+        };
+        assert_eq!(image.get_pixel(10, 10), &Rgb(expected));
+
+        assert_eq!(hsl_to_rgb(0.0, 1.0, 0.5), [255, 0, 0]);
+        assert_eq!(hsl_to_rgb(240.0, 1.0, 0.5), [0, 0, 255]);
+    }
+}
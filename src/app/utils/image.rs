@@ -0,0 +1,264 @@
+// ********************* interface ********************* //
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::app::common::prelude::AppResult;
+
+#[async_trait]
+pub trait ImageUtilsTrait {
+    // re-encode in the same format with the orientation applied, which drops exif (gps
+    // included) and any other metadata, gif carries no exif and is kept as it is
+    async fn strip_metadata(&self, data: Vec<u8>, mime_type: &str) -> AppResult<Vec<u8>>;
+    // scaled down to fit in a size x size box, encoded in the variant format
+    async fn variant(&self, data: Vec<u8>, size: u32) -> AppResult<Vec<u8>>;
+    fn variant_sizes(&self) -> &[u32];
+    fn variant_mime_type(&self) -> &'static str;
+}
+
+pub trait ImageUtilsProvider {
+    type ImageUtils: ImageUtilsTrait;
+    fn image_utils(&self) -> &Self::ImageUtils;
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct ImageConfig {
+    // edge lengths in pixels, only these variants are ever generated
+    pub variant_sizes: Vec<u32>,
+    pub variant_format: String, // webp jpeg
+    pub jpeg_quality: u8,
+    // larger images are refused before they are decoded
+    pub max_dimension: u32,
+}
+
+impl Default for ImageConfig {
+    fn default() -> Self {
+        Self {
+            variant_sizes: vec![64, 128, 512],
+            variant_format: "webp".into(),
+            jpeg_quality: 90,
+            max_dimension: 8192,
+        }
+    }
+}
+
+// ********************* implementation ********************* //
+use std::io::Cursor;
+
+use image::{
+    codecs::{jpeg::JpegEncoder, png::PngEncoder, webp::WebPEncoder},
+    imageops::FilterType,
+    metadata::Orientation,
+    DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits,
+};
+
+use crate::app::common::prelude::{AppError, AppErrorKind, WrapToAppResult};
+
+#[derive(Clone)]
+pub struct RasterImageUtils {
+    variant_sizes: Vec<u32>,
+    variant_format: ImageFormat,
+    jpeg_quality: u8,
+    max_dimension: u32,
+}
+
+impl RasterImageUtils {
+    pub fn new(cfg: &ImageConfig) -> AppResult<Self> {
+        let variant_format = match cfg.variant_format.as_str() {
+            "webp" => ImageFormat::WebP,
+            "jpeg" => ImageFormat::Jpeg,
+            format => {
+                return Err(AppError::new(
+                    format!("Unsupported variant format: {}", format),
+                    AppErrorKind::ConfigurationError,
+                ))
+            }
+        };
+        Ok(Self {
+            variant_sizes: cfg.variant_sizes.clone(),
+            variant_format,
+            jpeg_quality: cfg.jpeg_quality.clamp(1, 100),
+            max_dimension: cfg.max_dimension,
+        })
+    }
+
+    fn decode(&self, data: &[u8]) -> AppResult<DynamicImage> {
+        let mut reader = ImageReader::new(Cursor::new(data))
+            .with_guessed_format()
+            .wrap(
+                "Failed to guess image format",
+                AppErrorKind::UnsupportedMediaType,
+            )?;
+        let mut limits = Limits::default();
+        limits.max_image_width = Some(self.max_dimension);
+        limits.max_image_height = Some(self.max_dimension);
+        reader.limits(limits);
+        let mut decoder = reader
+            .into_decoder()
+            .wrap("Failed to read image", AppErrorKind::UnsupportedMediaType)?;
+        let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+        let mut image = DynamicImage::from_decoder(decoder)
+            .wrap("Failed to decode image", AppErrorKind::UnsupportedMediaType)?;
+        image.apply_orientation(orientation);
+        Ok(image)
+    }
+
+    fn encode(&self, image: &DynamicImage, format: ImageFormat) -> AppResult<Vec<u8>> {
+        let mut data = Vec::new();
+        let res = match format {
+            // neither has an alpha channel or more than 8 bits to offer
+            ImageFormat::Jpeg => image
+                .to_rgb8()
+                .write_with_encoder(JpegEncoder::new_with_quality(&mut data, self.jpeg_quality)),
+            ImageFormat::WebP if image.color().has_alpha() => image
+                .to_rgba8()
+                .write_with_encoder(WebPEncoder::new_lossless(&mut data)),
+            ImageFormat::WebP => image
+                .to_rgb8()
+                .write_with_encoder(WebPEncoder::new_lossless(&mut data)),
+            _ => image.write_with_encoder(PngEncoder::new(&mut data)),
+        };
+        res.wrap_with(
+            || format!("Failed to encode image as {:?}", format),
+            AppErrorKind::InternalError,
+        )?;
+        Ok(data)
+    }
+
+    fn strip_metadata_blocking(&self, data: Vec<u8>, mime_type: &str) -> AppResult<Vec<u8>> {
+        let format = match mime_type {
+            "image/gif" => return Ok(data),
+            "image/jpeg" => ImageFormat::Jpeg,
+            "image/webp" => ImageFormat::WebP,
+            _ => ImageFormat::Png,
+        };
+        self.encode(&self.decode(&data)?, format)
+    }
+
+    fn variant_blocking(&self, data: Vec<u8>, size: u32) -> AppResult<Vec<u8>> {
+        let mut image = self.decode(&data)?;
+        // fits the box keeping the aspect ratio, smaller images are not enlarged
+        if image.width() > size || image.height() > size {
+            image = image.resize(size, size, FilterType::Lanczos3);
+        }
+        self.encode(&image, self.variant_format)
+    }
+
+    // decoding and encoding keep a core busy, so they stay off the async workers
+    async fn blocking<F>(&self, f: F) -> AppResult<Vec<u8>>
+    where
+        F: FnOnce(Self) -> AppResult<Vec<u8>> + Send + 'static,
+    {
+        let image_utils = self.clone();
+        tokio::task::spawn_blocking(move || f(image_utils))
+            .await
+            .wrap("Image processing task failed", AppErrorKind::InternalError)?
+    }
+}
+
+#[async_trait]
+impl ImageUtilsTrait for RasterImageUtils {
+    async fn strip_metadata(&self, data: Vec<u8>, mime_type: &str) -> AppResult<Vec<u8>> {
+        let mime_type = mime_type.to_string();
+        self.blocking(move |image_utils| image_utils.strip_metadata_blocking(data, &mime_type))
+            .await
+    }
+
+    async fn variant(&self, data: Vec<u8>, size: u32) -> AppResult<Vec<u8>> {
+        self.blocking(move |image_utils| image_utils.variant_blocking(data, size))
+            .await
+    }
+
+    fn variant_sizes(&self) -> &[u32] {
+        &self.variant_sizes
+    }
+
+    fn variant_mime_type(&self) -> &'static str {
+        match self.variant_format {
+            ImageFormat::Jpeg => "image/jpeg",
+            _ => "image/webp",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgb, RgbImage};
+
+    use super::*;
+    use crate::app::utils::storage::sniff_mime;
+
+    // a jpeg with an exif block holding the orientation tag and a fake gps marker
+    fn jpeg_with_exif(width: u32, height: u32, orientation: u8) -> Vec<u8> {
+        let image = RgbImage::from_fn(width, height, |x, y| Rgb([x as u8, y as u8, 128]));
+        let mut data = Vec::new();
+        image
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut data, 90))
+            .unwrap();
+        let mut exif = b"Exif\0\0MM\0\x2a\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01\0".to_vec();
+        exif.extend_from_slice(&[orientation, 0, 0, 0, 0, 0, 0]);
+        exif.extend_from_slice(b"GPSLatitude");
+        let len = (exif.len() + 2) as u16;
+        let mut segment = vec![0xff, 0xe1];
+        segment.extend_from_slice(&len.to_be_bytes());
+        segment.extend_from_slice(&exif);
+        data.splice(2..2, segment);
+        data
+    }
+
+    fn contains(data: &[u8], needle: &[u8]) -> bool {
+        data.windows(needle.len()).any(|window| window == needle)
+    }
+
+    #[tokio::test]
+    async fn test_strip_metadata() {
+        let image_utils = RasterImageUtils::new(&ImageConfig::default()).unwrap();
+        let data = jpeg_with_exif(40, 20, 6);
+        assert!(contains(&data, b"GPSLatitude"));
+
+        let stripped = image_utils
+            .strip_metadata(data, "image/jpeg")
+            .await
+            .unwrap();
+        assert!(!contains(&stripped, b"Exif"));
+        assert!(!contains(&stripped, b"GPSLatitude"));
+        assert_eq!(sniff_mime(&stripped), Some("image/jpeg"));
+        // rotated by 90 degrees as the orientation said
+        let image = image::load_from_memory(&stripped).unwrap();
+        assert_eq!((image.width(), image.height()), (20, 40));
+
+        let gif = b"GIF89a\x01\0\x01\0".to_vec();
+        assert_eq!(
+            image_utils
+                .strip_metadata(gif.clone(), "image/gif")
+                .await
+                .unwrap(),
+            gif
+        );
+        assert!(image_utils
+            .strip_metadata(b"\xff\xd8\xffnot a jpeg".to_vec(), "image/jpeg")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_variant() {
+        let image_utils = RasterImageUtils::new(&ImageConfig::default()).unwrap();
+        let data = jpeg_with_exif(300, 150, 1);
+
+        let variant = image_utils.variant(data.clone(), 64).await.unwrap();
+        assert_eq!(sniff_mime(&variant), Some("image/webp"));
+        let image = image::load_from_memory(&variant).unwrap();
+        assert_eq!((image.width(), image.height()), (64, 32));
+        // never enlarged
+        let variant = image_utils.variant(data, 512).await.unwrap();
+        let image = image::load_from_memory(&variant).unwrap();
+        assert_eq!((image.width(), image.height()), (300, 150));
+
+        let cfg = ImageConfig {
+            variant_format: "avif".into(),
+            ..Default::default()
+        };
+        assert!(RasterImageUtils::new(&cfg).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+// ********************* interface ********************* //
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TocItem {
+    pub level: u8,
+    pub text: String,
+    pub anchor: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderedContent {
+    pub html: String,
+    pub toc: Vec<TocItem>,
+}
+
+pub trait RenderUtilsTrait {
+    fn render(&self, markdown: &str) -> RenderedContent;
+    fn excerpt(&self, markdown: &str) -> String;
+}
+
+pub trait RenderUtilsProvider {
+    type RenderUtils: RenderUtilsTrait;
+    fn render_utils(&self) -> &Self::RenderUtils;
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct RenderConfig {
+    // max number of characters of an auto-generated description
+    pub excerpt_len: usize,
+    pub cache_expire_sec: u64,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self {
+            excerpt_len: 200,
+            cache_expire_sec: 86_400,
+        }
+    }
+}
+
+// ********************* implementation ********************* //
+use std::collections::HashMap;
+
+use ammonia::Builder;
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd};
+
+// ammonia prefixes every user supplied id with it, so raw html cannot clobber anchors
+const ANCHOR_PREFIX: &str = "toc-";
+
+pub struct MarkdownRenderUtils {
+    excerpt_len: usize,
+    sanitizer: Builder<'static>,
+}
+
+impl MarkdownRenderUtils {
+    pub fn new(cfg: &RenderConfig) -> Self {
+        let mut sanitizer = Builder::default();
+        sanitizer
+            .add_tag_attributes("code", &["class"])
+            .id_prefix(Some(ANCHOR_PREFIX));
+        for tag in ["h1", "h2", "h3", "h4", "h5", "h6"] {
+            sanitizer.add_tag_attributes(tag, &["id"]);
+        }
+        Self {
+            excerpt_len: cfg.excerpt_len,
+            sanitizer,
+        }
+    }
+
+    fn parser(markdown: &str) -> Parser<'_> {
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_FOOTNOTES);
+        options.insert(Options::ENABLE_STRIKETHROUGH);
+        Parser::new_ext(markdown, options)
+    }
+}
+
+// lowercase words joined by '-', non-ascii letters such as CJK are kept as is
+fn slugify(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+// same headings get "-1", "-2"... appended in document order
+fn unique_anchor(text: &str, used: &mut HashMap<String, usize>) -> String {
+    let slug = match slugify(text) {
+        slug if slug.is_empty() => "section".to_string(),
+        slug => slug,
+    };
+    let anchor = match used.get(&slug).copied() {
+        None => slug.clone(),
+        Some(count) => format!("{}-{}", slug, count),
+    };
+    *used.entry(slug).or_insert(0) += 1;
+    format!("{}{}", ANCHOR_PREFIX, anchor)
+}
+
+impl RenderUtilsTrait for MarkdownRenderUtils {
+    fn render(&self, markdown: &str) -> RenderedContent {
+        let mut events: Vec<Event> = Self::parser(markdown).collect();
+
+        // fill in heading ids and collect the toc
+        let mut toc = Vec::new();
+        let mut used = HashMap::new();
+        let mut heading: Option<(usize, String)> = None;
+        for i in 0..events.len() {
+            match &events[i] {
+                Event::Start(Tag::Heading { .. }) => heading = Some((i, String::new())),
+                Event::Text(text) | Event::Code(text) => {
+                    if let Some((_, heading_text)) = heading.as_mut() {
+                        heading_text.push_str(text.as_ref());
+                    }
+                }
+                Event::End(TagEnd::Heading(level)) => {
+                    let level = *level as u8;
+                    if let Some((start, text)) = heading.take() {
+                        let anchor = unique_anchor(&text, &mut used);
+                        if let Event::Start(Tag::Heading { id, .. }) = &mut events[start] {
+                            *id = Some(CowStr::from(anchor.clone()));
+                        }
+                        toc.push(TocItem {
+                            level,
+                            text: text.trim().to_string(),
+                            anchor,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut unsafe_html = String::with_capacity(markdown.len() * 3 / 2);
+        html::push_html(&mut unsafe_html, events.into_iter());
+        RenderedContent {
+            html: self.sanitizer.clean(&unsafe_html).to_string(),
+            toc,
+        }
+    }
+
+    fn excerpt(&self, markdown: &str) -> String {
+        let mut text = String::new();
+        let mut in_code_block = false;
+        for event in Self::parser(markdown) {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                Event::End(TagEnd::CodeBlock) => in_code_block = false,
+                Event::Text(t) | Event::Code(t) if !in_code_block => text.push_str(&t),
+                Event::SoftBreak
+                | Event::HardBreak
+                | Event::End(
+                    TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::TableCell,
+                ) => text.push(' '),
+                _ => {}
+            }
+        }
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let text = words.join(" ");
+        match text.char_indices().nth(self.excerpt_len) {
+            Some((end, _)) => format!("{}…", text[..end].trim_end()),
+            None => text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let render_utils = MarkdownRenderUtils::new(&RenderConfig::default());
+        let markdown = "# Hello World\n\n## 简介\n\n## Hello World\n\n\
+                        <script>alert(1)</script>\n\n[link](javascript:alert(1))";
+        let rendered = render_utils.render(markdown);
+
+        let anchors: Vec<&str> = rendered.toc.iter().map(|t| t.anchor.as_str()).collect();
+        assert_eq!(
+            anchors,
+            ["toc-hello-world", "toc-简介", "toc-hello-world-1"]
+        );
+        assert_eq!(rendered.toc[1].level, 2);
+        assert!(rendered.html.contains("<h1 id=\"toc-hello-world\">"));
+        assert!(!rendered.html.contains("<script"));
+        assert!(!rendered.html.contains("javascript:"));
+
+        // raw html ids are prefixed as well
+        let rendered = render_utils.render("<h2 id=\"x\">x</h2>");
+        assert!(rendered.html.contains("id=\"toc-x\""));
+    }
+
+    #[test]
+    fn test_excerpt() {
+        let render_utils = MarkdownRenderUtils::new(&RenderConfig {
+            excerpt_len: 10,
+            ..Default::default()
+        });
+        assert_eq!(
+            render_utils.excerpt("# Title\n\nsome *text*"),
+            "Title some…"
+        );
+        assert_eq!(render_utils.excerpt("```\ncode\n```\nshort"), "short");
+    }
+}
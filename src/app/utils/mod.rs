@@ -1,15 +1,41 @@
 pub mod cache;
 pub mod crypto;
+pub mod front_matter;
+pub mod identicon;
+pub mod image;
 pub mod leak;
 pub mod log;
+pub mod markdown;
 pub mod page;
+pub mod search;
+pub mod slug;
+pub mod storage;
 pub mod token;
+pub mod xml;
 
 pub mod prelude {
     pub use super::cache::{CacheConfig, CacheUtilsProvider, CacheUtilsTrait, RedisCacheUtils};
     pub use super::crypto::{CryptoUtilsProvider, CryptoUtilsTrait, Pbkdf2CryptoUtils};
+    pub use super::front_matter::{format_markdown, parse_markdown, FrontMatter, MarkdownDoc};
+    pub use super::identicon::Identicon;
+    pub use super::image::{ImageConfig, ImageUtilsProvider, ImageUtilsTrait, RasterImageUtils};
     pub use super::leak::Leak;
     pub use super::log::{init_logging, LogConfig};
+    pub use super::markdown::{
+        MarkdownRenderUtils, RenderConfig, RenderUtilsProvider, RenderUtilsTrait, RenderedContent,
+        TocItem,
+    };
     pub use super::page::Page;
+    pub use super::search::{
+        is_cjk, InvertedSearchIndex, SearchConfig, SearchDoc, SearchHit, SearchIndex,
+        SearchIndexProvider,
+    };
+    pub use super::slug::{slugify, unique_slug, SLUG_MAX_LEN};
+    pub use super::storage::{
+        is_valid_storage_key, mime_extension, sniff_mime, storage_key, AnyStorageBackend,
+        LocalStorageBackend, S3StorageBackend, StorageBackend, StorageBackendProvider,
+        StorageConfig,
+    };
     pub use super::token::{Claims, JwtTokenUtils, TokenUtilsProvider, TokenUtilsTrait};
+    pub use super::xml::{escape_xml, http_date, to_utc};
 }
@@ -0,0 +1,340 @@
+// ********************* interface ********************* //
+use serde::Deserialize;
+
+#[derive(Debug, Clone)]
+pub struct SearchDoc {
+    pub id: i32,
+    pub title: String,
+    pub content: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub id: i32,
+    pub score: f64,
+}
+
+pub trait SearchIndex {
+    // insert the doc or replace the one with the same id
+    fn upsert(&self, doc: SearchDoc);
+    fn remove(&self, id: i32);
+    // drop every doc and index the given ones instead
+    fn replace_all(&self, docs: Vec<SearchDoc>);
+    // return the total number of hits and the requested page, most relevant first
+    fn search(&self, query: &str, offset: usize, limit: usize) -> (usize, Vec<SearchHit>);
+    // titles and tag names containing the prefix, those starting with it first
+    fn suggest(&self, prefix: &str, limit: usize) -> Vec<String>;
+}
+
+pub trait SearchIndexProvider {
+    type SearchIndex: SearchIndex;
+    fn search_index(&self) -> &Self::SearchIndex;
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    // `index` keeps an in-memory index in every instance, rebuilt from the database on the
+    // first search after any article or tag change, which suits small sites
+    pub engine: String, // database index
+    pub suggest_limit: usize,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            engine: "database".into(),
+            suggest_limit: 10,
+        }
+    }
+}
+
+// ********************* implementation ********************* //
+use std::{
+    collections::{HashMap, HashSet},
+    sync::RwLock,
+};
+
+const TITLE_WEIGHT: f64 = 3.0;
+const TAG_WEIGHT: f64 = 2.0;
+const CONTENT_WEIGHT: f64 = 1.0;
+
+pub fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'     // hiragana, katakana
+        | '\u{3400}'..='\u{4DBF}'   // cjk extension a
+        | '\u{4E00}'..='\u{9FFF}'   // cjk unified ideographs
+        | '\u{AC00}'..='\u{D7AF}'   // hangul syllables
+        | '\u{F900}'..='\u{FAFF}'   // cjk compatibility ideographs
+        | '\u{20000}'..='\u{2A6DF}' // cjk extension b
+    )
+}
+
+// latin words are lowercased, CJK runs are split into overlapping bigrams, plus
+// unigrams when indexing so that single character queries still match
+fn tokenize(text: &str, with_unigrams: bool) -> Vec<String> {
+    fn flush_cjk(run: &mut Vec<char>, with_unigrams: bool, tokens: &mut Vec<String>) {
+        if run.len() == 1 || with_unigrams {
+            tokens.extend(run.iter().map(|c| c.to_string()));
+        }
+        tokens.extend(run.windows(2).map(|pair| pair.iter().collect::<String>()));
+        run.clear();
+    }
+
+    let mut tokens = Vec::new();
+    let mut cjk_run = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if is_cjk(c) {
+            if !word.is_empty() {
+                tokens.push(std::mem::take(&mut word));
+            }
+            cjk_run.push(c);
+            continue;
+        }
+        if !cjk_run.is_empty() {
+            flush_cjk(&mut cjk_run, with_unigrams, &mut tokens);
+        }
+        if c.is_alphanumeric() {
+            word.extend(c.to_lowercase());
+        } else if !word.is_empty() {
+            tokens.push(std::mem::take(&mut word));
+        }
+    }
+    if !cjk_run.is_empty() {
+        flush_cjk(&mut cjk_run, with_unigrams, &mut tokens);
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+    tokens
+}
+
+struct IndexedDoc {
+    title: String,
+    tags: Vec<String>,
+    // weighted term frequency
+    terms: HashMap<String, f64>,
+}
+
+#[derive(Default)]
+struct IndexState {
+    docs: HashMap<i32, IndexedDoc>,
+    postings: HashMap<String, HashSet<i32>>,
+}
+
+impl IndexState {
+    fn insert(&mut self, doc: SearchDoc) {
+        let mut terms: HashMap<String, f64> = HashMap::new();
+        let fields = [
+            (doc.title.clone(), TITLE_WEIGHT),
+            (doc.tags.join(" "), TAG_WEIGHT),
+            (doc.content, CONTENT_WEIGHT),
+        ];
+        for (text, weight) in fields {
+            for token in tokenize(&text, true) {
+                *terms.entry(token).or_default() += weight;
+            }
+        }
+
+        self.remove(doc.id);
+        for term in terms.keys() {
+            self.postings
+                .entry(term.clone())
+                .or_default()
+                .insert(doc.id);
+        }
+        self.docs.insert(
+            doc.id,
+            IndexedDoc {
+                title: doc.title,
+                tags: doc.tags,
+                terms,
+            },
+        );
+    }
+
+    fn remove(&mut self, id: i32) {
+        let Some(doc) = self.docs.remove(&id) else {
+            return;
+        };
+        for term in doc.terms.keys() {
+            if let Some(ids) = self.postings.get_mut(term) {
+                ids.remove(&id);
+                if ids.is_empty() {
+                    self.postings.remove(term);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct InvertedSearchIndex {
+    state: RwLock<IndexState>,
+}
+
+impl InvertedSearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SearchIndex for InvertedSearchIndex {
+    fn upsert(&self, doc: SearchDoc) {
+        self.state
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(doc);
+    }
+
+    // the new state is built aside, searches keep using the old one until the swap
+    fn replace_all(&self, docs: Vec<SearchDoc>) {
+        let mut new_state = IndexState::default();
+        for doc in docs {
+            new_state.insert(doc);
+        }
+        *self.state.write().unwrap_or_else(|e| e.into_inner()) = new_state;
+    }
+
+    fn remove(&self, id: i32) {
+        self.state
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(id);
+    }
+
+    fn search(&self, query: &str, offset: usize, limit: usize) -> (usize, Vec<SearchHit>) {
+        let terms: HashSet<String> = tokenize(query, false).into_iter().collect();
+        if terms.is_empty() {
+            return (0, Vec::new());
+        }
+        let state = self.state.read().unwrap_or_else(|e| e.into_inner());
+
+        // every term has to match, start from the rarest one
+        let mut postings = Vec::with_capacity(terms.len());
+        for term in &terms {
+            match state.postings.get(term) {
+                Some(ids) => postings.push((term, ids)),
+                None => return (0, Vec::new()),
+            }
+        }
+        postings.sort_by_key(|(_, ids)| ids.len());
+        let doc_num = state.docs.len() as f64;
+        let mut hits: Vec<SearchHit> = postings[0]
+            .1
+            .iter()
+            .filter(|id| postings[1..].iter().all(|(_, ids)| ids.contains(id)))
+            .map(|id| {
+                let doc = &state.docs[id];
+                let score = postings
+                    .iter()
+                    .map(|(term, ids)| {
+                        let idf = (1.0 + doc_num / ids.len() as f64).ln();
+                        doc.terms[*term] * idf
+                    })
+                    .sum();
+                SearchHit { id: *id, score }
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then(b.id.cmp(&a.id)));
+        let total = hits.len();
+        (total, hits.into_iter().skip(offset).take(limit).collect())
+    }
+
+    fn suggest(&self, prefix: &str, limit: usize) -> Vec<String> {
+        let prefix = prefix.trim().to_lowercase();
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+        let state = self.state.read().unwrap_or_else(|e| e.into_inner());
+        let mut seen = HashSet::new();
+        let mut candidates: Vec<(bool, &str)> = state
+            .docs
+            .values()
+            .flat_map(|doc| std::iter::once(&doc.title).chain(doc.tags.iter()))
+            .filter_map(|text| {
+                let lower = text.to_lowercase();
+                lower
+                    .find(&prefix)
+                    .filter(|_| seen.insert(text.as_str()))
+                    .map(|pos| (pos != 0, text.as_str()))
+            })
+            .collect();
+        candidates.sort();
+        candidates
+            .into_iter()
+            .take(limit)
+            .map(|(_, text)| text.to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(id: i32, title: &str, content: &str, tags: &[&str]) -> SearchDoc {
+        SearchDoc {
+            id,
+            title: title.to_string(),
+            content: content.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(tokenize("Rust 数据库!", false), ["rust", "数据", "据库"]);
+        assert_eq!(tokenize("数", false), ["数"]);
+        assert_eq!(tokenize("a数据", true), ["a", "数", "据", "数据"]);
+    }
+
+    #[test]
+    fn test_search_index() {
+        let index = InvertedSearchIndex::new();
+        index.upsert(doc(1, "Rust 入门", "所有权与借用", &["编程"]));
+        index.upsert(doc(2, "数据库设计", "MySQL 与 Rust", &["数据库"]));
+        index.upsert(doc(3, "随笔", "今天天气不错", &[]));
+
+        // title matches weigh more than content matches
+        let (total, hits) = index.search("rust", 0, 10);
+        assert_eq!(total, 2);
+        assert_eq!(hits[0].id, 1);
+
+        // CJK queries match through bigrams, tags are searchable
+        let (_, hits) = index.search("数据库", 0, 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, 2);
+        let (_, hits) = index.search("编程", 0, 10);
+        assert_eq!(hits[0].id, 1);
+        let (_, hits) = index.search("天", 0, 10);
+        assert_eq!(hits[0].id, 3);
+
+        // all terms must match
+        assert_eq!(index.search("rust 天气", 0, 10).0, 0);
+
+        // pagination
+        let (total, hits) = index.search("rust", 1, 10);
+        assert_eq!(total, 2);
+        assert_eq!(hits.len(), 1);
+
+        // update and remove
+        index.upsert(doc(1, "Go 入门", "", &[]));
+        assert_eq!(index.search("rust", 0, 10).0, 1);
+        index.remove(2);
+        assert_eq!(index.search("rust", 0, 10).0, 0);
+
+        // suggest
+        index.upsert(doc(4, "Rust 进阶", "", &["rust"]));
+        index.upsert(doc(5, "学习 Rust", "", &[]));
+        assert_eq!(index.suggest("ru", 10)[2], "学习 Rust");
+        assert_eq!(index.suggest("ru", 2).len(), 2);
+
+        // replace all
+        index.replace_all(vec![doc(6, "数据库", "", &[])]);
+        assert_eq!(index.search("rust", 0, 10).0, 0);
+        assert_eq!(index.search("数据库", 0, 10).1[0].id, 6);
+    }
+}
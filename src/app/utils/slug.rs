@@ -0,0 +1,60 @@
+// ********************* import ********************* //
+use std::collections::HashSet;
+
+use deunicode::deunicode;
+
+// ********************* content ********************* //
+pub const SLUG_MAX_LEN: usize = 96;
+
+// transliterate to ascii (CJK becomes pinyin-like syllables), keep lowercase
+// letters and digits joined by '-', fall back when nothing is left
+pub fn slugify(text: &str, fallback: &str) -> String {
+    let mut slug = String::new();
+    for word in deunicode(text)
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        if slug.len() + word.len() + 1 > SLUG_MAX_LEN {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word.to_ascii_lowercase());
+    }
+    match slug.is_empty() {
+        true => fallback.to_string(),
+        false => slug,
+    }
+}
+
+// append "-2", "-3"... until the slug is not taken
+pub fn unique_slug(base: &str, taken: &HashSet<String>) -> String {
+    if !taken.contains(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|slug| !taken.contains(slug))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Hello, World!", "article"), "hello-world");
+        assert_eq!(slugify("Rust 数据库", "article"), "rust-shu-ju-ku");
+        assert_eq!(slugify("？！", "article"), "article");
+        assert!(slugify(&"word ".repeat(100), "article").len() <= SLUG_MAX_LEN);
+    }
+
+    #[test]
+    fn test_unique_slug() {
+        let taken = HashSet::from(["rust".to_string(), "rust-2".to_string()]);
+        assert_eq!(unique_slug("go", &taken), "go");
+        assert_eq!(unique_slug("rust", &taken), "rust-3");
+    }
+}
@@ -0,0 +1,56 @@
+// ********************* import ********************* //
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+
+// ********************* content ********************* //
+// text and attribute values alike, control characters xml 1.0 forbids are dropped
+pub fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+// the database stores local time without an offset
+pub fn to_utc(time: NaiveDateTime) -> DateTime<Utc> {
+    time.and_local_timezone(Local)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(|| time.and_utc())
+}
+
+// the IMF-fixdate form of RFC 7231, also valid as an RFC 822 date in RSS
+pub fn http_date(time: DateTime<Utc>) -> String {
+    time.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("<a href=\"x\">Tom & Jerry's</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&apos;s&lt;/a&gt;"
+        );
+        assert_eq!(escape_xml("a\u{0}b\nc"), "ab\nc");
+    }
+
+    #[test]
+    fn test_http_date() {
+        let time = Utc.with_ymd_and_hms(2024, 3, 5, 7, 8, 9).unwrap();
+        assert_eq!(http_date(time), "Tue, 05 Mar 2024 07:08:09 GMT");
+    }
+}
@@ -1,6 +1,13 @@
-use space_backend_lib::prelude::App;
+use space_backend_lib::prelude::{App, Command};
 
 #[tokio::main]
 async fn main() {
-    App::run().await.expect("run app failed");
+    let command = match Command::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}", e.cause);
+            std::process::exit(2);
+        }
+    };
+    App::exec(command).await.expect("run app failed");
 }